@@ -48,5 +48,6 @@ build_exceptions! {
     ScriptEvalFailed(1008),
     TransientException(1009),
     ExecutionException(1009),
+    IllegalState(1010),
     UnknownException(1999),
 }
@@ -7,7 +7,7 @@ mod run;
 mod utils;
 
 pub use metadata::{
-    RetryLogic, SubWorkflowParams, TaskDef, TaskTimeoutPolicy, TaskType, TimeoutPolicy,
+    JoinMode, RetryLogic, SubWorkflowParams, TaskDef, TaskTimeoutPolicy, TaskType, TimeoutPolicy,
     WorkflowDef, WorkflowTask,
 };
 pub use run::{
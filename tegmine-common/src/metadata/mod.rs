@@ -2,4 +2,4 @@ mod tasks;
 mod workflow;
 
 pub use tasks::{RetryLogic, TaskDef, TaskType, TimeoutPolicy as TaskTimeoutPolicy};
-pub use workflow::{SubWorkflowParams, TimeoutPolicy, WorkflowDef, WorkflowTask};
+pub use workflow::{JoinMode, SubWorkflowParams, TimeoutPolicy, WorkflowDef, WorkflowTask};
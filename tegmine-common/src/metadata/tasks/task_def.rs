@@ -251,15 +251,69 @@ impl TryFrom<&serde_json::Value> for TaskDef {
                 .into(),
             isolation_group_id: InlineStr::default(),
             execution_name_space: InlineStr::default(),
-            backoff_scale_factor: 1,
-            created_by: InlineStr::default(),
-            create_time: 0,
-            updated_by: InlineStr::default(),
-            update_time: 0,
+            backoff_scale_factor: value
+                .get("backoffScaleFactor")
+                .and_then(|x| x.as_i64())
+                .map(|x| x as i32)
+                .unwrap_or(1),
+            created_by: value
+                .get("createdBy")
+                .and_then(|x| x.as_str())
+                .unwrap_or("")
+                .into(),
+            create_time: value
+                .get("createTime")
+                .and_then(|x| x.as_i64())
+                .unwrap_or(0),
+            updated_by: value
+                .get("updatedBy")
+                .and_then(|x| x.as_str())
+                .unwrap_or("")
+                .into(),
+            update_time: value
+                .get("updateTime")
+                .and_then(|x| x.as_i64())
+                .unwrap_or(0),
         })
     }
 }
 
+impl TaskDef {
+    /// Converts to the json representation accepted by `TaskDef::try_from`
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "name": self.name.as_str(),
+            "description": self.description.as_str(),
+            "retryCount": self.retry_count,
+            "retryLogic": self.retry_logic.as_ref(),
+            "retryDelaySeconds": self.retry_delay_seconds,
+            "timeoutPolicy": self.timeout_policy.as_ref(),
+            "timeoutSeconds": self.timeout_seconds,
+            "responseTimeoutSeconds": self.response_timeout_seconds,
+            "pollTimeoutSeconds": self.poll_timeout_seconds,
+            "inputKeys": self.input_keys.iter().map(|x| x.as_str()).collect::<Vec<_>>(),
+            "outputKeys": self.output_keys.iter().map(|x| x.as_str()).collect::<Vec<_>>(),
+            "inputTemplate": Object::convert_hashmap_to_json(&self.input_template),
+            "ownerEmail": self.owner_email.as_str(),
+            "backoffScaleFactor": self.backoff_scale_factor,
+            "createdBy": self.created_by.as_str(),
+            "createTime": self.create_time,
+            "updatedBy": self.updated_by.as_str(),
+            "updateTime": self.update_time,
+        });
+        if let Some(concurrent_exec_limit) = self.concurrent_exec_limit {
+            json["concurrentExecLimit"] = concurrent_exec_limit.into();
+        }
+        if let Some(rate_limit_frequency_in_seconds) = self.rate_limit_frequency_in_seconds {
+            json["rateLimitFrequencyInSeconds"] = rate_limit_frequency_in_seconds.into();
+        }
+        if let Some(rate_limit_per_frequency) = self.rate_limit_per_frequency {
+            json["rateLimitPerFrequency"] = rate_limit_per_frequency.into();
+        }
+        json
+    }
+}
+
 #[derive(Clone, Copy, Debug, AsRefStr, EnumString)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum TimeoutPolicy {
@@ -4,4 +4,4 @@ mod workflow_task;
 
 pub use sub_workflow_params::SubWorkflowParams;
 pub use workflow_def::{TimeoutPolicy, WorkflowDef};
-pub use workflow_task::WorkflowTask;
+pub use workflow_task::{JoinMode, WorkflowTask};
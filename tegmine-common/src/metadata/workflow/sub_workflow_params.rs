@@ -1,2 +1,98 @@
+use crate::prelude::*;
+use crate::WorkflowDef;
+
+/// The parameters of a SUB_WORKFLOW task, identifying the workflow to be started as the child.
 #[derive(Clone, Debug)]
-pub struct SubWorkflowParams;
+pub struct SubWorkflowParams {
+    /// Name of the workflow to execute
+    pub name: InlineStr,
+    /// Version of the workflow to execute, the latest version is used if not set
+    pub version: Option<i32>,
+    /// Allows scheduling the sub workflow's tasks per given mappings. If empty, the parent
+    /// workflow's domain mappings are used.
+    pub task_to_domain: HashMap<InlineStr, InlineStr>,
+    /// Workflow definition to be executed instead of the registered one, `name` and `version`
+    /// are ignored when it is set
+    pub workflow_definition: Option<Box<WorkflowDef>>,
+}
+
+impl TryFrom<&serde_json::Value> for SubWorkflowParams {
+    type Error = ErrorCode;
+    fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
+        let workflow_definition = match value.get("workflowDefinition") {
+            Some(json) if !json.is_null() => Some(Box::new(WorkflowDef::try_from(json)?)),
+            _ => None,
+        };
+
+        let name: InlineStr = match value.get("name") {
+            Some(json) if !json.is_null() => json
+                .as_str()
+                .ok_or_else(|| ErrorCode::IllegalArgument("SubWorkflowParams: name invalid"))?
+                .trim()
+                .into(),
+            _ => InlineStr::new(),
+        };
+        let name = match workflow_definition.as_ref() {
+            Some(workflow_def) => workflow_def.name.clone(),
+            None if name.is_empty() => {
+                return str_err!(
+                    IllegalArgument,
+                    "SubWorkflowParams: name not found, workflowDefinition or name is required"
+                );
+            }
+            None => name,
+        };
+
+        let version = if let Some(json) = value.get("version").filter(|x| !x.is_null()) {
+            let version = json
+                .as_i64()
+                .ok_or_else(|| ErrorCode::IllegalArgument("SubWorkflowParams: version invalid"))?;
+            Some(version as i32)
+        } else {
+            None
+        };
+
+        let mut task_to_domain = HashMap::default();
+        if let Some(json) = value.get("taskToDomain").filter(|x| !x.is_null()) {
+            for (k, v) in json.as_object().ok_or_else(|| {
+                ErrorCode::IllegalArgument("SubWorkflowParams: taskToDomain invalid")
+            })? {
+                let domain = v.as_str().ok_or_else(|| {
+                    ErrorCode::IllegalArgument("SubWorkflowParams: taskToDomain invalid")
+                })?;
+                task_to_domain.insert(k.into(), domain.trim().into());
+            }
+        }
+
+        Ok(Self {
+            name,
+            version,
+            task_to_domain,
+            workflow_definition,
+        })
+    }
+}
+
+impl SubWorkflowParams {
+    /// Converts to the json representation accepted by `SubWorkflowParams::try_from`
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "name": self.name.as_str(),
+        });
+        if let Some(version) = self.version {
+            json["version"] = version.into();
+        }
+        if !self.task_to_domain.is_empty() {
+            json["taskToDomain"] = serde_json::Value::Object(
+                self.task_to_domain
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), serde_json::Value::from(v.as_str())))
+                    .collect(),
+            );
+        }
+        if let Some(workflow_definition) = self.workflow_definition.as_ref() {
+            json["workflowDefinition"] = workflow_definition.to_json();
+        }
+        json
+    }
+}
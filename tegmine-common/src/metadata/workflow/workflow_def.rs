@@ -97,10 +97,14 @@ impl WorkflowDef {
         tasks
     }
 
-    pub fn populate_tasks(&mut self, populate_fn: fn(&mut WorkflowTask)) {
+    pub fn populate_tasks(
+        &mut self,
+        populate_fn: fn(&mut WorkflowTask) -> TegResult<()>,
+    ) -> TegResult<()> {
         for workflow_task in &mut self.tasks {
-            workflow_task.populate_tasks(populate_fn);
+            workflow_task.populate_tasks(populate_fn)?;
         }
+        Ok(())
     }
 }
 
@@ -229,9 +233,43 @@ impl TryFrom<&serde_json::Value> for WorkflowDef {
                     .trim(),
             )
             .map_err(|_| ErrorCode::IllegalArgument("WorkflowDef: timeoutPolicy invalid"))?,
-            variables: HashMap::default(),
-            create_time: 0,
-            update_time: 0,
+            variables: value
+                .get("variables")
+                .and_then(|x| x.as_object())
+                .map(|x| Object::convert_jsonmap_to_hashmap(x))
+                .unwrap_or_default(),
+            create_time: value
+                .get("createTime")
+                .and_then(|x| x.as_i64())
+                .unwrap_or(0),
+            update_time: value
+                .get("updateTime")
+                .and_then(|x| x.as_i64())
+                .unwrap_or(0),
+        })
+    }
+}
+
+impl WorkflowDef {
+    /// Converts to the json representation accepted by `WorkflowDef::try_from`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name.as_str(),
+            "description": self.description.as_str(),
+            "version": self.version,
+            "tasks": WorkflowTask::to_jsonlist(&self.tasks),
+            "inputParameters": self.input_parameters.iter().map(|x| x.as_str()).collect::<Vec<_>>(),
+            "outputParameters": Object::convert_hashmap_to_json(&self.output_parameters),
+            "inputTemplate": Object::convert_hashmap_to_json(&self.input_template),
+            "failureWorkflow": self.failure_workflow.as_str(),
+            "restartable": self.restartable,
+            "workflowStatusListenerEnabled": self.workflow_status_listener_enabled,
+            "ownerEmail": self.owner_email.as_str(),
+            "timeoutSeconds": self.timeout_seconds,
+            "timeoutPolicy": self.timeout_policy.as_ref(),
+            "variables": Object::convert_hashmap_to_json(&self.variables),
+            "createTime": self.create_time,
+            "updateTime": self.update_time,
         })
     }
 }
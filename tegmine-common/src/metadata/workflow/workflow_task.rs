@@ -1,8 +1,11 @@
+use std::str::FromStr;
+
 use linked_hash_map::LinkedHashMap;
+use strum_macros::{AsRefStr, EnumString};
 
 use crate::metadata::tasks::TaskDef;
 use crate::prelude::*;
-use crate::TaskType;
+use crate::{SubWorkflowParams, TaskType};
 
 /// This is the task definition definied as part of the `WorkflowDef`. The tasks definied in the
 /// Workflow definition are saved as part of `WorkflowDef::getTasks`
@@ -53,23 +56,36 @@ pub struct WorkflowTask {
     /// "taskToExecute"
     pub dynamic_task_name_param: InlineStr,
     /// DO_WHILE
-    /// Condition to be evaluated after every iteration. This is a Rhai expression, evaluated
-    /// using the rhai engine. If an exception occurs during evaluation, the DO_WHILE task is
-    /// set to FAILED_WITH_TERMINAL_ERROR.
+    /// Condition to be evaluated after every iteration. This is a javascript expression, evaluated
+    /// against the outputs of the loop task and the loop over tasks. If an exception occurs
+    /// during evaluation, the DO_WHILE task is set to FAILED_WITH_TERMINAL_ERROR.
     pub loop_condition: InlineStr,
     /// List of tasks that needs to be executed as long as the condition is true.
     pub loop_over: Vec<WorkflowTask>,
 
-    // /// SUB_WORKFLOW
-    // pub sub_workflow_param: Option<SubWorkflowParams>,
+    /// SUB_WORKFLOW
+    /// The workflow to be started as the child of the current workflow.
+    pub sub_workflow_param: Option<SubWorkflowParams>,
 
     // /// FORK_JOIN/JOIN
     pub fork_tasks: Vec<Vec<WorkflowTask>>,
+    /// The reference names of the tasks to wait for, empty for the JOIN of a FORK_JOIN_DYNAMIC
+    /// task as the forked tasks are only known at runtime.
     pub join_on: Vec<InlineStr>,
-
-    // /// FORK_JOIN_DYNAMIC
-    // pub dynamic_fork_tasks_param: InlineStr,
-    // pub dynamic_fork_tasks_input_param_name: InlineStr,
+    /// How many of the `join_on` tasks the JOIN waits for, the remaining ones are canceled once
+    /// the JOIN is satisfied.
+    pub join_mode: JoinMode,
+    /// The number of tasks to wait for when `join_mode` is `JoinMode::FirstN`.
+    pub join_count: i32,
+    /// The percentage of the tasks to wait for when `join_mode` is `JoinMode::Quorum`.
+    pub join_quorum: i32,
+
+    /// FORK_JOIN_DYNAMIC
+    /// Name of the parameter from inputParameters whose value is the list of the tasks to fork.
+    pub dynamic_fork_tasks_param: InlineStr,
+    /// Name of the parameter from inputParameters whose value is the map from the reference name
+    /// of every forked task to its input.
+    pub dynamic_fork_tasks_input_param_name: InlineStr,
 
     // /// Event
     // pub sink: InlineStr,
@@ -131,14 +147,18 @@ impl WorkflowTask {
         tasks
     }
 
-    pub fn populate_tasks(&mut self, populate_fn: fn(&mut WorkflowTask)) {
-        populate_fn(self);
+    pub fn populate_tasks(
+        &mut self,
+        populate_fn: fn(&mut WorkflowTask) -> TegResult<()>,
+    ) -> TegResult<()> {
+        populate_fn(self)?;
 
         for workflow_task_list in self.children_mut() {
             for workflow_task in workflow_task_list {
-                populate_fn(workflow_task);
+                populate_fn(workflow_task)?;
             }
         }
+        Ok(())
     }
 
     pub fn next<'a>(
@@ -274,33 +294,82 @@ impl TryFrom<&serde_json::Value> for WorkflowTask {
             }
         }
 
+        // START_WORKFLOW
+        if type_.eq("START_WORKFLOW") {
+            if !input_parameters.contains_key("startWorkflow") {
+                return fmt_err!(
+                    IllegalArgument,
+                    "WorkflowTask: startWorkflow in inputParameters not found when task type is START_WORKFLOW"
+                );
+            }
+        }
+
+        // HTTP
+        if type_.eq("HTTP") {
+            if !input_parameters.contains_key("http_request") {
+                return fmt_err!(
+                    IllegalArgument,
+                    "WorkflowTask: http_request in inputParameters not found when task type is HTTP"
+                );
+            }
+        }
+
+        // INLINE
+        if type_.eq("INLINE") {
+            if !input_parameters.contains_key("evaluatorType")
+                || !input_parameters.contains_key("expression")
+            {
+                return fmt_err!(
+                    IllegalArgument,
+                    "WorkflowTask: evaluatorType and expression in inputParameters not found when task type is INLINE"
+                );
+            }
+        }
+
+        // JSON_JQ_TRANSFORM
+        if type_.eq("JSON_JQ_TRANSFORM") {
+            if !input_parameters.contains_key("queryExpression") {
+                return fmt_err!(
+                    IllegalArgument,
+                    "WorkflowTask: queryExpression in inputParameters not found when task type is JSON_JQ_TRANSFORM"
+                );
+            }
+        }
+
+        // WAIT
+        if type_.eq("WAIT") {
+            if input_parameters.contains_key("duration") && input_parameters.contains_key("until") {
+                return fmt_err!(
+                    IllegalArgument,
+                    "WorkflowTask: both duration and until in inputParameters when task type is WAIT"
+                );
+            }
+        }
+
         // DO_WHILE
         let (loop_condition, loop_over) = Self::loop_try_from(&type_, value)?;
 
         // FORK_JOIN
         let fork_tasks = Self::fork_join_try_from(&type_, value)?;
 
+        // FORK_JOIN_DYNAMIC
+        let (dynamic_fork_tasks_param, dynamic_fork_tasks_input_param_name) =
+            Self::fork_join_dynamic_try_from(&type_, &input_parameters, value)?;
+
         // JOIN
-        let join_on = Self::join_try_from(&type_, value, &fork_tasks)?;
+        let (join_on, join_mode, join_count, join_quorum) = Self::join_try_from(&type_, value)?;
 
         // EXCLUSIVE_JOIN
         let (exclusive_join_on, default_exclusive_join_task) =
             Self::exclusive_join_try_from(&type_, value)?;
 
+        // SUB_WORKFLOW
+        let sub_workflow_param = Self::sub_workflow_try_from(&type_, value)?;
+
         // TODO
         {
-            // START_WORKFLOW -> inputParameters<startWorkflow>
-            // SUB_WORKFLOW -> subWorkflowParam
-
-            // FORK_JOIN_DYNAMIC -> dynamicForkTasksParam, dynamicForkTasksInputParamName,
-            // inputParameters<dynamicTasks, dynamicTasksInput>
-            //
             // EVENT -> sink, asyncComplete
-            // HTTP -> inputParameters<http_request>
-            // INLINE -> inputParameters<evaluatorType, expression>
-            // JSON_JQ_TRANSFORM -> inputParameters<queryExpression>
             // KAFKA_PUBLISH -> inputParameters<kafka_request, ...>
-            // WAIT -> inputParameters<duration or until>
         }
 
         // ad hoc TaskDef
@@ -358,14 +427,21 @@ impl TryFrom<&serde_json::Value> for WorkflowTask {
             dynamic_task_name_param,
             loop_condition,
             loop_over,
-            // sub_workflow_param: (),
+            sub_workflow_param,
             fork_tasks,
             join_on,
-            // dynamic_fork_tasks_param: (),
-            // dynamic_fork_tasks_input_param_name: (),
+            join_mode,
+            join_count,
+            join_quorum,
+            dynamic_fork_tasks_param,
+            dynamic_fork_tasks_input_param_name,
             // sink: (),
             // rate_limited: (),
-            retry_count: 0,
+            retry_count: value
+                .get("retryCount")
+                .and_then(|x| x.as_i64())
+                .map(|x| x as i32)
+                .unwrap_or(0),
             task_definition,
             // case_value_param: (),
             // case_expression: (),
@@ -376,14 +452,150 @@ impl TryFrom<&serde_json::Value> for WorkflowTask {
 }
 
 impl WorkflowTask {
+    /// Converts to the json representation accepted by `WorkflowTask::try_from`
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "name": self.name.as_str(),
+            "taskReferenceName": self.task_reference_name.as_str(),
+            "type": self.type_.as_str(),
+            "description": self.description.as_str(),
+            "optional": self.optional,
+            "inputParameters": Object::convert_hashmap_to_json(&self.input_parameters),
+            "asyncComplete": self.async_complete,
+            "startDelay": self.start_delay,
+            "retryCount": self.retry_count,
+        });
+
+        // SWITCH
+        if !self.evaluator_type.is_empty() {
+            json["evaluatorType"] = self.evaluator_type.as_str().into();
+            json["expression"] = self.expression.as_str().into();
+        }
+        if !self.decision_cases.is_empty() {
+            let mut decision_cases = serde_json::Map::with_capacity(self.decision_cases.len());
+            for (case, tasks) in self.decision_cases.iter() {
+                decision_cases.insert(case.to_string(), Self::to_jsonlist(tasks));
+            }
+            json["decisionCases"] = serde_json::Value::Object(decision_cases);
+        }
+        if !self.default_case.is_empty() {
+            json["defaultCase"] = Self::to_jsonlist(&self.default_case);
+        }
+
+        // EXCLUSIVE_JOIN
+        if !self.exclusive_join_on.is_empty() {
+            json["exclusiveJoinOn"] = Self::to_json_strlist(&self.exclusive_join_on);
+        }
+        if !self.default_exclusive_join_task.is_empty() {
+            json["defaultExclusiveJoinTask"] =
+                Self::to_json_strlist(&self.default_exclusive_join_task);
+        }
+
+        // DYNAMIC
+        if !self.dynamic_task_name_param.is_empty() {
+            json["dynamicTaskNameParam"] = self.dynamic_task_name_param.as_str().into();
+        }
+
+        // DO_WHILE
+        if !self.loop_condition.is_empty() {
+            json["loopCondition"] = self.loop_condition.as_str().into();
+        }
+        if !self.loop_over.is_empty() {
+            json["loopOver"] = Self::to_jsonlist(&self.loop_over);
+        }
+
+        // SUB_WORKFLOW
+        if let Some(sub_workflow_param) = self.sub_workflow_param.as_ref() {
+            json["subWorkflowParam"] = sub_workflow_param.to_json();
+        }
+
+        // FORK_JOIN/JOIN
+        if !self.fork_tasks.is_empty() {
+            json["forkTasks"] = serde_json::Value::Array(
+                self.fork_tasks
+                    .iter()
+                    .map(|x| Self::to_jsonlist(x))
+                    .collect(),
+            );
+        }
+        if !self.join_on.is_empty() {
+            json["joinOn"] = Self::to_json_strlist(&self.join_on);
+        }
+        match self.join_mode {
+            JoinMode::All => {}
+            JoinMode::Any => json["joinMode"] = self.join_mode.as_ref().into(),
+            JoinMode::FirstN => {
+                json["joinMode"] = self.join_mode.as_ref().into();
+                json["joinCount"] = self.join_count.into();
+            }
+            JoinMode::Quorum => {
+                json["joinMode"] = self.join_mode.as_ref().into();
+                json["joinQuorum"] = self.join_quorum.into();
+            }
+        }
+
+        // FORK_JOIN_DYNAMIC
+        if !self.dynamic_fork_tasks_param.is_empty() {
+            json["dynamicForkTasksParam"] = self.dynamic_fork_tasks_param.as_str().into();
+            json["dynamicForkTasksInputParamName"] =
+                self.dynamic_fork_tasks_input_param_name.as_str().into();
+        }
+
+        if let Some(task_definition) = self.task_definition.as_ref() {
+            json["taskDefinition"] = task_definition.to_json();
+        }
+        json
+    }
+
+    pub fn to_jsonlist(tasks: &Vec<Self>) -> serde_json::Value {
+        serde_json::Value::Array(tasks.iter().map(|x| x.to_json()).collect())
+    }
+
+    fn to_json_strlist(list: &Vec<InlineStr>) -> serde_json::Value {
+        serde_json::Value::Array(list.iter().map(|x| x.as_str().into()).collect())
+    }
+
     pub fn try_from_jsonlist(jsonlist: &Vec<serde_json::Value>) -> TegResult<Vec<Self>> {
-        let mut tasks = Vec::with_capacity(jsonlist.len());
+        let mut tasks: Vec<Self> = Vec::with_capacity(jsonlist.len());
         for json in jsonlist {
-            tasks.push(json.try_into()?);
+            let task: Self = json.try_into()?;
+            if task.type_.eq("JOIN") {
+                Self::validate_join_on(tasks.last(), &task)?;
+            }
+            tasks.push(task);
         }
         Ok(tasks)
     }
 
+    /// The joinOn of a JOIN can only be validated against the task preceding it, it is only empty
+    /// after a FORK_JOIN_DYNAMIC task, and every reference name has to be one of the forked tasks
+    /// after a FORK_JOIN task.
+    fn validate_join_on(previous: Option<&WorkflowTask>, join: &WorkflowTask) -> TegResult<()> {
+        if join.join_on.is_empty() && !previous.map_or(false, |x| x.type_.eq("FORK_JOIN_DYNAMIC")) {
+            return fmt_err!(IllegalArgument, "WorkflowTask: joinOn can not be empty");
+        }
+        let fork = match previous {
+            Some(fork) if fork.type_.eq("FORK_JOIN") => fork,
+            _ => return Ok(()),
+        };
+        for join_on_ref in &join.join_on {
+            if !fork
+                .fork_tasks
+                .iter()
+                .flatten()
+                .any(|x| x.task_reference_name.eq(join_on_ref))
+            {
+                return fmt_err!(
+                    IllegalArgument,
+                    "WorkflowTask: joinOn task_ref {} not exist in forkTasks of {}",
+                    join_on_ref,
+                    fork.task_reference_name
+                );
+            }
+        }
+        Ok(())
+    }
+
     fn switch_try_from(
         type_: &InlineStr,
         value: &serde_json::Value,
@@ -522,6 +734,20 @@ impl WorkflowTask {
         }
     }
 
+    fn sub_workflow_try_from(
+        type_: &InlineStr,
+        value: &serde_json::Value,
+    ) -> TegResult<Option<SubWorkflowParams>> {
+        if type_.eq("SUB_WORKFLOW") {
+            let sub_workflow_param = value.get("subWorkflowParam").ok_or_else(|| {
+                ErrorCode::IllegalArgument("WorkflowTask: subWorkflowParam not found")
+            })?;
+            Ok(Some(SubWorkflowParams::try_from(sub_workflow_param)?))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn fork_join_try_from(
         type_: &InlineStr,
         value: &serde_json::Value,
@@ -555,45 +781,108 @@ impl WorkflowTask {
         }
     }
 
+    fn fork_join_dynamic_try_from(
+        type_: &InlineStr,
+        input_parameters: &HashMap<InlineStr, Object>,
+        value: &serde_json::Value,
+    ) -> TegResult<(InlineStr, InlineStr)> {
+        if type_.eq("FORK_JOIN_DYNAMIC") {
+            let param_try_from = |key: &str| -> TegResult<InlineStr> {
+                let param: InlineStr = value
+                    .get(key)
+                    .and_then(|x| x.as_str())
+                    .ok_or_else(|| {
+                        ErrorCode::IllegalArgument(format!("WorkflowTask: {} not found", key))
+                    })?
+                    .trim()
+                    .into();
+                if !input_parameters.contains_key(&param) {
+                    return fmt_err!(
+                        IllegalArgument,
+                        "WorkflowTask: {} invalid: can not find {} in inputParameters",
+                        key,
+                        param
+                    );
+                }
+                Ok(param)
+            };
+            Ok((
+                param_try_from("dynamicForkTasksParam")?,
+                param_try_from("dynamicForkTasksInputParamName")?,
+            ))
+        } else {
+            Ok((InlineStr::default(), InlineStr::default()))
+        }
+    }
+
     fn join_try_from(
         type_: &InlineStr,
         value: &serde_json::Value,
-        fork_tasks: &Vec<Vec<WorkflowTask>>,
-    ) -> TegResult<Vec<InlineStr>> {
+    ) -> TegResult<(Vec<InlineStr>, JoinMode, i32, i32)> {
         if type_.eq("JOIN") {
             let mut join_on = Vec::default();
 
-            for v in value
-                .get("joinOn")
-                .and_then(|x| x.as_array())
-                .ok_or_else(|| ErrorCode::IllegalArgument("WorkflowTask: join invalid"))?
-            {
-                let fork_task_ref = v
+            // the JOIN of a FORK_JOIN_DYNAMIC task has no joinOn, it is filled in at runtime
+            if let Some(json) = value.get("joinOn").filter(|x| !x.is_null()) {
+                for v in json
+                    .as_array()
+                    .ok_or_else(|| ErrorCode::IllegalArgument("WorkflowTask: join invalid"))?
+                {
+                    let fork_task_ref = v
+                        .as_str()
+                        .ok_or_else(|| ErrorCode::IllegalArgument("WorkflowTask: joinOn invalid"))?
+                        .into();
+                    join_on.push(fork_task_ref);
+                }
+            }
+
+            let join_mode = JoinMode::from_str(
+                value
+                    .get("joinMode")
+                    .unwrap_or(&serde_json::json!("ALL"))
                     .as_str()
-                    .ok_or_else(|| ErrorCode::IllegalArgument("WorkflowTask: joinOn invalid"))?
-                    .into();
-                let mut exist_ref = false;
-                for tasks in fork_tasks {
-                    if tasks
-                        .iter()
-                        .find(|x| x.task_reference_name.eq(&fork_task_ref))
-                        .is_some()
-                    {
-                        exist_ref = true;
-                        break;
+                    .ok_or_else(|| ErrorCode::IllegalArgument("WorkflowTask: joinMode invalid"))?
+                    .trim(),
+            )
+            .map_err(|_| ErrorCode::IllegalArgument("WorkflowTask: joinMode invalid"))?;
+
+            let mut join_count = 0;
+            let mut join_quorum = 0;
+            match join_mode {
+                JoinMode::All | JoinMode::Any => {}
+                JoinMode::FirstN => {
+                    join_count = value
+                        .get("joinCount")
+                        .and_then(|x| x.as_i64())
+                        .filter(|x| *x > 0)
+                        .ok_or_else(|| {
+                            ErrorCode::IllegalArgument(
+                                "WorkflowTask: joinCount has to be positive when joinMode is FIRST_N",
+                            )
+                        })? as i32;
+                    if !join_on.is_empty() && join_count as usize > join_on.len() {
+                        return fmt_err!(
+                            IllegalArgument,
+                            "WorkflowTask: joinCount {} is greater than the number of joinOn tasks",
+                            join_count
+                        );
                     }
                 }
-                if !exist_ref {
-                    return fmt_err!(IllegalArgument, "WorkflowTask: joinOn task_ref not exist");
+                JoinMode::Quorum => {
+                    join_quorum = value
+                        .get("joinQuorum")
+                        .and_then(|x| x.as_i64())
+                        .filter(|x| *x > 0 && *x <= 100)
+                        .ok_or_else(|| {
+                            ErrorCode::IllegalArgument(
+                                "WorkflowTask: joinQuorum has to be a percentage between 1 and 100 when joinMode is QUORUM",
+                            )
+                        })? as i32;
                 }
-                join_on.push(fork_task_ref);
-            }
-            if join_on.is_empty() {
-                return fmt_err!(IllegalArgument, "WorkflowTask: joinOn can not be empty");
             }
-            Ok(join_on)
+            Ok((join_on, join_mode, join_count, join_quorum))
         } else {
-            Ok(Vec::default())
+            Ok((Vec::default(), JoinMode::All, 0, 0))
         }
     }
 
@@ -651,3 +940,17 @@ impl WorkflowTask {
         }
     }
 }
+
+/// How many of the joined tasks a JOIN task waits for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AsRefStr, EnumString)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum JoinMode {
+    /// Waits for all the joined tasks. This is the default value.
+    All,
+    /// Waits for the first joined task to complete.
+    Any,
+    /// Waits for the first `WorkflowTask::join_count` joined tasks to complete.
+    FirstN,
+    /// Waits for `WorkflowTask::join_quorum` percent of the joined tasks to complete.
+    Quorum,
+}
@@ -1 +1,62 @@
-pub struct RerunWorkflowRequest;
+use crate::prelude::*;
+
+pub struct RerunWorkflowRequest {
+    /// Id of the workflow to rerun
+    pub re_run_from_workflow_id: InlineStr,
+    /// Replaces the input of the workflow when set
+    pub workflow_input: Option<HashMap<InlineStr, Object>>,
+    /// Id of the task to rerun the workflow from, the workflow is rerun from the beginning when
+    /// empty. It may be the id of a task of a sub workflow.
+    pub re_run_from_task_id: InlineStr,
+    /// Replaces the input of the task to rerun from when set
+    pub task_input: Option<HashMap<InlineStr, Object>>,
+    /// Replaces the correlation id of the workflow when not empty
+    pub correlation_id: InlineStr,
+}
+
+impl RerunWorkflowRequest {
+    pub fn new(re_run_from_workflow_id: InlineStr, re_run_from_task_id: InlineStr) -> Self {
+        Self {
+            re_run_from_workflow_id,
+            workflow_input: None,
+            re_run_from_task_id,
+            task_input: None,
+            correlation_id: InlineStr::default(),
+        }
+    }
+
+    fn map_from_json(
+        value: &serde_json::Value,
+        key: &str,
+    ) -> TegResult<Option<HashMap<InlineStr, Object>>> {
+        match value.get(key) {
+            Some(serde_json::Value::Object(map)) => {
+                Ok(Some(Object::convert_jsonmap_to_hashmap(map)))
+            }
+            Some(serde_json::Value::Null) | None => Ok(None),
+            Some(_) => fmt_err!(IllegalArgument, "{} invalid", key),
+        }
+    }
+
+    fn str_from_json(value: &serde_json::Value, key: &str) -> InlineStr {
+        value
+            .get(key)
+            .and_then(|x| x.as_str())
+            .unwrap_or("")
+            .trim()
+            .into()
+    }
+}
+
+impl TryFrom<serde_json::Value> for RerunWorkflowRequest {
+    type Error = ErrorCode;
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        Ok(Self {
+            re_run_from_workflow_id: Self::str_from_json(&value, "reRunFromWorkflowId"),
+            workflow_input: Self::map_from_json(&value, "workflowInput")?,
+            re_run_from_task_id: Self::str_from_json(&value, "reRunFromTaskId"),
+            task_input: Self::map_from_json(&value, "taskInput")?,
+            correlation_id: Self::str_from_json(&value, "correlationId"),
+        })
+    }
+}
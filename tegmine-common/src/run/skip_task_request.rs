@@ -1 +1,32 @@
-pub struct SkipTaskRequest;
+use crate::prelude::*;
+
+#[derive(Default)]
+pub struct SkipTaskRequest {
+    /// The input of the skipped task
+    pub task_input: HashMap<InlineStr, Object>,
+    /// The output of the skipped task, which the tasks following it can refer to
+    pub task_output: HashMap<InlineStr, Object>,
+}
+
+impl SkipTaskRequest {
+    fn map_from_json(
+        value: &serde_json::Value,
+        key: &str,
+    ) -> TegResult<HashMap<InlineStr, Object>> {
+        match value.get(key) {
+            Some(serde_json::Value::Object(map)) => Ok(Object::convert_jsonmap_to_hashmap(map)),
+            Some(serde_json::Value::Null) | None => Ok(HashMap::default()),
+            Some(_) => fmt_err!(IllegalArgument, "{} invalid", key),
+        }
+    }
+}
+
+impl TryFrom<serde_json::Value> for SkipTaskRequest {
+    type Error = ErrorCode;
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        Ok(Self {
+            task_input: Self::map_from_json(&value, "taskInput")?,
+            task_output: Self::map_from_json(&value, "taskOutput")?,
+        })
+    }
+}
@@ -0,0 +1,87 @@
+-- ******************************************
+-- *************** Metadata *****************
+-- ******************************************
+
+CREATE TABLE IF NOT EXISTS meta_task_def (
+    name TEXT NOT NULL PRIMARY KEY,
+    json_data TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS meta_workflow_def (
+    name TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    json_data TEXT NOT NULL,
+    PRIMARY KEY (name, version)
+);
+
+CREATE TABLE IF NOT EXISTS meta_workflow_def_disabled (
+    name TEXT NOT NULL PRIMARY KEY
+);
+
+-- ******************************************
+-- *************** Execution ****************
+-- ******************************************
+
+CREATE TABLE IF NOT EXISTS workflow (
+    workflow_id TEXT NOT NULL PRIMARY KEY,
+    correlation_id TEXT NOT NULL DEFAULT '',
+    status TEXT NOT NULL,
+    json_data TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS workflow_corr_id_index ON workflow (correlation_id);
+
+CREATE TABLE IF NOT EXISTS workflow_def_to_workflow (
+    workflow_def TEXT NOT NULL,
+    create_time INTEGER NOT NULL,
+    workflow_id TEXT NOT NULL,
+    PRIMARY KEY (workflow_def, create_time, workflow_id)
+);
+
+CREATE TABLE IF NOT EXISTS workflow_pending (
+    workflow_type TEXT NOT NULL,
+    workflow_id TEXT NOT NULL,
+    PRIMARY KEY (workflow_type, workflow_id)
+);
+
+CREATE TABLE IF NOT EXISTS workflow_to_task (
+    workflow_id TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    PRIMARY KEY (workflow_id, task_id)
+);
+
+CREATE TABLE IF NOT EXISTS task_scheduled (
+    workflow_id TEXT NOT NULL,
+    task_key TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    PRIMARY KEY (workflow_id, task_key)
+);
+
+CREATE TABLE IF NOT EXISTS task_in_progress (
+    task_def_name TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    workflow_id TEXT NOT NULL,
+    in_progress_status INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (task_def_name, task_id)
+);
+
+CREATE TABLE IF NOT EXISTS task (
+    task_id TEXT NOT NULL PRIMARY KEY,
+    json_data TEXT NOT NULL
+);
+
+-- ******************************************
+-- *************** Queue ********************
+-- ******************************************
+
+CREATE TABLE IF NOT EXISTS queue_message (
+    queue_name TEXT NOT NULL,
+    message_id TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    deliver_on INTEGER NOT NULL,
+    popped INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (queue_name, message_id)
+);
+
+CREATE INDEX IF NOT EXISTS queue_message_combo_index
+    ON queue_message (queue_name, popped, deliver_on, priority);
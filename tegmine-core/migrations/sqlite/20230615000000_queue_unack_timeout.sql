@@ -0,0 +1,6 @@
+-- popped messages stay in the queue until they are ack'ed, their deliver_on is the time they are
+-- delivered again
+DROP INDEX IF EXISTS queue_message_combo_index;
+
+CREATE INDEX IF NOT EXISTS queue_message_deliver_on_index
+    ON queue_message (queue_name, deliver_on, priority);
@@ -0,0 +1,5 @@
+-- the concurrency limit of a task definition lets the tasks in progress execute in order of arrival
+ALTER TABLE task_in_progress ADD COLUMN created_on INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS task_in_progress_created_on_index
+    ON task_in_progress (task_def_name, created_on);
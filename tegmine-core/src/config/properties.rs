@@ -1,3 +1,11 @@
+use std::path::PathBuf;
+
+use tegmine_common::prelude::*;
+
+use crate::engine::EngineContext;
+
+/// The configuration of an engine.
+#[derive(Clone)]
 pub struct Properties {
     /// The timeout duration to set when a workflow is pushed to the decider queue.
     pub workflow_offset_timeout_sec: i64,
@@ -21,6 +29,10 @@ pub struct Properties {
     pub max_workflow_variables_payload_size_threshold: i32,
     ///
     pub task_pending_time_threshold_sec: i64,
+
+    /// The directory to which the execution records of the deleted workflows are archived, the
+    /// workflows can not be deleted with archiving unless it is set.
+    pub workflow_archive_dir: Option<PathBuf>,
 }
 
 impl Default for Properties {
@@ -34,6 +46,14 @@ impl Default for Properties {
             lock_time_to_try_ms: 500,
             max_workflow_variables_payload_size_threshold: 256,
             task_pending_time_threshold_sec: 60 * 60, // 60min
+            workflow_archive_dir: None,
         }
     }
 }
+
+impl Properties {
+    /// Returns the properties of the current engine.
+    pub(crate) fn get() -> TegResult<Arc<Properties>> {
+        Ok(Arc::clone(&EngineContext::current()?.properties))
+    }
+}
@@ -0,0 +1,51 @@
+use tegmine_common::prelude::*;
+
+use super::in_memory::{
+    InMemoryExecutionDao, InMemoryIndexDao, InMemoryMetadataDao, InMemoryPollDataDao,
+    InMemoryQueueDao, InMemoryRateLimitingDao,
+};
+use super::{
+    ConcurrentExecutionLimitDao, ExecutionDao, IndexDao, MetadataDao, PollDataDao, QueueDao,
+    RateLimitingDao,
+};
+use crate::engine::EngineContext;
+
+/// The storage layer an engine dispatches to. Every field defaults to the in-memory
+/// implementation, so an embedder only needs to replace the DAOs it wants to back by its own
+/// storage.
+///
+/// The `concurrent_execution_limit_dao` counts the tasks in progress tracked by the
+/// `execution_dao`, so both should be backed by the same storage, e.g. the same
+/// `SqliteExecutionDao` instance.
+#[derive(Clone)]
+pub struct Daos {
+    pub execution_dao: Arc<dyn ExecutionDao>,
+    pub queue_dao: Arc<dyn QueueDao>,
+    pub metadata_dao: Arc<dyn MetadataDao>,
+    pub index_dao: Arc<dyn IndexDao>,
+    pub poll_data_dao: Arc<dyn PollDataDao>,
+    pub rate_limiting_dao: Arc<dyn RateLimitingDao>,
+    pub concurrent_execution_limit_dao: Arc<dyn ConcurrentExecutionLimitDao>,
+}
+
+impl Default for Daos {
+    fn default() -> Self {
+        let execution_dao = Arc::new(InMemoryExecutionDao::new());
+        Self {
+            execution_dao: execution_dao.clone(),
+            queue_dao: Arc::new(InMemoryQueueDao::new()),
+            metadata_dao: Arc::new(InMemoryMetadataDao::new()),
+            index_dao: Arc::new(InMemoryIndexDao),
+            poll_data_dao: Arc::new(InMemoryPollDataDao),
+            rate_limiting_dao: Arc::new(InMemoryRateLimitingDao::new()),
+            concurrent_execution_limit_dao: execution_dao,
+        }
+    }
+}
+
+impl Daos {
+    /// Returns the DAOs of the current engine.
+    pub(crate) fn get() -> TegResult<Arc<Daos>> {
+        Ok(Arc::clone(&EngineContext::current()?.daos))
+    }
+}
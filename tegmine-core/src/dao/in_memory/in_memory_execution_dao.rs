@@ -0,0 +1,443 @@
+use chrono::Utc;
+use dashmap::DashMap;
+use tegmine_common::prelude::*;
+
+use crate::dao::{ConcurrentExecutionLimitDao, ExecutionDao};
+use crate::metrics::Monitors;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::WorkflowStatus;
+
+/// In-memory implementation of the data access layer for storing workflow executions
+#[derive(Default)]
+pub struct InMemoryExecutionDao {
+    /// ******************************************
+    /// *************** Task *********************
+    /// ******************************************
+    /// the non terminal tasks per task def name, in order of creation
+    in_progress_tasks: DashMap<InlineStr, Vec<InlineStr>>,
+    /// the non terminal tasks per task type
+    pending_tasks_by_type: DashMap<InlineStr, HashSet<InlineStr>>,
+    tasks_in_progress_status: DashMap<InlineStr, Vec<InlineStr>>,
+    workflow_to_tasks: DashMap<InlineStr, Vec<InlineStr>>,
+    scheduled_tasks: DashMap<InlineStr, HashMap<InlineStr, InlineStr>>,
+    task: DashMap<InlineStr, TaskModel>,
+
+    /// ******************************************
+    /// *************** Workflow *****************
+    /// ******************************************
+    workflow: DashMap<InlineStr, WorkflowModel>,
+    pending_workflows: DashMap<InlineStr, Vec<InlineStr>>,
+    workflow_def_to_workflows: DashMap<(InlineStr, i64), Vec<InlineStr>>,
+    corr_id_to_workflows: DashMap<InlineStr, Vec<InlineStr>>,
+    // EVENT_EXECUTION
+}
+
+impl InMemoryExecutionDao {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn remove_task_mappings(&self, task: &TaskModel) {
+        let task_key = task.get_task_key();
+        self.scheduled_tasks
+            .get_mut(&task.workflow_instance_id)
+            .map(|mut x| x.remove(&task_key));
+        self.in_progress_tasks
+            .get_mut(&task.task_def_name)
+            .map(|mut x| x.retain(|x| !x.eq(&task.task_id)));
+        self.workflow_to_tasks
+            .get_mut(&task.workflow_instance_id)
+            .map(|mut x| x.retain(|x| !x.eq(&task.task_id)));
+        self.pending_tasks_by_type
+            .get_mut(&task.task_type)
+            .map(|mut x| x.remove(&task.task_id));
+        self.tasks_in_progress_status
+            .get_mut(&task.task_def_name)
+            .map(|mut x| x.retain(|x| !x.eq(&task.task_id)));
+    }
+
+    // removeTaskMappingsWithExpiry
+
+    // removeTaskWithExpiry
+
+    // removeWorkflowWithExpiry
+
+    // canSearchAcrossWorkflows
+
+    /// Inserts a new workflow/ updates an existing workflow in the datastore. Additionally, if a
+    /// workflow is in terminal state, it is removed from the set of pending workflows.
+    fn insert_or_update_workflow(&self, workflow: &WorkflowModel, update: bool) {
+        let workflow_id = workflow.workflow_id.clone();
+
+        // Store the workflow object
+        let mut cloned_workflow = workflow.clone();
+        cloned_workflow.tasks.clear();
+        self.workflow.insert(workflow_id.clone(), cloned_workflow);
+
+        if !update {
+            // Add to list of workflows for a workflow_def
+            self.workflow_def_to_workflows
+                .entry((
+                    workflow.workflow_definition.name.clone(),
+                    workflow.create_time,
+                ))
+                .or_default()
+                .push(workflow_id.clone());
+            if !workflow.correlation_id.is_empty() {
+                // Add to list of workflows for a correlationId
+                self.corr_id_to_workflows
+                    .entry(workflow.correlation_id.clone())
+                    .or_default()
+                    .push(workflow_id.clone());
+            }
+        }
+
+        // Add or remove from the pending workflows
+        if workflow.status.is_terminal() {
+            self.pending_workflows
+                .get_mut(&workflow.workflow_definition.name)
+                .map(|mut x| x.value_mut().retain(|x| !x.eq(&workflow_id)));
+        } else {
+            self.pending_workflows
+                .entry(workflow.workflow_definition.name.clone())
+                .or_default()
+                .push(workflow_id.clone())
+        }
+    }
+
+    /// Stores the correlation of a task to the workflow instance in the datastore
+    fn correlate_task_to_workflow_in_ds(
+        &self,
+        task_id: &InlineStr,
+        workflow_instance_id: &InlineStr,
+    ) {
+        self.workflow_to_tasks
+            .entry(workflow_instance_id.clone())
+            .or_default()
+            .push(task_id.clone());
+        debug!(
+            "Task mapped in WORKFLOW_TO_TASKS with  workflowId: {}, taskId: {}",
+            workflow_instance_id, task_id
+        )
+    }
+
+    // getPendingWorkflowCount
+
+    fn get_in_progress_task_count(&self, task_def_name: &InlineStr) -> usize {
+        self.tasks_in_progress_status
+            .get(task_def_name)
+            .map(|x| x.len())
+            .unwrap_or(0)
+    }
+
+    /// ******************************************
+    /// *************** Event *****************
+    /// ******************************************
+
+    // addEventExecution
+
+    // updateEventExecution
+
+    // removeEventExecution
+
+    // getEventExecutions
+
+    fn validate(task: &TaskModel) -> TegResult<()> {
+        if task.task_id.is_empty() {
+            fmt_err!(IllegalArgument, "task object cannot be null")
+        } else if task.workflow_instance_id.is_empty() {
+            fmt_err!(IllegalArgument, "Workflow instance id cannot be null")
+        } else if task.reference_task_name.is_empty() {
+            fmt_err!(IllegalArgument, "Task reference name cannot be null")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl ExecutionDao for InMemoryExecutionDao {
+    /// ******************************************
+    /// *************** Task *********************
+    /// ******************************************
+
+    fn create_tasks(&self, tasks: &mut [&mut TaskModel]) -> TegResult<()> {
+        for task in tasks {
+            Self::validate(task)?;
+
+            let task_key = task.get_task_key();
+
+            let added = self
+                .scheduled_tasks
+                .entry(task.workflow_instance_id.clone())
+                .or_insert(HashMap::default())
+                .insert(task_key.clone(), task.task_id.clone())
+                .is_none();
+            if !added {
+                debug!(
+                    "Task already scheduled, skipping the run {}, ref={}, key={}",
+                    task.task_id, task.reference_task_name, task_key
+                );
+                continue;
+            }
+
+            if !task.status.is_terminal() && task.scheduled_time == 0 {
+                task.scheduled_time = Utc::now().timestamp_millis();
+            }
+
+            self.correlate_task_to_workflow_in_ds(&task.task_id, &task.workflow_instance_id);
+            debug!(
+                "Scheduled task added to WORKFLOW_TO_TASKS workflowId: {}, taskId: {}, taskType: {} during createTasks",
+                task.workflow_instance_id, task.task_id, task.task_type
+            );
+
+            self.in_progress_tasks
+                .entry(task.task_def_name.clone())
+                .or_default()
+                .push(task.task_id.clone());
+            debug!(
+                "Scheduled task added to IN_PROGRESS_TASKS with inProgressTaskKey: {}, workflowId: {}, taskId: {}, taskType: {} during createTasks",
+                task.task_def_name, task.workflow_instance_id, task.task_id, task.task_type
+            );
+
+            self.update_task(task)?;
+        }
+
+        Ok(())
+    }
+
+    fn update_task(&self, task: &mut TaskModel) -> TegResult<()> {
+        let task_id = task.task_id.clone();
+        let task_definition = task.get_task_definition();
+
+        if task_definition.map(|x| x.concurrency_limit()).unwrap_or(0) > 0 {
+            if task.status == TaskStatus::InProgress {
+                let mut tasks_in_progress = self
+                    .tasks_in_progress_status
+                    .entry(task.task_def_name.clone())
+                    .or_default();
+                if !tasks_in_progress.contains(&task_id) {
+                    tasks_in_progress.push(task_id.clone());
+                }
+                drop(tasks_in_progress);
+                debug!(
+                    "Workflow Task added to TASKS_IN_PROGRESS_STATUS with tasksInProgressKey: {}, workflowId: {}, taskId: {}, taskType: {}, taskStatus: {} during updateTask",
+                    task.task_def_name,
+                    task.workflow_instance_id,
+                    task_id,
+                    task.task_type,
+                    task.status.as_ref()
+                );
+            } else {
+                self.tasks_in_progress_status
+                    .entry(task.task_def_name.clone())
+                    .or_default()
+                    .retain(|x| !x.eq(&task_id));
+                debug!(
+                    "Workflow Task removed from TASKS_IN_PROGRESS_STATUS with tasksInProgressKey: {}, workflowId: {}, taskId: {}, taskType: {}, taskStatus: {} during updateTask",
+                    task.task_def_name,
+                    task.workflow_instance_id,
+                    task_id,
+                    task.task_type,
+                    task.status.as_ref()
+                );
+            }
+        }
+
+        self.task.insert(task_id.clone(), task.clone());
+
+        debug!(
+            "Workflow task payload saved to TASK with taskKey: {}, workflowId: {}, taskId: {}, taskType: {} during updateTask",
+            task.task_id, task.workflow_instance_id, task.task_id, task.task_type
+        );
+        if task.status.is_terminal() {
+            self.in_progress_tasks
+                .entry(task.task_def_name.clone())
+                .or_default()
+                .retain(|x| !x.eq(&task.task_id));
+            debug!(
+                "Workflow Task removed from TASKS_IN_PROGRESS_STATUS with tasksInProgressKey: {}, workflowId: {}, taskId: {}, taskType: {}, taskStatus: {} during updateTask",
+                task.task_def_name,
+                task.workflow_instance_id,
+                task.task_id,
+                task.task_type,
+                task.status.as_ref()
+            );
+            self.pending_tasks_by_type
+                .get_mut(&task.task_type)
+                .map(|mut x| x.remove(&task.task_id));
+        } else {
+            self.pending_tasks_by_type
+                .entry(task.task_type.clone())
+                .or_default()
+                .insert(task.task_id.clone());
+        }
+
+        let not_exist = self
+            .workflow_to_tasks
+            .get(&task.workflow_instance_id)
+            .map(|x| !x.value().contains(&task.task_id))
+            .unwrap_or(true);
+        if not_exist {
+            self.correlate_task_to_workflow_in_ds(&task.task_id, &task.workflow_instance_id);
+        }
+
+        Ok(())
+    }
+
+    fn remove_task(&self, task_id: &InlineStr) -> TegResult<bool> {
+        if let Some(task) = self.get_task(task_id)? {
+            self.remove_task_mappings(&task);
+            let _ = self.task.remove(task_id);
+            Ok(true)
+        } else {
+            warn!("No such task found by id {}", task_id);
+            Ok(false)
+        }
+    }
+
+    fn get_task(&self, task_id: &InlineStr) -> TegResult<Option<TaskModel>> {
+        Ok(self.task.get(task_id).map(|x| x.clone()))
+    }
+
+    fn get_tasks(&self, task_ids: Vec<InlineStr>) -> TegResult<Vec<TaskModel>> {
+        let mut tasks = Vec::with_capacity(task_ids.len());
+        for task_id in &task_ids {
+            if let Some(task) = self.task.get(task_id) {
+                tasks.push(task.clone());
+            }
+        }
+        Ok(tasks)
+    }
+
+    fn get_tasks_for_workflow(&self, workflow_id: &InlineStr) -> TegResult<Vec<TaskModel>> {
+        let task_ids = self
+            .workflow_to_tasks
+            .get(workflow_id)
+            .map(|x| x.value().clone())
+            .unwrap_or_default();
+        self.get_tasks(task_ids)
+    }
+
+    fn get_pending_tasks_for_task_type(&self, task_type: &str) -> TegResult<Vec<TaskModel>> {
+        let task_ids = self
+            .pending_tasks_by_type
+            .get(task_type)
+            .map(|x| x.value().iter().cloned().collect())
+            .unwrap_or_default();
+        self.get_tasks(task_ids)
+    }
+
+    /// ******************************************
+    /// *************** Workflow *****************
+    /// ******************************************
+
+    fn create_workflow(&self, workflow: &WorkflowModel) -> TegResult<()> {
+        self.insert_or_update_workflow(workflow, false);
+        Ok(())
+    }
+
+    fn update_workflow(&self, workflow: &WorkflowModel) -> TegResult<()> {
+        self.insert_or_update_workflow(workflow, true);
+        Ok(())
+    }
+
+    fn remove_workflow(&self, workflow_id: &InlineStr) -> TegResult<bool> {
+        if let Some(workflow) = self.get_workflow(workflow_id)? {
+            // Remove from lists
+            self.workflow_def_to_workflows
+                .get_mut(&(
+                    workflow.workflow_definition.name.clone(),
+                    workflow.create_time,
+                ))
+                .map(|mut x| x.value_mut().retain(|x| !x.eq(workflow_id)));
+            self.corr_id_to_workflows
+                .get_mut(&workflow.correlation_id)
+                .map(|mut x| x.value_mut().retain(|x| !x.eq(workflow_id)));
+            self.pending_workflows
+                .get_mut(&workflow.workflow_definition.name)
+                .map(|mut x| x.value_mut().retain(|x| !x.eq(workflow_id)));
+
+            // Remove the object
+            let _ = self.workflow.remove(workflow_id);
+
+            // Remove task
+            for task in &workflow.tasks {
+                self.remove_task(&task.task_id)?;
+            }
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn remove_from_pending_workflow(
+        &self,
+        workflow_type: &InlineStr,
+        workflow_id: &InlineStr,
+    ) -> TegResult<()> {
+        self.scheduled_tasks.remove(workflow_id);
+        self.pending_workflows
+            .get_mut(workflow_type)
+            .map(|mut x| x.retain(|x| !x.eq(workflow_id)));
+        Ok(())
+    }
+
+    fn get_workflow_status(&self, workflow_id: &InlineStr) -> TegResult<Option<WorkflowStatus>> {
+        Ok(self.workflow.get(workflow_id).map(|x| x.status))
+    }
+
+    fn get_workflow_include_tasks(
+        &self,
+        workflow_id: &InlineStr,
+        include_tasks: bool,
+    ) -> TegResult<Option<WorkflowModel>> {
+        let workflow = self.workflow.get(workflow_id).map(|x| x.clone());
+        if let Some(mut workflow) = workflow {
+            if include_tasks {
+                let mut tasks = self.get_tasks_for_workflow(workflow_id)?;
+                tasks.sort_by(|a, b| a.seq.cmp(&b.seq));
+                workflow.tasks = tasks.into_iter().collect::<LinkedList<_>>();
+            }
+            Ok(Some(workflow))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ConcurrentExecutionLimitDao for InMemoryExecutionDao {
+    fn exceeds_limit(&self, task: &TaskModel) -> bool {
+        let limit = match task.get_task_definition() {
+            Some(task_definition) => task_definition.concurrency_limit(),
+            None => return false,
+        };
+        if limit <= 0 {
+            return false;
+        }
+
+        let current = self.get_in_progress_task_count(&task.task_def_name);
+        if current >= limit as usize {
+            debug!(
+                "Task execution count limited. task - {}:{}, limit: {}, current: {}",
+                task.task_id, task.task_def_name, limit, current
+            );
+            Monitors::record_task_concurrent_execution_limited(&task.task_def_name, limit);
+            return true;
+        }
+
+        // the first `limit` tasks not in a terminal status may execute, in order of creation
+        let rate_limited = !self
+            .in_progress_tasks
+            .get(&task.task_def_name)
+            .map(|x| x.iter().take(limit as usize).any(|x| x.eq(&task.task_id)))
+            .unwrap_or(false);
+        if rate_limited {
+            debug!(
+                "Task execution count limited. task - {}:{}, limit: {}, current: {}",
+                task.task_id, task.task_def_name, limit, current
+            );
+            Monitors::record_task_concurrent_execution_limited(&task.task_def_name, limit);
+        }
+        rate_limited
+    }
+}
@@ -0,0 +1,22 @@
+use tegmine_common::prelude::*;
+
+use crate::dao::IndexDao;
+use crate::model::{TaskSummary, WorkflowSummary};
+
+/// In-memory implementation of the `IndexDao`, the summaries are not indexed.
+#[derive(Default)]
+pub struct InMemoryIndexDao;
+
+impl IndexDao for InMemoryIndexDao {
+    fn index_workflow(&self, _workflow: WorkflowSummary) -> TegResult<()> {
+        Ok(())
+    }
+
+    fn async_index_workflow(&self, _workflow: WorkflowSummary) -> TegResult<()> {
+        Ok(())
+    }
+
+    fn index_task(&self, _task: TaskSummary) -> TegResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use dashmap::{DashMap, DashSet};
+use tegmine_common::prelude::*;
+use tegmine_common::{TaskDef, WorkflowDef};
+
+use crate::dao::MetadataDao;
+
+/// In-memory implementation of the data access layer for the workflow metadata - task
+/// definitions and workflow definitions
+#[derive(Default)]
+pub struct InMemoryMetadataDao {
+    task_def: DashMap<InlineStr, TaskDef>,
+    workflow_def: DashMap<InlineStr, HashMap<i32, WorkflowDef>>,
+    workflow_def_names: DashSet<InlineStr>,
+    disabled_workflow_def: DashSet<InlineStr>,
+}
+
+impl InMemoryMetadataDao {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert_or_update_task_def(&self, task_def: TaskDef) {
+        let task_name = task_def.name.clone();
+
+        // Store all task def in under one key
+        self.task_def.insert(task_name, task_def);
+    }
+
+    fn insert_or_update_workflow_def(&self, workflow_def: WorkflowDef) {
+        let workflow_name = workflow_def.name.clone();
+        let version = workflow_def.version;
+
+        // First set the workflow def
+        self.workflow_def
+            .entry(workflow_name.clone())
+            .or_default()
+            .insert(version, workflow_def);
+
+        self.workflow_def_names.insert(workflow_name);
+    }
+
+    fn get_workflow_max_version(&self, workflow_name: &InlineStr) -> Option<i32> {
+        self.workflow_def
+            .get(workflow_name)
+            .and_then(|x| x.value().keys().max().copied())
+    }
+}
+
+impl MetadataDao for InMemoryMetadataDao {
+    /// ******************************************
+    /// *************** TaskDef **************
+    /// ******************************************
+    fn create_task_def(&self, task_def: TaskDef) -> TegResult<()> {
+        self.insert_or_update_task_def(task_def);
+        Ok(())
+    }
+
+    fn update_task_def(&self, task_def: TaskDef) -> TegResult<()> {
+        self.insert_or_update_task_def(task_def);
+        Ok(())
+    }
+
+    fn get_task_def(&self, name: &InlineStr) -> TegResult<Option<TaskDef>> {
+        Ok(self.task_def.get(name).map(|x| x.value().clone()))
+    }
+
+    fn get_all_task_defs(&self) -> TegResult<Vec<TaskDef>> {
+        Ok(self.task_def.iter().map(|x| x.value().clone()).collect())
+    }
+
+    fn remove_task_def(&self, name: &InlineStr) -> TegResult<()> {
+        if self.task_def.remove(name).is_none() {
+            fmt_err!(
+                NotFound,
+                "Cannot remove the task: {} - no such task definition",
+                name
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    /// ******************************************
+    /// *************** WorkflowDef **************
+    /// ******************************************
+
+    fn create_workflow_def(&self, workflow_def: WorkflowDef) -> TegResult<()> {
+        if self
+            .workflow_def
+            .get(&workflow_def.name)
+            .map(|x| x.value().contains_key(&workflow_def.version))
+            .unwrap_or(false)
+        {
+            fmt_err!(
+                Conflict,
+                "Workflow with {}/{} already exists!",
+                workflow_def.name,
+                workflow_def.version
+            )
+        } else {
+            self.insert_or_update_workflow_def(workflow_def);
+            Ok(())
+        }
+    }
+
+    fn update_workflow_def(&self, workflow_def: WorkflowDef) -> TegResult<()> {
+        self.insert_or_update_workflow_def(workflow_def);
+        Ok(())
+    }
+
+    fn toggle_workflow_def(&self, name: &InlineStr, enable: bool) -> TegResult<()> {
+        if !self.workflow_def_names.contains(name) {
+            return fmt_err!(
+                NotFound,
+                "Cannot change the status: {} - no such workflow definition",
+                name
+            );
+        }
+
+        if enable {
+            self.disabled_workflow_def.remove(name);
+        } else {
+            self.disabled_workflow_def.insert(name.clone());
+        }
+        Ok(())
+    }
+
+    fn check_workflow_def_endabled(&self, name: &str) -> TegResult<bool> {
+        if !self.workflow_def_names.contains(name) {
+            return Ok(false);
+        }
+        Ok(!self.disabled_workflow_def.contains(name))
+    }
+
+    fn get_latest_workflow_def(&self, name: &InlineStr) -> TegResult<Option<WorkflowDef>> {
+        if let Some(max_version) = self.get_workflow_max_version(name) {
+            self.get_workflow_def(name, max_version)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_all_versions(&self, name: &InlineStr) -> TegResult<Vec<WorkflowDef>> {
+        let mut workflow_defs = self
+            .workflow_def
+            .get(name)
+            .map(|x| x.value().values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        workflow_defs.sort_by_key(|x| x.version);
+        Ok(workflow_defs)
+    }
+
+    fn get_workflow_def(&self, name: &InlineStr, version: i32) -> TegResult<Option<WorkflowDef>> {
+        Ok(self
+            .workflow_def
+            .get(name)
+            .and_then(|x| x.value().get(&version).cloned()))
+    }
+
+    fn remove_workflow_def(&self, name: &InlineStr, version: i32) -> TegResult<()> {
+        if self
+            .workflow_def
+            .get_mut(name)
+            .and_then(|mut x| x.remove(&version))
+            .is_none()
+        {
+            fmt_err!(
+                NotFound,
+                "Cannot remove the workflow - no such workflow definition: {} version: {}",
+                name,
+                version
+            )
+        } else {
+            // check if there are any more versions remaining if not delete the
+            // workflow name
+            let max_version = self.get_workflow_max_version(name);
+
+            // delete workflow name
+            if max_version.is_none() {
+                self.workflow_def.remove(name);
+                self.workflow_def_names.remove(name);
+                self.disabled_workflow_def.remove(name);
+            }
+            Ok(())
+        }
+    }
+
+    fn get_all_workflow_defs(&self) -> TegResult<Vec<InlineStr>> {
+        // Get all from WORKFLOW_DEF_NAMES
+        Ok(self
+            .workflow_def_names
+            .iter()
+            .map(|x| x.key().clone())
+            .collect())
+    }
+}
@@ -0,0 +1,18 @@
+use tegmine_common::prelude::*;
+
+use crate::dao::PollDataDao;
+
+#[derive(Default)]
+pub struct InMemoryPollDataDao;
+
+impl PollDataDao for InMemoryPollDataDao {
+    fn update_last_poll_data(
+        &self,
+        _task_def_name: &str,
+        _domain: &str,
+        _worker_id: &str,
+    ) -> TegResult<()> {
+        // TODO
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use dashmap::DashMap;
+use keyed_priority_queue::KeyedPriorityQueue;
+use tegmine_common::prelude::*;
+
+use crate::dao::{PushNotifier, QueueDao, UNACK_TIMEOUT_MS};
+
+/// In-memory implementation of the DAO responsible for managing queuing for the tasks.
+///
+/// Pollers block on a condition variable of the queue, they are woken up as soon as a message is
+/// pushed, or when the next message of the queue becomes visible. Polling a queue does not create
+/// it, the pollers of an unknown queue wait until a queue is created by a push.
+#[derive(Default)]
+pub struct InMemoryQueueDao {
+    queues: DashMap<InlineStr, Arc<QueueShard>>,
+    /// notified when a queue is created
+    created: PushNotifier,
+}
+
+impl InMemoryQueueDao {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn queue(&self, queue_name: &str) -> Arc<QueueShard> {
+        if let Some(shard) = self.existing_queue(queue_name) {
+            return shard;
+        }
+        let shard = Arc::clone(self.queues.entry(queue_name.into()).or_default().value());
+        self.created.notify();
+        shard
+    }
+
+    fn existing_queue(&self, queue_name: &str) -> Option<Arc<QueueShard>> {
+        self.queues.get(queue_name).map(|x| Arc::clone(x.value()))
+    }
+}
+
+impl QueueDao for InMemoryQueueDao {
+    fn push(
+        &self,
+        queue_name: &str,
+        id: &InlineStr,
+        priority: i32,
+        offset_time_in_second: i64,
+    ) -> TegResult<()> {
+        let mut message = Message::new(id.clone());
+        if (0..=99).contains(&priority) {
+            message.priority = priority;
+        }
+
+        let shard = self.queue(queue_name);
+        {
+            let mut queue = shard.lock();
+            queue.messages.insert(message.id.clone(), message);
+            queue.set_visible_at(
+                id,
+                Utc::now().timestamp_millis() + offset_time_in_second * 1000,
+            );
+        }
+        shard.changed.notify_all();
+        Ok(())
+    }
+
+    fn pop(&self, queue_name: &str, count: i32, timeout_ms: i32) -> TegResult<Vec<InlineStr>> {
+        let mut message_ids = Vec::with_capacity(count.max(0) as usize);
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms.max(0) as u64);
+
+        let shard = loop {
+            let version = self.created.version();
+            if let Some(shard) = self.existing_queue(queue_name) {
+                break shard;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(message_ids);
+            }
+            self.created.wait(version, deadline - now);
+        };
+        let mut queue = shard.lock();
+        loop {
+            let next_visible_at = queue.pop_visible(count, &mut message_ids);
+            if message_ids.len() as i32 >= count {
+                break;
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            // wait for a push, or until the next message becomes visible
+            let mut wait_time = deadline - now;
+            if let Some(next_visible_at) = next_visible_at {
+                let visible_in = (next_visible_at - Utc::now().timestamp_millis()).max(0) as u64;
+                wait_time = wait_time.min(Duration::from_millis(visible_in));
+            }
+            queue = shard
+                .changed
+                .wait_timeout(queue, wait_time)
+                .expect("lock poisoned")
+                .0;
+        }
+
+        Ok(message_ids)
+    }
+
+    fn remove(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<()> {
+        if let Some(shard) = self.existing_queue(queue_name) {
+            shard.lock().remove(message_id);
+        }
+        Ok(())
+    }
+
+    fn ack(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<bool> {
+        if let Some(shard) = self.existing_queue(queue_name) {
+            let mut queue = shard.lock();
+            if queue
+                .messages
+                .get(message_id)
+                .map(|x| x.popped)
+                .unwrap_or(false)
+            {
+                queue.remove(message_id);
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// By default, the message is removed and pushed backed with postponeDurationInSeconds to be
+    /// backwards compatible.
+    fn postpone(
+        &self,
+        queue_name: &str,
+        message_id: &InlineStr,
+        priority: i32,
+        postpone_duration_in_seconds: i64,
+    ) -> TegResult<bool> {
+        self.remove(queue_name, message_id)?;
+        self.push(
+            queue_name,
+            message_id,
+            priority,
+            postpone_duration_in_seconds,
+        )?;
+        Ok(true)
+    }
+
+    fn set_unack_timeout(
+        &self,
+        queue_name: &str,
+        message_id: &InlineStr,
+        unack_timeout_ms: i64,
+    ) -> TegResult<bool> {
+        if let Some(shard) = self.existing_queue(queue_name) {
+            let mut queue = shard.lock();
+            if queue.messages.contains_key(message_id) {
+                queue.set_visible_at(message_id, Utc::now().timestamp_millis() + unack_timeout_ms);
+                drop(queue);
+                shard.changed.notify_all();
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn exists(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<bool> {
+        Ok(self
+            .existing_queue(queue_name)
+            .map(|x| x.lock().messages.contains_key(message_id))
+            .unwrap_or(false))
+    }
+
+    fn size(&self, queue_name: &str) -> TegResult<i64> {
+        Ok(self
+            .existing_queue(queue_name)
+            .map(|x| x.lock().messages.len() as i64)
+            .unwrap_or(0))
+    }
+
+    fn flush(&self, queue_name: &str) -> TegResult<()> {
+        if let Some(shard) = self.existing_queue(queue_name) {
+            // the pollers waiting on the queue keep waiting for new messages
+            *shard.lock() = Queue::default();
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct QueueShard {
+    queue: Mutex<Queue>,
+    /// notified when a message is pushed, or its visibility changes
+    changed: Condvar,
+}
+
+impl QueueShard {
+    fn lock(&self) -> MutexGuard<Queue> {
+        self.queue.lock().expect("lock poisoned")
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    /// The message ids ordered by the time they become visible, then by their priority.
+    priority_queue: KeyedPriorityQueue<InlineStr, i64>,
+    messages: HashMap<InlineStr, Message>,
+}
+
+impl Queue {
+    fn set_visible_at(&mut self, message_id: &InlineStr, visible_at: i64) {
+        let priority = self
+            .messages
+            .get(message_id)
+            .map(|x| x.priority)
+            .unwrap_or_default();
+        // the priority queue pops the greatest score first
+        let score = visible_at * 100 + priority as i64;
+        self.priority_queue.push(message_id.clone(), -score);
+    }
+
+    fn visible_at(score: i64) -> i64 {
+        -score / 100
+    }
+
+    /// Pops the messages which are visible now, until `message_ids` holds `count` messages.
+    ///
+    /// return the time the next message becomes visible, if any
+    fn pop_visible(&mut self, count: i32, message_ids: &mut Vec<InlineStr>) -> Option<i64> {
+        let now = Utc::now().timestamp_millis();
+        while (message_ids.len() as i32) < count {
+            let message_id = match self.priority_queue.peek() {
+                Some((message_id, score)) if Self::visible_at(*score) <= now => message_id.clone(),
+                Some((_, score)) => return Some(Self::visible_at(*score)),
+                None => return None,
+            };
+
+            // the message is invisible until it is ack'ed or the unack timeout elapses
+            self.set_visible_at(&message_id, now + UNACK_TIMEOUT_MS);
+            if let Some(message) = self.messages.get_mut(&message_id) {
+                message.popped = true;
+            }
+            message_ids.push(message_id);
+        }
+        None
+    }
+
+    fn remove(&mut self, message_id: &InlineStr) {
+        self.priority_queue.remove(message_id);
+        self.messages.remove(message_id);
+    }
+}
+
+struct Message {
+    id: InlineStr,
+    /// 0-99, 0 is highest priority
+    priority: i32,
+    /// whether the message has been popped and waits for an ack
+    popped: bool,
+}
+
+impl Message {
+    fn new(id: InlineStr) -> Self {
+        Self {
+            id,
+            priority: 0,
+            popped: false,
+        }
+    }
+}
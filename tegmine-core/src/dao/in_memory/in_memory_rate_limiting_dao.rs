@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use chrono::Utc;
+use dashmap::DashMap;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskDef;
+
+use crate::dao::RateLimitingDao;
+use crate::model::TaskModel;
+
+/// The minimum time between two purges of the windows without any task let through.
+const PURGE_INTERVAL_MS: i64 = 60000;
+
+/// In-memory implementation of `RateLimitingDao`, based on a sliding window of the tasks of a task
+/// definition let through, per domain.
+#[derive(Default)]
+pub struct InMemoryRateLimitingDao {
+    /// the times the tasks let through leave the window, in ms
+    windows: DashMap<InlineStr, VecDeque<i64>>,
+    last_purge_ms: AtomicI64,
+}
+
+impl InMemoryRateLimitingDao {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The tasks of different domains are usually executed by different workers, so they are
+    /// limited separately.
+    fn rate_limit_key(task: &TaskModel) -> InlineStr {
+        if task.domain.is_empty() {
+            task.task_def_name.clone()
+        } else {
+            InlineStr::from(format!("{}:{}", task.task_def_name, task.domain))
+        }
+    }
+
+    /// Removes the windows whose tasks have all left them, at most once per `PURGE_INTERVAL_MS`.
+    fn purge_expired_windows(&self, now_ms: i64) {
+        if now_ms - self.last_purge_ms.load(Ordering::Relaxed) < PURGE_INTERVAL_MS {
+            return;
+        }
+        self.last_purge_ms.store(now_ms, Ordering::Relaxed);
+        self.windows
+            .retain(|_, window| window.back().map(|x| *x > now_ms).unwrap_or(false));
+    }
+}
+
+impl RateLimitingDao for InMemoryRateLimitingDao {
+    fn exceeds_rate_limit_per_frequency(
+        &self,
+        task: &TaskModel,
+        task_def: Option<&TaskDef>,
+    ) -> bool {
+        // the latest task definition takes precedence over the values copied to the task
+        let (rate_limit, frequency_in_seconds) = match task_def {
+            Some(task_def) => (
+                task_def.rate_limit_per_frequency.unwrap_or(0),
+                task_def.rate_limit_frequency_in_seconds.unwrap_or(1),
+            ),
+            None => (
+                task.rate_limit_per_frequency,
+                task.rate_limit_frequency_in_seconds,
+            ),
+        };
+        if rate_limit <= 0 || frequency_in_seconds <= 0 {
+            return false;
+        }
+
+        let now = Utc::now().timestamp_millis();
+        self.purge_expired_windows(now);
+
+        let key = Self::rate_limit_key(task);
+        let mut window = self.windows.entry(key.clone()).or_default();
+        while window.front().map(|x| *x <= now).unwrap_or(false) {
+            window.pop_front();
+        }
+
+        if window.len() >= rate_limit as usize {
+            debug!(
+                "Task: {} with task def name: {} rate limited, {} tasks in the last {} seconds, key: {}",
+                task.task_id,
+                task.task_def_name,
+                window.len(),
+                frequency_in_seconds,
+                key
+            );
+            return true;
+        }
+        window.push_back(now + frequency_in_seconds as i64 * 1000);
+        false
+    }
+}
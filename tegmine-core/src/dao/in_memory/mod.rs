@@ -0,0 +1,13 @@
+mod in_memory_execution_dao;
+mod in_memory_index_dao;
+mod in_memory_metadata_dao;
+mod in_memory_poll_data_dao;
+mod in_memory_queue_dao;
+mod in_memory_rate_limiting_dao;
+
+pub use in_memory_execution_dao::InMemoryExecutionDao;
+pub use in_memory_index_dao::InMemoryIndexDao;
+pub use in_memory_metadata_dao::InMemoryMetadataDao;
+pub use in_memory_poll_data_dao::InMemoryPollDataDao;
+pub use in_memory_queue_dao::InMemoryQueueDao;
+pub use in_memory_rate_limiting_dao::InMemoryRateLimitingDao;
@@ -1,13 +1,14 @@
-use crate::model::{TaskSummary, WorkflowSummary};
+use tegmine_common::prelude::*;
 
-pub struct IndexDao;
+use crate::model::{TaskSummary, WorkflowSummary};
 
-impl IndexDao {
+/// DAO to index the workflow and task details for searching.
+pub trait IndexDao: Send + Sync {
     /// This method should return an unique identifier of the indexed doc
-    pub fn index_workflow(_workflow: WorkflowSummary) {}
+    fn index_workflow(&self, workflow: WorkflowSummary) -> TegResult<()>;
 
     /// This method should return an unique identifier of the indexed doc
-    pub fn async_index_workflow(_workflow: WorkflowSummary) {}
+    fn async_index_workflow(&self, workflow: WorkflowSummary) -> TegResult<()>;
 
-    pub fn index_task(_task: TaskSummary) {}
+    fn index_task(&self, task: TaskSummary) -> TegResult<()>;
 }
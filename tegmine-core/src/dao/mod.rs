@@ -1,17 +1,27 @@
 mod concurrent_execution_limit_dao;
+mod daos;
 mod event_handler_dao;
 mod execution_dao;
+mod in_memory;
 mod index_dao;
 mod metadata_dao;
 mod poll_data_dao;
 mod queue_dao;
 mod rate_limiting_dao;
+mod sqlite;
 
 pub use concurrent_execution_limit_dao::ConcurrentExecutionLimitDao;
+pub use daos::Daos;
 pub use event_handler_dao::EventHandlerDao;
 pub use execution_dao::ExecutionDao;
+pub use in_memory::{
+    InMemoryExecutionDao, InMemoryIndexDao, InMemoryMetadataDao, InMemoryPollDataDao,
+    InMemoryQueueDao, InMemoryRateLimitingDao,
+};
 pub use index_dao::IndexDao;
 pub use metadata_dao::MetadataDao;
 pub use poll_data_dao::PollDataDao;
-pub use queue_dao::QueueDao;
+pub(crate) use queue_dao::PushNotifier;
+pub use queue_dao::{QueueDao, DECIDER_QUEUE, UNACK_TIMEOUT_MS};
 pub use rate_limiting_dao::RateLimitingDao;
+pub use sqlite::{SqliteDataSource, SqliteExecutionDao, SqliteMetadataDao, SqliteQueueDao};
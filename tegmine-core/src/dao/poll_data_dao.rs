@@ -1,15 +1,12 @@
 use tegmine_common::prelude::*;
 
-pub struct PollDataDao;
-
-impl PollDataDao {
+/// An abstraction to enable different PollData store implementations
+pub trait PollDataDao: Send + Sync {
     /// Updates the `PollData` information with the most recently polled data for a task queue.
-    pub fn update_last_poll_data(
+    fn update_last_poll_data(
+        &self,
         task_def_name: &str,
         domain: &str,
         worker_id: &str,
-    ) -> TegResult<()> {
-        // TODO
-        Ok(())
-    }
+    ) -> TegResult<()>;
 }
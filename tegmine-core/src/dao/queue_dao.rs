@@ -1,147 +1,99 @@
+use std::sync::{Condvar, Mutex};
 use std::time::Duration;
 
-use chrono::Utc;
-use dashmap::DashMap;
-use keyed_priority_queue::KeyedPriorityQueue;
-use rhai::Instant;
 use tegmine_common::prelude::*;
 
+/// The queue the workflows to be evaluated by the decider are pushed to.
+pub const DECIDER_QUEUE: &str = "_deciderQueue";
+
+/// The time (in milliseconds) a popped message stays invisible before it is redelivered, unless
+/// it is ack'ed or its unack timeout is changed.
+pub const UNACK_TIMEOUT_MS: i64 = 60 * 1000;
+
 /// DAO responsible for managing queuing for the tasks.
-pub struct QueueDao;
-
-static QUEUES_PRIORITY: Lazy<DashMap<InlineStr, KeyedPriorityQueue<InlineStr, i64>>> =
-    Lazy::new(|| DashMap::new());
-static QUEUES_MESSAGE: Lazy<DashMap<InlineStr, DashMap<InlineStr, Message>>> =
-    Lazy::new(|| DashMap::new());
-
-impl QueueDao {
-    pub const DECIDER_QUEUE: &'static str = "_deciderQueue";
-
-    pub fn push(queue_name: &str, id: &InlineStr, priority: i32, offset_time_in_second: i64) {
-        let mut message = Message::new(id.clone());
-        message.timeout_millis = offset_time_in_second * 1000;
-        if priority >= 0 && priority <= 99 {
-            message.priority = priority;
-        }
-        if !QUEUES_PRIORITY.contains_key(queue_name) {
-            QUEUES_PRIORITY.insert(queue_name.into(), KeyedPriorityQueue::new());
-            QUEUES_MESSAGE.insert(queue_name.into(), DashMap::new());
-        }
-
-        let score = Utc::now().timestamp_millis() * 100
-            + message.timeout_millis * 100
-            + message.priority as i64;
-
-        QUEUES_PRIORITY
-            .get_mut(queue_name)
-            .expect("not empty")
-            .value_mut()
-            .push(message.id.clone(), -score);
-        QUEUES_MESSAGE
-            .get_mut(queue_name)
-            .expect("not empty")
-            .value_mut()
-            .insert(message.id.clone(), message);
-    }
+pub trait QueueDao: Send + Sync {
+    /// `priority` is 0-99, 0 is highest priority. The message is not available for polls until
+    /// `offset_time_in_second` is elapsed.
+    fn push(
+        &self,
+        queue_name: &str,
+        id: &InlineStr,
+        priority: i32,
+        offset_time_in_second: i64,
+    ) -> TegResult<()>;
 
     /// If the popped messages are not acknowledge in a timely manner, they are pushed back
-    /// into the queue.
+    /// into the queue. The popped messages are invisible for `UNACK_TIMEOUT_MS`, after which they
+    /// are delivered again unless `ack` is called.
+    ///
+    /// Blocks until `count` messages are popped or `timeout_ms` elapses. Implementations should
+    /// wake waiting pollers when a message is pushed rather than polling the storage repeatedly.
     ///
     /// return list of elements from the named queue
-    pub fn pop(queue_name: &str, count: i32, timeout_ms: i32) -> TegResult<Vec<InlineStr>> {
-        if let Some(mut queue) = QUEUES_PRIORITY.get_mut(queue_name) {
-            let mut wait_count = count;
-            let mut wait_time = timeout_ms;
-            let mut message_ids = Vec::with_capacity(count as usize);
-
-            let mut start = Instant::now();
-            loop {
-                if wait_count <= 0 {
-                    break;
-                }
-
-                let mut found = false;
-                if let Some(message_pri) = queue.value().peek() {
-                    if (-message_pri.1 / 100) <= Utc::now().timestamp_millis() {
-                        let message_id = message_pri.0.clone();
-
-                        queue.value_mut().remove(&message_id);
-                        message_ids.push(message_id);
-                        found = true;
-                        wait_count -= 1;
-                    }
-                }
-
-                if !found {
-                    wait_time -= start.elapsed().as_millis() as i32;
-                    start = Instant::now();
-
-                    if wait_time > 0 {
-                        // at least sleep 10ms
-                        std::thread::sleep(Duration::from_millis((wait_time as u64).min(10)));
-                    } else {
-                        break;
-                    }
-                }
-            }
-
-            Ok(message_ids)
-        } else {
-            Ok(Vec::default())
-        }
-    }
+    fn pop(&self, queue_name: &str, count: i32, timeout_ms: i32) -> TegResult<Vec<InlineStr>>;
 
-    pub fn remove(queue_name: &str, message_id: &InlineStr) -> TegResult<()> {
-        if let Some(mut queue) = QUEUES_PRIORITY.get_mut(queue_name) {
-            queue.value_mut().remove(message_id);
-        }
+    fn remove(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<()>;
 
-        if let Some(mut queue) = QUEUES_MESSAGE.get_mut(queue_name) {
-            queue.value_mut().remove(message_id);
-        }
+    /// Removes a popped message from the queue, so that it is not delivered again.
+    ///
+    /// return true if the message was found and ack'ed
+    fn ack(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<bool>;
 
-        Ok(())
-    }
+    /// Sets the unack timeout on the message, it won't be available for polls until
+    /// `unack_timeout_ms` is elapsed. Useful when an extended lease is required for a message by
+    /// the consumer before sending ack.
+    ///
+    /// return true if the message was found
+    fn set_unack_timeout(
+        &self,
+        queue_name: &str,
+        message_id: &InlineStr,
+        unack_timeout_ms: i64,
+    ) -> TegResult<bool>;
 
-    /// return true if the message was found and ack'ed
-    pub fn ack(_queue_name: &str, _message_id: &InlineStr) -> bool {
-        // no need to implement ack
-        true
-    }
+    /// return true if the message exists in the queue, popped or not
+    fn exists(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<bool>;
+
+    /// return the number of messages in the queue, including the popped but not ack'ed ones
+    fn size(&self, queue_name: &str) -> TegResult<i64>;
+
+    /// Removes all the messages of the queue.
+    fn flush(&self, queue_name: &str) -> TegResult<()>;
 
     /// Postpone a given message with postponeDurationInSeconds, so that the message won't be
-    /// available for further polls until specified duration. By default, the message is removed and
-    /// pushed backed with postponeDurationInSeconds to be backwards compatible.
-    pub fn postpone(
+    /// available for further polls until specified duration.
+    fn postpone(
+        &self,
         queue_name: &str,
         message_id: &InlineStr,
         priority: i32,
         postpone_duration_in_seconds: i64,
-    ) -> TegResult<bool> {
-        Self::remove(queue_name, message_id)?;
-        Self::push(
-            queue_name,
-            message_id,
-            priority,
-            postpone_duration_in_seconds,
-        );
-        Ok(true)
-    }
+    ) -> TegResult<bool>;
 }
 
-struct Message {
-    id: InlineStr,
-    timeout_millis: i64,
-    /// 0-99, 0 is highest priority
-    priority: i32,
+/// Counts the changes of a queue, so that a poller can not miss a change between its query and
+/// its wait.
+#[derive(Default)]
+pub(crate) struct PushNotifier {
+    version: Mutex<u64>,
+    pushed: Condvar,
 }
 
-impl Message {
-    fn new(id: InlineStr) -> Self {
-        Self {
-            id,
-            timeout_millis: 0,
-            priority: 0,
-        }
+impl PushNotifier {
+    pub fn version(&self) -> u64 {
+        *self.version.lock().expect("lock poisoned")
+    }
+
+    pub fn notify(&self) {
+        *self.version.lock().expect("lock poisoned") += 1;
+        self.pushed.notify_all();
+    }
+
+    /// Blocks until `notify` is called after `version` was read, or `timeout` elapses.
+    pub fn wait(&self, version: u64, timeout: Duration) {
+        let current = self.version.lock().expect("lock poisoned");
+        self.pushed
+            .wait_timeout_while(current, timeout, |x| *x == version)
+            .expect("lock poisoned");
     }
 }
@@ -0,0 +1,9 @@
+mod sqlite_data_source;
+mod sqlite_execution_dao;
+mod sqlite_metadata_dao;
+mod sqlite_queue_dao;
+
+pub use sqlite_data_source::SqliteDataSource;
+pub use sqlite_execution_dao::SqliteExecutionDao;
+pub use sqlite_metadata_dao::SqliteMetadataDao;
+pub use sqlite_queue_dao::SqliteQueueDao;
@@ -0,0 +1,81 @@
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Duration;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use tegmine_common::prelude::*;
+use tokio::runtime::Runtime;
+
+use crate::utils::RuntimeUtils;
+
+/// Connection pool and schema migrations shared by the sqlite backed DAOs. The engine is driven by
+/// plain threads, so every query is executed on a small dedicated tokio runtime.
+pub struct SqliteDataSource {
+    pool: SqlitePool,
+    /// only taken when the data source is dropped
+    runtime: Option<Runtime>,
+}
+
+impl SqliteDataSource {
+    const MAX_CONNECTIONS: u32 = 8;
+    const BUSY_TIMEOUT_SECS: u64 = 5;
+
+    /// Opens (or creates) the database at `url`, e.g. `sqlite://tegmine.db`, and applies the
+    /// pending migrations
+    pub fn connect(url: &str) -> TegResult<Arc<Self>> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("tegmine-sqlite")
+            .enable_all()
+            .build()?;
+
+        let options = SqliteConnectOptions::from_str(url)
+            .map_err(ErrorCode::from_std_error)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(Duration::from_secs(Self::BUSY_TIMEOUT_SECS));
+
+        let pool = RuntimeUtils::block_on(
+            &runtime,
+            SqlitePoolOptions::new()
+                .max_connections(Self::MAX_CONNECTIONS)
+                .connect_with(options),
+        )
+        .map_err(ErrorCode::from_std_error)?;
+
+        RuntimeUtils::block_on(&runtime, sqlx::migrate!("./migrations/sqlite").run(&pool))
+            .map_err(ErrorCode::from_std_error)?;
+        info!("sqlite data source connected: {}", url);
+
+        Ok(Arc::new(Self {
+            pool,
+            runtime: Some(runtime),
+        }))
+    }
+
+    pub(crate) fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    pub(crate) fn block_on<F>(&self, future: F) -> F::Output
+    where
+        F: Future + Send,
+        F::Output: Send,
+    {
+        RuntimeUtils::block_on(
+            self.runtime
+                .as_ref()
+                .expect("runtime is only taken on drop"),
+            future,
+        )
+    }
+}
+
+impl Drop for SqliteDataSource {
+    fn drop(&mut self) {
+        // dropping a runtime blocks, which panics when dropped from an async context
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
@@ -0,0 +1,593 @@
+use std::str::FromStr;
+
+use chrono::Utc;
+use sqlx::{Sqlite, Transaction};
+use tegmine_common::prelude::*;
+
+use super::SqliteDataSource;
+use crate::dao::{ConcurrentExecutionLimitDao, ExecutionDao};
+use crate::metrics::Monitors;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::WorkflowStatus;
+
+/// Sqlite implementation of the data access layer for storing workflow executions, keeps the same
+/// semantics as the `InMemoryExecutionDao`
+pub struct SqliteExecutionDao {
+    data_source: Arc<SqliteDataSource>,
+}
+
+impl SqliteExecutionDao {
+    pub fn new(data_source: Arc<SqliteDataSource>) -> Self {
+        Self { data_source }
+    }
+
+    async fn update_task_in_tx(
+        tx: &mut Transaction<'_, Sqlite>,
+        task: &mut TaskModel,
+    ) -> TegResult<()> {
+        let task_definition = task.get_task_definition();
+
+        if task_definition.map(|x| x.concurrency_limit()).unwrap_or(0) > 0 {
+            let in_progress_status = task.status == TaskStatus::InProgress;
+            sqlx::query(
+                "UPDATE task_in_progress SET in_progress_status = ? \
+                 WHERE task_def_name = ? AND task_id = ?",
+            )
+            .bind(in_progress_status)
+            .bind(task.task_def_name.as_str())
+            .bind(task.task_id.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(ErrorCode::from_std_error)?;
+            debug!(
+                "Workflow Task in_progress_status set to {} with taskDefName: {}, workflowId: {}, taskId: {}, taskType: {}, taskStatus: {} during updateTask",
+                in_progress_status,
+                task.task_def_name,
+                task.workflow_instance_id,
+                task.task_id,
+                task.task_type,
+                task.status.as_ref()
+            );
+        }
+
+        sqlx::query(
+            "INSERT INTO task (task_id, json_data) VALUES (?, ?) \
+             ON CONFLICT (task_id) DO UPDATE SET json_data = excluded.json_data",
+        )
+        .bind(task.task_id.as_str())
+        .bind(task.to_json().to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(ErrorCode::from_std_error)?;
+        debug!(
+            "Workflow task payload saved to task with taskId: {}, workflowId: {}, taskType: {} during updateTask",
+            task.task_id, task.workflow_instance_id, task.task_type
+        );
+
+        if task.status.is_terminal() {
+            sqlx::query("DELETE FROM task_in_progress WHERE task_def_name = ? AND task_id = ?")
+                .bind(task.task_def_name.as_str())
+                .bind(task.task_id.as_str())
+                .execute(&mut *tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+            debug!(
+                "Workflow Task removed from task_in_progress with taskDefName: {}, workflowId: {}, taskId: {}, taskType: {}, taskStatus: {} during updateTask",
+                task.task_def_name,
+                task.workflow_instance_id,
+                task.task_id,
+                task.task_type,
+                task.status.as_ref()
+            );
+        }
+
+        Self::correlate_task_to_workflow_in_ds(tx, &task.task_id, &task.workflow_instance_id).await
+    }
+
+    async fn remove_task_mappings(
+        tx: &mut Transaction<'_, Sqlite>,
+        task: &TaskModel,
+    ) -> TegResult<()> {
+        sqlx::query("DELETE FROM task_scheduled WHERE workflow_id = ? AND task_key = ?")
+            .bind(task.workflow_instance_id.as_str())
+            .bind(task.get_task_key().as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(ErrorCode::from_std_error)?;
+        sqlx::query("DELETE FROM task_in_progress WHERE task_def_name = ? AND task_id = ?")
+            .bind(task.task_def_name.as_str())
+            .bind(task.task_id.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(ErrorCode::from_std_error)?;
+        sqlx::query("DELETE FROM workflow_to_task WHERE workflow_id = ? AND task_id = ?")
+            .bind(task.workflow_instance_id.as_str())
+            .bind(task.task_id.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(())
+    }
+
+    fn parse_task(json_data: &str) -> TegResult<TaskModel> {
+        let json: serde_json::Value = serde_json::from_str(json_data)?;
+        TaskModel::try_from(&json)
+    }
+
+    /// Inserts a new workflow/ updates an existing workflow in the datastore. Additionally, if a
+    /// workflow is in terminal state, it is removed from the set of pending workflows.
+    fn insert_or_update_workflow(&self, workflow: &WorkflowModel, update: bool) -> TegResult<()> {
+        // Store the workflow object, tasks are stored separately
+        let json_data = workflow.to_json().to_string();
+
+        self.data_source.block_on(async {
+            let mut tx = self
+                .data_source
+                .pool()
+                .begin()
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+
+            sqlx::query(
+                "INSERT INTO workflow (workflow_id, correlation_id, status, json_data) \
+                 VALUES (?, ?, ?, ?) ON CONFLICT (workflow_id) DO UPDATE SET \
+                 correlation_id = excluded.correlation_id, status = excluded.status, \
+                 json_data = excluded.json_data",
+            )
+            .bind(workflow.workflow_id.as_str())
+            .bind(workflow.correlation_id.as_str())
+            .bind(workflow.status.as_ref())
+            .bind(json_data)
+            .execute(&mut tx)
+            .await
+            .map_err(ErrorCode::from_std_error)?;
+
+            if !update {
+                // Add to list of workflows for a workflow_def
+                sqlx::query(
+                    "INSERT OR IGNORE INTO workflow_def_to_workflow \
+                     (workflow_def, create_time, workflow_id) VALUES (?, ?, ?)",
+                )
+                .bind(workflow.workflow_definition.name.as_str())
+                .bind(workflow.create_time)
+                .bind(workflow.workflow_id.as_str())
+                .execute(&mut tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+            }
+
+            // Add or remove from the pending workflows
+            let sql = if workflow.status.is_terminal() {
+                "DELETE FROM workflow_pending WHERE workflow_type = ? AND workflow_id = ?"
+            } else {
+                "INSERT OR IGNORE INTO workflow_pending (workflow_type, workflow_id) VALUES (?, ?)"
+            };
+            sqlx::query(sql)
+                .bind(workflow.workflow_definition.name.as_str())
+                .bind(workflow.workflow_id.as_str())
+                .execute(&mut tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+
+            tx.commit().await.map_err(ErrorCode::from_std_error)
+        })
+    }
+
+    /// Stores the correlation of a task to the workflow instance in the datastore
+    async fn correlate_task_to_workflow_in_ds(
+        tx: &mut Transaction<'_, Sqlite>,
+        task_id: &InlineStr,
+        workflow_instance_id: &InlineStr,
+    ) -> TegResult<()> {
+        sqlx::query("INSERT OR IGNORE INTO workflow_to_task (workflow_id, task_id) VALUES (?, ?)")
+            .bind(workflow_instance_id.as_str())
+            .bind(task_id.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(ErrorCode::from_std_error)?;
+        debug!(
+            "Task mapped in workflow_to_task with workflowId: {}, taskId: {}",
+            workflow_instance_id, task_id
+        );
+        Ok(())
+    }
+
+    fn validate(task: &TaskModel) -> TegResult<()> {
+        if task.task_id.is_empty() {
+            fmt_err!(IllegalArgument, "task object cannot be null")
+        } else if task.workflow_instance_id.is_empty() {
+            fmt_err!(IllegalArgument, "Workflow instance id cannot be null")
+        } else if task.reference_task_name.is_empty() {
+            fmt_err!(IllegalArgument, "Task reference name cannot be null")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl ExecutionDao for SqliteExecutionDao {
+    /// ******************************************
+    /// *************** Task *********************
+    /// ******************************************
+
+    fn create_tasks(&self, tasks: &mut [&mut TaskModel]) -> TegResult<()> {
+        self.data_source.block_on(async {
+            for task in tasks {
+                Self::validate(task)?;
+
+                let mut tx = self
+                    .data_source
+                    .pool()
+                    .begin()
+                    .await
+                    .map_err(ErrorCode::from_std_error)?;
+
+                let task_key = task.get_task_key();
+                let added = sqlx::query(
+                    "INSERT OR IGNORE INTO task_scheduled (workflow_id, task_key, task_id) \
+                     VALUES (?, ?, ?)",
+                )
+                .bind(task.workflow_instance_id.as_str())
+                .bind(task_key.as_str())
+                .bind(task.task_id.as_str())
+                .execute(&mut tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?
+                .rows_affected()
+                    > 0;
+                if !added {
+                    debug!(
+                        "Task already scheduled, skipping the run {}, ref={}, key={}",
+                        task.task_id, task.reference_task_name, task_key
+                    );
+                    tx.rollback().await.map_err(ErrorCode::from_std_error)?;
+                    continue;
+                }
+
+                if !task.status.is_terminal() && task.scheduled_time == 0 {
+                    task.scheduled_time = Utc::now().timestamp_millis();
+                }
+
+                Self::correlate_task_to_workflow_in_ds(
+                    &mut tx,
+                    &task.task_id,
+                    &task.workflow_instance_id,
+                )
+                .await?;
+
+                sqlx::query(
+                    "INSERT OR IGNORE INTO task_in_progress \
+                     (task_def_name, task_id, workflow_id, created_on) VALUES (?, ?, ?, ?)",
+                )
+                .bind(task.task_def_name.as_str())
+                .bind(task.task_id.as_str())
+                .bind(task.workflow_instance_id.as_str())
+                .bind(Utc::now().timestamp_millis())
+                .execute(&mut tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+                debug!(
+                    "Scheduled task added to task_in_progress with taskDefName: {}, workflowId: {}, taskId: {}, taskType: {} during createTasks",
+                    task.task_def_name, task.workflow_instance_id, task.task_id, task.task_type
+                );
+
+                Self::update_task_in_tx(&mut tx, task).await?;
+                tx.commit().await.map_err(ErrorCode::from_std_error)?;
+            }
+            Ok::<_, ErrorCode>(())
+        })
+    }
+
+    fn update_task(&self, task: &mut TaskModel) -> TegResult<()> {
+        self.data_source.block_on(async {
+            let mut tx = self
+                .data_source
+                .pool()
+                .begin()
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+            Self::update_task_in_tx(&mut tx, task).await?;
+            tx.commit().await.map_err(ErrorCode::from_std_error)
+        })
+    }
+
+    fn remove_task(&self, task_id: &InlineStr) -> TegResult<bool> {
+        if let Some(task) = self.get_task(task_id)? {
+            self.data_source.block_on(async {
+                let mut tx = self
+                    .data_source
+                    .pool()
+                    .begin()
+                    .await
+                    .map_err(ErrorCode::from_std_error)?;
+                Self::remove_task_mappings(&mut tx, &task).await?;
+                sqlx::query("DELETE FROM task WHERE task_id = ?")
+                    .bind(task_id.as_str())
+                    .execute(&mut tx)
+                    .await
+                    .map_err(ErrorCode::from_std_error)?;
+                tx.commit().await.map_err(ErrorCode::from_std_error)
+            })?;
+            Ok(true)
+        } else {
+            warn!("No such task found by id {}", task_id);
+            Ok(false)
+        }
+    }
+
+    fn get_task(&self, task_id: &InlineStr) -> TegResult<Option<TaskModel>> {
+        let row: Option<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT json_data FROM task WHERE task_id = ?")
+                    .bind(task_id.as_str())
+                    .fetch_optional(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        if let Some((json_data,)) = row {
+            Ok(Some(Self::parse_task(&json_data)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_tasks(&self, task_ids: Vec<InlineStr>) -> TegResult<Vec<TaskModel>> {
+        let mut tasks = Vec::with_capacity(task_ids.len());
+        for task_id in &task_ids {
+            if let Some(task) = self.get_task(task_id)? {
+                tasks.push(task);
+            }
+        }
+        Ok(tasks)
+    }
+
+    fn get_tasks_for_workflow(&self, workflow_id: &InlineStr) -> TegResult<Vec<TaskModel>> {
+        let rows: Vec<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as(
+                    "SELECT t.json_data FROM workflow_to_task w \
+                     INNER JOIN task t ON t.task_id = w.task_id WHERE w.workflow_id = ?",
+                )
+                .bind(workflow_id.as_str())
+                .fetch_all(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        let mut tasks = Vec::with_capacity(rows.len());
+        for (json_data,) in rows {
+            tasks.push(Self::parse_task(&json_data)?);
+        }
+        Ok(tasks)
+    }
+
+    fn get_pending_tasks_for_task_type(&self, task_type: &str) -> TegResult<Vec<TaskModel>> {
+        let rows: Vec<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as(
+                    "SELECT t.json_data FROM task_in_progress p \
+                     INNER JOIN task t ON t.task_id = p.task_id \
+                     WHERE json_extract(t.json_data, '$.taskType') = ?",
+                )
+                .bind(task_type)
+                .fetch_all(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        let mut tasks = Vec::with_capacity(rows.len());
+        for (json_data,) in rows {
+            let task = Self::parse_task(&json_data)?;
+            if !task.status.is_terminal() {
+                tasks.push(task);
+            }
+        }
+        Ok(tasks)
+    }
+
+    /// ******************************************
+    /// *************** Workflow *****************
+    /// ******************************************
+
+    fn create_workflow(&self, workflow: &WorkflowModel) -> TegResult<()> {
+        self.insert_or_update_workflow(workflow, false)
+    }
+
+    fn update_workflow(&self, workflow: &WorkflowModel) -> TegResult<()> {
+        self.insert_or_update_workflow(workflow, true)
+    }
+
+    /// return true if the deletion is successful, false otherwise
+    fn remove_workflow(&self, workflow_id: &InlineStr) -> TegResult<bool> {
+        if let Some(workflow) = self.get_workflow(workflow_id)? {
+            self.data_source.block_on(async {
+                let mut tx = self
+                    .data_source
+                    .pool()
+                    .begin()
+                    .await
+                    .map_err(ErrorCode::from_std_error)?;
+
+                // Remove from lists
+                sqlx::query(
+                    "DELETE FROM workflow_def_to_workflow \
+                     WHERE workflow_def = ? AND create_time = ? AND workflow_id = ?",
+                )
+                .bind(workflow.workflow_definition.name.as_str())
+                .bind(workflow.create_time)
+                .bind(workflow_id.as_str())
+                .execute(&mut tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+                sqlx::query(
+                    "DELETE FROM workflow_pending WHERE workflow_type = ? AND workflow_id = ?",
+                )
+                .bind(workflow.workflow_definition.name.as_str())
+                .bind(workflow_id.as_str())
+                .execute(&mut tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+
+                // Remove the object
+                sqlx::query("DELETE FROM workflow WHERE workflow_id = ?")
+                    .bind(workflow_id.as_str())
+                    .execute(&mut tx)
+                    .await
+                    .map_err(ErrorCode::from_std_error)?;
+
+                // Remove task
+                for task in &workflow.tasks {
+                    Self::remove_task_mappings(&mut tx, task).await?;
+                    sqlx::query("DELETE FROM task WHERE task_id = ?")
+                        .bind(task.task_id.as_str())
+                        .execute(&mut tx)
+                        .await
+                        .map_err(ErrorCode::from_std_error)?;
+                }
+                tx.commit().await.map_err(ErrorCode::from_std_error)
+            })?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn remove_from_pending_workflow(
+        &self,
+        workflow_type: &InlineStr,
+        workflow_id: &InlineStr,
+    ) -> TegResult<()> {
+        self.data_source.block_on(async {
+            let mut tx = self
+                .data_source
+                .pool()
+                .begin()
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+            sqlx::query("DELETE FROM task_scheduled WHERE workflow_id = ?")
+                .bind(workflow_id.as_str())
+                .execute(&mut tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+            sqlx::query("DELETE FROM workflow_pending WHERE workflow_type = ? AND workflow_id = ?")
+                .bind(workflow_type.as_str())
+                .bind(workflow_id.as_str())
+                .execute(&mut tx)
+                .await
+                .map_err(ErrorCode::from_std_error)?;
+            tx.commit().await.map_err(ErrorCode::from_std_error)
+        })
+    }
+
+    fn get_workflow_status(&self, workflow_id: &InlineStr) -> TegResult<Option<WorkflowStatus>> {
+        let row: Option<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT status FROM workflow WHERE workflow_id = ?")
+                    .bind(workflow_id.as_str())
+                    .fetch_optional(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        if let Some((status,)) = row {
+            Ok(Some(WorkflowStatus::from_str(&status).map_err(|_| {
+                ErrorCode::IllegalArgument(format!("invalid workflow status: {}", status))
+            })?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_workflow_include_tasks(
+        &self,
+        workflow_id: &InlineStr,
+        include_tasks: bool,
+    ) -> TegResult<Option<WorkflowModel>> {
+        let row: Option<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT json_data FROM workflow WHERE workflow_id = ?")
+                    .bind(workflow_id.as_str())
+                    .fetch_optional(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        if let Some((json_data,)) = row {
+            let json: serde_json::Value = serde_json::from_str(&json_data)?;
+            let mut workflow = WorkflowModel::try_from(&json)?;
+            if include_tasks {
+                let mut tasks = self.get_tasks_for_workflow(workflow_id)?;
+                tasks.sort_by(|a, b| a.seq.cmp(&b.seq));
+                workflow.tasks = tasks.into_iter().collect::<LinkedList<_>>();
+            }
+            Ok(Some(workflow))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ConcurrentExecutionLimitDao for SqliteExecutionDao {
+    fn exceeds_limit(&self, task: &TaskModel) -> bool {
+        let limit = match task.get_task_definition() {
+            Some(task_definition) => task_definition.concurrency_limit(),
+            None => return false,
+        };
+        if limit <= 0 {
+            return false;
+        }
+
+        let result = self.data_source.block_on(async {
+            let (current,): (i64,) = sqlx::query_as(
+                "SELECT COUNT(*) FROM task_in_progress \
+                 WHERE task_def_name = ? AND in_progress_status = 1",
+            )
+            .bind(task.task_def_name.as_str())
+            .fetch_one(self.data_source.pool())
+            .await
+            .map_err(ErrorCode::from_std_error)?;
+            if current >= limit as i64 {
+                return Ok::<_, ErrorCode>((current, true));
+            }
+
+            // the first `limit` tasks not in a terminal status may execute, in order of creation
+            let task_ids: Vec<(String,)> = sqlx::query_as(
+                "SELECT task_id FROM task_in_progress WHERE task_def_name = ? \
+                 ORDER BY created_on, rowid LIMIT ?",
+            )
+            .bind(task.task_def_name.as_str())
+            .bind(limit)
+            .fetch_all(self.data_source.pool())
+            .await
+            .map_err(ErrorCode::from_std_error)?;
+            Ok((
+                current,
+                !task_ids
+                    .iter()
+                    .any(|x| x.0.as_str() == task.task_id.as_str()),
+            ))
+        });
+
+        match result {
+            Ok((current, rate_limited)) => {
+                if rate_limited {
+                    debug!(
+                        "Task execution count limited. task - {}:{}, limit: {}, current: {}",
+                        task.task_id, task.task_def_name, limit, current
+                    );
+                    Monitors::record_task_concurrent_execution_limited(&task.task_def_name, limit);
+                }
+                rate_limited
+            }
+            Err(e) => {
+                // the task is limited rather than risk exceeding the limit, it is polled again
+                error!(
+                    "Failed to check the concurrency limit of task: {}, {}",
+                    task.task_id, e
+                );
+                true
+            }
+        }
+    }
+}
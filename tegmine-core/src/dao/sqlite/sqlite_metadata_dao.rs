@@ -0,0 +1,289 @@
+use tegmine_common::prelude::*;
+use tegmine_common::{TaskDef, WorkflowDef};
+
+use super::SqliteDataSource;
+use crate::dao::MetadataDao;
+
+/// Sqlite implementation of the data access layer for the workflow metadata - task definitions
+/// and workflow definitions
+pub struct SqliteMetadataDao {
+    data_source: Arc<SqliteDataSource>,
+}
+
+impl SqliteMetadataDao {
+    pub fn new(data_source: Arc<SqliteDataSource>) -> Self {
+        Self { data_source }
+    }
+
+    fn insert_or_update_task_def(&self, task_def: TaskDef) -> TegResult<()> {
+        let json_data = task_def.to_json().to_string();
+        self.data_source
+            .block_on(
+                sqlx::query(
+                    "INSERT INTO meta_task_def (name, json_data) VALUES (?, ?) \
+                     ON CONFLICT (name) DO UPDATE SET json_data = excluded.json_data",
+                )
+                .bind(task_def.name.as_str())
+                .bind(json_data)
+                .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(())
+    }
+
+    fn insert_or_update_workflow_def(&self, workflow_def: WorkflowDef) -> TegResult<()> {
+        let json_data = workflow_def.to_json().to_string();
+        self.data_source
+            .block_on(
+                sqlx::query(
+                    "INSERT INTO meta_workflow_def (name, version, json_data) VALUES (?, ?, ?) \
+                     ON CONFLICT (name, version) DO UPDATE SET json_data = excluded.json_data",
+                )
+                .bind(workflow_def.name.as_str())
+                .bind(workflow_def.version)
+                .bind(json_data)
+                .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(())
+    }
+
+    fn get_workflow_max_version(&self, workflow_name: &InlineStr) -> TegResult<Option<i32>> {
+        let (max_version,): (Option<i32>,) = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT MAX(version) FROM meta_workflow_def WHERE name = ?")
+                    .bind(workflow_name.as_str())
+                    .fetch_one(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(max_version)
+    }
+}
+
+impl MetadataDao for SqliteMetadataDao {
+    /// ******************************************
+    /// *************** TaskDef **************
+    /// ******************************************
+    fn create_task_def(&self, task_def: TaskDef) -> TegResult<()> {
+        self.insert_or_update_task_def(task_def)
+    }
+
+    fn update_task_def(&self, task_def: TaskDef) -> TegResult<()> {
+        self.insert_or_update_task_def(task_def)
+    }
+
+    fn get_task_def(&self, name: &InlineStr) -> TegResult<Option<TaskDef>> {
+        let row: Option<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT json_data FROM meta_task_def WHERE name = ?")
+                    .bind(name.as_str())
+                    .fetch_optional(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        if let Some((json_data,)) = row {
+            let json: serde_json::Value = serde_json::from_str(&json_data)?;
+            Ok(Some(TaskDef::try_from(&json)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_all_task_defs(&self) -> TegResult<Vec<TaskDef>> {
+        let rows: Vec<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT json_data FROM meta_task_def ORDER BY name")
+                    .fetch_all(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        let mut task_defs = Vec::with_capacity(rows.len());
+        for (json_data,) in rows {
+            let json: serde_json::Value = serde_json::from_str(&json_data)?;
+            task_defs.push(TaskDef::try_from(&json)?);
+        }
+        Ok(task_defs)
+    }
+
+    fn remove_task_def(&self, name: &InlineStr) -> TegResult<()> {
+        let result = self
+            .data_source
+            .block_on(
+                sqlx::query("DELETE FROM meta_task_def WHERE name = ?")
+                    .bind(name.as_str())
+                    .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        if result.rows_affected() == 0 {
+            fmt_err!(
+                NotFound,
+                "Cannot remove the task: {} - no such task definition",
+                name
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    /// ******************************************
+    /// *************** WorkflowDef **************
+    /// ******************************************
+
+    fn create_workflow_def(&self, workflow_def: WorkflowDef) -> TegResult<()> {
+        if self
+            .get_workflow_def(&workflow_def.name, workflow_def.version)?
+            .is_some()
+        {
+            fmt_err!(
+                Conflict,
+                "Workflow with {}/{} already exists!",
+                workflow_def.name,
+                workflow_def.version
+            )
+        } else {
+            self.insert_or_update_workflow_def(workflow_def)
+        }
+    }
+
+    fn update_workflow_def(&self, workflow_def: WorkflowDef) -> TegResult<()> {
+        self.insert_or_update_workflow_def(workflow_def)
+    }
+
+    fn toggle_workflow_def(&self, name: &InlineStr, enable: bool) -> TegResult<()> {
+        if self.get_workflow_max_version(name)?.is_none() {
+            return fmt_err!(
+                NotFound,
+                "Cannot change the status: {} - no such workflow definition",
+                name
+            );
+        }
+
+        let sql = if enable {
+            "DELETE FROM meta_workflow_def_disabled WHERE name = ?"
+        } else {
+            "INSERT OR IGNORE INTO meta_workflow_def_disabled (name) VALUES (?)"
+        };
+        self.data_source
+            .block_on(
+                sqlx::query(sql)
+                    .bind(name.as_str())
+                    .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(())
+    }
+
+    fn check_workflow_def_endabled(&self, name: &str) -> TegResult<bool> {
+        let (defined, disabled): (i64, i64) = self
+            .data_source
+            .block_on(
+                sqlx::query_as(
+                    "SELECT \
+                     (SELECT COUNT(*) FROM meta_workflow_def WHERE name = ?1), \
+                     (SELECT COUNT(*) FROM meta_workflow_def_disabled WHERE name = ?1)",
+                )
+                .bind(name)
+                .fetch_one(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(defined > 0 && disabled == 0)
+    }
+
+    fn get_latest_workflow_def(&self, name: &InlineStr) -> TegResult<Option<WorkflowDef>> {
+        if let Some(max_version) = self.get_workflow_max_version(name)? {
+            self.get_workflow_def(name, max_version)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_all_versions(&self, name: &InlineStr) -> TegResult<Vec<WorkflowDef>> {
+        let rows: Vec<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as(
+                    "SELECT json_data FROM meta_workflow_def WHERE name = ? ORDER BY version",
+                )
+                .bind(name.as_str())
+                .fetch_all(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        let mut workflow_defs = Vec::with_capacity(rows.len());
+        for (json_data,) in rows {
+            let json: serde_json::Value = serde_json::from_str(&json_data)?;
+            workflow_defs.push(WorkflowDef::try_from(&json)?);
+        }
+        Ok(workflow_defs)
+    }
+
+    fn get_workflow_def(&self, name: &InlineStr, version: i32) -> TegResult<Option<WorkflowDef>> {
+        let row: Option<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as(
+                    "SELECT json_data FROM meta_workflow_def WHERE name = ? AND version = ?",
+                )
+                .bind(name.as_str())
+                .bind(version)
+                .fetch_optional(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        if let Some((json_data,)) = row {
+            let json: serde_json::Value = serde_json::from_str(&json_data)?;
+            Ok(Some(WorkflowDef::try_from(&json)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn remove_workflow_def(&self, name: &InlineStr, version: i32) -> TegResult<()> {
+        let result = self
+            .data_source
+            .block_on(
+                sqlx::query("DELETE FROM meta_workflow_def WHERE name = ? AND version = ?")
+                    .bind(name.as_str())
+                    .bind(version)
+                    .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+
+        if result.rows_affected() == 0 {
+            return fmt_err!(
+                NotFound,
+                "Cannot remove the workflow - no such workflow definition: {} version: {}",
+                name,
+                version
+            );
+        }
+
+        // check if there are any more versions remaining if not delete the
+        // workflow name
+        if self.get_workflow_max_version(name)?.is_none() {
+            self.data_source
+                .block_on(
+                    sqlx::query("DELETE FROM meta_workflow_def_disabled WHERE name = ?")
+                        .bind(name.as_str())
+                        .execute(self.data_source.pool()),
+                )
+                .map_err(ErrorCode::from_std_error)?;
+        }
+        Ok(())
+    }
+
+    fn get_all_workflow_defs(&self) -> TegResult<Vec<InlineStr>> {
+        let rows: Vec<(String,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT DISTINCT name FROM meta_workflow_def ORDER BY name")
+                    .fetch_all(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(rows.into_iter().map(|(name,)| name.into()).collect())
+    }
+}
@@ -0,0 +1,255 @@
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use dashmap::DashMap;
+use tegmine_common::prelude::*;
+
+use super::SqliteDataSource;
+use crate::dao::{PushNotifier, QueueDao, UNACK_TIMEOUT_MS};
+
+/// The longest time a poller waits without querying the database, messages pushed by other
+/// processes sharing the database are not notified.
+const MAX_WAIT_MS: i64 = 1000;
+
+/// Sqlite implementation of the DAO responsible for managing queuing for the tasks.
+///
+/// Pollers of an empty queue wait until a message is pushed through this DAO, or until the next
+/// message of the queue becomes visible.
+pub struct SqliteQueueDao {
+    data_source: Arc<SqliteDataSource>,
+    notifiers: DashMap<InlineStr, Arc<PushNotifier>>,
+}
+
+impl SqliteQueueDao {
+    pub fn new(data_source: Arc<SqliteDataSource>) -> Self {
+        Self {
+            data_source,
+            notifiers: DashMap::new(),
+        }
+    }
+
+    fn notifier(&self, queue_name: &str) -> Arc<PushNotifier> {
+        Arc::clone(self.notifiers.entry(queue_name.into()).or_default().value())
+    }
+
+    fn notify(&self, queue_name: &str) {
+        if let Some(notifier) = self.notifiers.get(queue_name) {
+            notifier.notify();
+        }
+    }
+
+    /// return the time the next message of the queue becomes visible, if any
+    fn next_deliver_on(&self, queue_name: &str) -> TegResult<Option<i64>> {
+        let (deliver_on,): (Option<i64>,) = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT MIN(deliver_on) FROM queue_message WHERE queue_name = ?")
+                    .bind(queue_name)
+                    .fetch_one(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(deliver_on)
+    }
+}
+
+impl QueueDao for SqliteQueueDao {
+    fn push(
+        &self,
+        queue_name: &str,
+        id: &InlineStr,
+        priority: i32,
+        offset_time_in_second: i64,
+    ) -> TegResult<()> {
+        let priority = if (0..=99).contains(&priority) {
+            priority
+        } else {
+            0
+        };
+        let deliver_on = Utc::now().timestamp_millis() + offset_time_in_second * 1000;
+
+        self.data_source
+            .block_on(
+                sqlx::query(
+                    "INSERT INTO queue_message (queue_name, message_id, priority, deliver_on) \
+                     VALUES (?, ?, ?, ?) ON CONFLICT (queue_name, message_id) DO UPDATE SET \
+                     priority = excluded.priority, deliver_on = excluded.deliver_on, popped = 0",
+                )
+                .bind(queue_name)
+                .bind(id.as_str())
+                .bind(priority)
+                .bind(deliver_on)
+                .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        self.notify(queue_name);
+        Ok(())
+    }
+
+    fn pop(&self, queue_name: &str, count: i32, timeout_ms: i32) -> TegResult<Vec<InlineStr>> {
+        let mut message_ids = Vec::with_capacity(count.max(0) as usize);
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms.max(0) as u64);
+
+        let notifier = self.notifier(queue_name);
+        loop {
+            let wait_count = count - message_ids.len() as i32;
+            if wait_count <= 0 {
+                break;
+            }
+
+            // the popped messages are invisible until they are ack'ed or the unack timeout
+            // elapses. Their new deliver_on is offset by their rank, so that the returned rows can
+            // be ordered and are redelivered in the same order
+            let version = notifier.version();
+            let now = Utc::now().timestamp_millis();
+            let mut popped: Vec<(String, i64)> = self
+                .data_source
+                .block_on(
+                    sqlx::query_as(
+                        "UPDATE queue_message SET popped = 1, deliver_on = ?4 + visible.row_rank \
+                         FROM (SELECT message_id AS id, ROW_NUMBER() OVER (ORDER BY deliver_on, \
+                         priority) AS row_rank FROM queue_message WHERE queue_name = ?1 AND \
+                         deliver_on <= ?2 ORDER BY deliver_on, priority LIMIT ?3) AS visible \
+                         WHERE queue_name = ?1 AND message_id = visible.id \
+                         RETURNING message_id, deliver_on",
+                    )
+                    .bind(queue_name)
+                    .bind(now)
+                    .bind(wait_count)
+                    .bind(now + UNACK_TIMEOUT_MS)
+                    .fetch_all(self.data_source.pool()),
+                )
+                .map_err(ErrorCode::from_std_error)?;
+
+            if popped.is_empty() {
+                let now_instant = Instant::now();
+                if now_instant >= deadline {
+                    break;
+                }
+                // wait for a push, or until the next message becomes visible
+                let visible_in = match self.next_deliver_on(queue_name)? {
+                    Some(deliver_on) => (deliver_on - now).clamp(0, MAX_WAIT_MS),
+                    None => MAX_WAIT_MS,
+                };
+                notifier.wait(
+                    version,
+                    (deadline - now_instant).min(Duration::from_millis(visible_in as u64)),
+                );
+            } else {
+                popped.sort_by_key(|x| x.1);
+                message_ids.extend(popped.into_iter().map(|x| InlineStr::from(x.0)));
+            }
+        }
+
+        Ok(message_ids)
+    }
+
+    fn remove(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<()> {
+        self.data_source
+            .block_on(
+                sqlx::query("DELETE FROM queue_message WHERE queue_name = ? AND message_id = ?")
+                    .bind(queue_name)
+                    .bind(message_id.as_str())
+                    .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(())
+    }
+
+    fn ack(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<bool> {
+        let result = self
+            .data_source
+            .block_on(
+                sqlx::query(
+                    "DELETE FROM queue_message \
+                     WHERE queue_name = ? AND message_id = ? AND popped = 1",
+                )
+                .bind(queue_name)
+                .bind(message_id.as_str())
+                .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Postpone a given message with postponeDurationInSeconds, so that the message won't be
+    /// available for further polls until specified duration.
+    fn postpone(
+        &self,
+        queue_name: &str,
+        message_id: &InlineStr,
+        priority: i32,
+        postpone_duration_in_seconds: i64,
+    ) -> TegResult<bool> {
+        self.push(
+            queue_name,
+            message_id,
+            priority,
+            postpone_duration_in_seconds,
+        )?;
+        Ok(true)
+    }
+
+    fn set_unack_timeout(
+        &self,
+        queue_name: &str,
+        message_id: &InlineStr,
+        unack_timeout_ms: i64,
+    ) -> TegResult<bool> {
+        let result = self
+            .data_source
+            .block_on(
+                sqlx::query(
+                    "UPDATE queue_message SET deliver_on = ? \
+                     WHERE queue_name = ? AND message_id = ?",
+                )
+                .bind(Utc::now().timestamp_millis() + unack_timeout_ms)
+                .bind(queue_name)
+                .bind(message_id.as_str())
+                .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        if result.rows_affected() > 0 {
+            self.notify(queue_name);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn exists(&self, queue_name: &str, message_id: &InlineStr) -> TegResult<bool> {
+        let found: Option<(i32,)> = self
+            .data_source
+            .block_on(
+                sqlx::query_as(
+                    "SELECT 1 FROM queue_message WHERE queue_name = ? AND message_id = ?",
+                )
+                .bind(queue_name)
+                .bind(message_id.as_str())
+                .fetch_optional(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(found.is_some())
+    }
+
+    fn size(&self, queue_name: &str) -> TegResult<i64> {
+        let (size,): (i64,) = self
+            .data_source
+            .block_on(
+                sqlx::query_as("SELECT COUNT(*) FROM queue_message WHERE queue_name = ?")
+                    .bind(queue_name)
+                    .fetch_one(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(size)
+    }
+
+    fn flush(&self, queue_name: &str) -> TegResult<()> {
+        self.data_source
+            .block_on(
+                sqlx::query("DELETE FROM queue_message WHERE queue_name = ?")
+                    .bind(queue_name)
+                    .execute(self.data_source.pool()),
+            )
+            .map_err(ErrorCode::from_std_error)?;
+        Ok(())
+    }
+}
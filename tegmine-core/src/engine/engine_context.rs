@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+use tegmine_common::prelude::*;
+
+use crate::config::Properties;
+use crate::dao::Daos;
+use crate::runtime::{
+    Channel, EvaluatorRegistry, Lock, SystemTaskRegistry, SystemTaskWorker,
+    SystemTaskWorkerCoordinator, TaskMapperRegistry,
+};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<EngineContext>>> = RefCell::new(None);
+}
+
+/// Everything an engine owns: configuration, stores, registries, channels and worker threads.
+///
+/// The runtime reaches the context of the engine it is running for through
+/// `EngineContext::current`, the engine enters its context on every thread it drives. The
+/// registries and the other per-engine components are plain fields of the context, so their
+/// associated functions (`SystemTaskRegistry::get`, `Channel::send_evaluation_event`, ...) operate
+/// on the instance of the current engine.
+pub(crate) struct EngineContext {
+    pub properties: Arc<Properties>,
+    pub daos: Arc<Daos>,
+    pub lock: Arc<dyn Lock>,
+    pub system_task_registry: SystemTaskRegistry,
+    pub task_mapper_registry: TaskMapperRegistry,
+    pub evaluator_registry: EvaluatorRegistry,
+    pub channel: Channel,
+    pub system_task_worker: SystemTaskWorker,
+    pub system_task_worker_coordinator: SystemTaskWorkerCoordinator,
+    shutdown: AtomicBool,
+    workers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl EngineContext {
+    pub fn new(properties: Properties, daos: Daos, lock: Arc<dyn Lock>) -> Self {
+        Self {
+            properties: Arc::new(properties),
+            daos: Arc::new(daos),
+            lock,
+            system_task_registry: SystemTaskRegistry::default(),
+            task_mapper_registry: TaskMapperRegistry::default(),
+            evaluator_registry: EvaluatorRegistry::default(),
+            channel: Channel::default(),
+            system_task_worker: SystemTaskWorker::default(),
+            system_task_worker_coordinator: SystemTaskWorkerCoordinator::default(),
+            shutdown: AtomicBool::new(false),
+            workers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the context of the engine entered on the current thread, or an error if no engine
+    /// is entered, see `TegmineEngine::enter`.
+    pub fn current() -> TegResult<Arc<EngineContext>> {
+        CURRENT.with(|x| x.borrow().clone()).ok_or_else(|| {
+            ErrorCode::IllegalState(
+                "No TegmineEngine entered on the current thread, see TegmineEngine::enter",
+            )
+        })
+    }
+
+    /// Makes this context the current one until the returned guard is dropped.
+    pub fn enter(self: &Arc<Self>) -> EngineGuard {
+        let previous = CURRENT.with(|x| x.borrow_mut().replace(Arc::clone(self)));
+        EngineGuard {
+            previous,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Spawns a thread which runs `f` with this context entered until the engine is shut down,
+    /// the errors of `f` are logged. `f` must return in a timely manner, so that the shutdown is
+    /// observed.
+    pub fn spawn_worker<F>(self: &Arc<Self>, name: &str, mut f: F) -> TegResult<()>
+    where
+        F: FnMut() -> TegResult<()> + Send + 'static,
+    {
+        let context = Arc::clone(self);
+        let name = name.to_string();
+        let handle = std::thread::Builder::new()
+            .name(name.clone())
+            .spawn(move || {
+                let _guard = context.enter();
+                while !context.is_shutdown() {
+                    if let Err(e) = f() {
+                        error!("worker thread {} failed, {}", name, e);
+                    }
+                }
+            })?;
+        self.workers.lock().expect("lock poisoned").push(handle);
+        Ok(())
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.shutdown.load(Ordering::SeqCst)
+    }
+
+    /// Stops and joins all the worker threads.
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        let workers = std::mem::take(&mut *self.workers.lock().expect("lock poisoned"));
+        for worker in workers {
+            if worker.join().is_err() {
+                error!("worker thread of the engine panicked");
+            }
+        }
+    }
+}
+
+/// Restores the previously entered engine when dropped.
+pub struct EngineGuard {
+    previous: Option<Arc<EngineContext>>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for EngineGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|x| *x.borrow_mut() = previous);
+    }
+}
@@ -0,0 +1,6 @@
+mod engine_context;
+mod tegmine_engine;
+
+pub(crate) use engine_context::EngineContext;
+pub use engine_context::EngineGuard;
+pub use tegmine_engine::TegmineEngine;
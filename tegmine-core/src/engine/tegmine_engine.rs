@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use tegmine_common::prelude::*;
+use tegmine_common::StartWorkflowRequest;
+
+use super::{EngineContext, EngineGuard};
+use crate::config::Properties;
+use crate::dao::Daos;
+use crate::model::Workflow;
+use crate::runtime::{
+    Channel, LocalOnlyLock, Lock, SystemTaskRegistry, SystemTaskWorkerCoordinator, TaskMapper,
+    TaskMapperRegistry, WorkflowSweeper, WorkflowSystemTask,
+};
+use crate::{ExecutionService, WorkflowService, WorkflowStatus};
+
+/// A workflow engine instance. It owns its configuration, stores, registries and worker threads,
+/// so several engines can live side by side in one process.
+///
+/// The services (`WorkflowService`, `ExecutionService`, ...) operate on the engine entered on the
+/// current thread, either through `TegmineEngine::enter` or implicitly by the methods of this
+/// type. Dropping the engine stops its worker threads.
+pub struct TegmineEngine {
+    context: Arc<EngineContext>,
+}
+
+impl Default for TegmineEngine {
+    fn default() -> Self {
+        Self::new(Properties::default(), Daos::default())
+    }
+}
+
+impl TegmineEngine {
+    pub fn new(properties: Properties, daos: Daos) -> Self {
+        Self::with_lock(properties, daos, Arc::new(LocalOnlyLock::new()))
+    }
+
+    /// Creates an engine deciding the workflows under the given lock, when
+    /// `Properties::workflow_execution_lock_enabled` is set. Engines deciding the same workflows
+    /// must share the lock, e.g. a `FileLock` on the same directory.
+    pub fn with_lock(properties: Properties, daos: Daos, lock: Arc<dyn Lock>) -> Self {
+        Self {
+            context: Arc::new(EngineContext::new(properties, daos, lock)),
+        }
+    }
+
+    /// Enters the engine on the current thread, until the returned guard is dropped.
+    pub fn enter(&self) -> EngineGuard {
+        self.context.enter()
+    }
+
+    pub fn properties(&self) -> &Properties {
+        &self.context.properties
+    }
+
+    pub fn daos(&self) -> &Daos {
+        &self.context.daos
+    }
+
+    /// ***** Registries *****
+
+    pub fn register_system_task(
+        &self,
+        task_type: &str,
+        task: Box<dyn WorkflowSystemTask>,
+    ) -> TegResult<()> {
+        let _guard = self.enter();
+        SystemTaskRegistry::register(task_type, task)
+    }
+
+    pub fn unregister_system_task(&self, task_type: &str) -> TegResult<()> {
+        let _guard = self.enter();
+        SystemTaskRegistry::unregister(task_type)
+    }
+
+    pub fn register_task_mapper(
+        &self,
+        task_type: &str,
+        task_mapper: Box<dyn TaskMapper>,
+    ) -> TegResult<()> {
+        let _guard = self.enter();
+        TaskMapperRegistry::register(task_type, task_mapper)
+    }
+
+    pub fn unregister_task_mapper(&self, task_type: &str) -> TegResult<()> {
+        let _guard = self.enter();
+        TaskMapperRegistry::unregister(task_type)
+    }
+
+    pub fn register_async_system_task(
+        &self,
+        system_task: Box<dyn WorkflowSystemTask>,
+    ) -> TegResult<()> {
+        let _guard = self.enter();
+        SystemTaskWorkerCoordinator::register_async_system_task(system_task)
+    }
+
+    /// ***** Worker threads *****
+
+    /// Spawns the threads handling the workflow creation and evaluation events.
+    pub fn spawn_event_loop(&self) -> TegResult<()> {
+        self.context.spawn_worker(
+            "tegmine-evaluation",
+            Channel::handle_evaluation_event_paralle,
+        )?;
+        self.context
+            .spawn_worker("tegmine-creation", Channel::handle_creation_event)
+    }
+
+    /// Spawns the thread deciding the running workflows again when they are due in the decider
+    /// queue, it completes the WAIT tasks whose duration has elapsed.
+    pub fn spawn_sweeper(&self) -> TegResult<()> {
+        self.context
+            .spawn_worker("tegmine-sweeper", WorkflowSweeper::poll_and_sweep)
+    }
+
+    /// Starts polling the queues of the registered async system tasks.
+    pub fn init_system_task_executor(&self) -> TegResult<()> {
+        let _guard = self.enter();
+        SystemTaskWorkerCoordinator::init_system_task_executor()
+    }
+
+    /// Stops and joins the worker threads of the engine, it is called when the engine is dropped.
+    pub fn shutdown(&self) {
+        self.context.shutdown()
+    }
+
+    /// ***** Workflow execution *****
+
+    pub fn start_workflow(
+        &self,
+        start_workflow_request: StartWorkflowRequest,
+    ) -> TegResult<InlineStr> {
+        let _guard = self.enter();
+        WorkflowService::start_workflow(start_workflow_request)
+    }
+
+    pub fn get_execution_status(
+        &self,
+        workflow_id: &str,
+        include_tasks: bool,
+    ) -> TegResult<(WorkflowStatus, Option<Workflow>)> {
+        let _guard = self.enter();
+        ExecutionService::get_execution_status(workflow_id, include_tasks)
+    }
+
+    /// Handles one pending evaluation event on the calling thread.
+    pub fn evaluate_once(&self) -> TegResult<()> {
+        let _guard = self.enter();
+        Channel::evaluate_once()
+    }
+
+    pub fn block_execute_workflow(
+        &self,
+        start_workflow_request: StartWorkflowRequest,
+        timeout: Duration,
+    ) -> TegResult<HashMap<InlineStr, Object>> {
+        let _guard = self.enter();
+        Channel::block_execute(start_workflow_request, timeout)
+    }
+
+    pub async fn async_execute_workflow(
+        &self,
+        start_workflow_request: StartWorkflowRequest,
+    ) -> TegResult<HashMap<InlineStr, Object>> {
+        // the guard must not be held across the await point, the future may be resumed on
+        // another thread
+        let (workflow_instance_id, receiver) = {
+            let _guard = self.enter();
+            Channel::async_start(start_workflow_request)?
+        };
+        Channel::async_wait(&workflow_instance_id, receiver).await?;
+
+        let _guard = self.enter();
+        Channel::fetch_execute_output(&workflow_instance_id)
+    }
+}
+
+impl Drop for TegmineEngine {
+    fn drop(&mut self) {
+        self.shutdown()
+    }
+}
@@ -69,4 +69,6 @@ impl Monitors {
     pub fn record_queue_wait_time(task_type: &str, queue_wait_time: i64) {}
 
     pub fn record_system_task_worker_polling_limited(queue_name: &str) {}
+
+    pub fn record_task_concurrent_execution_limited(task_def_name: &str, limit: i32) {}
 }
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use chrono::Utc;
 use numtoa::NumToA;
 use strum_macros::{AsRefStr, EnumString};
@@ -145,6 +147,160 @@ impl TaskModel {
     }
 }
 
+/// json <-> TaskModel
+impl TaskModel {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "taskType": self.task_type.as_str(),
+            "status": self.status.as_ref(),
+            "referenceTaskName": self.reference_task_name.as_str(),
+            "retryCount": self.retry_count,
+            "seq": self.seq,
+            "correlationId": self.correlation_id.as_str(),
+            "pollCount": self.poll_count,
+            "taskDefName": self.task_def_name.as_str(),
+            "scheduledTime": self.scheduled_time,
+            "startTime": self.start_time,
+            "endTime": self.end_time,
+            "updateTime": self.update_time,
+            "startDelayInSeconds": self.start_delay_in_seconds,
+            "retriedTaskId": self.retried_task_id.as_str(),
+            "retried": self.retried,
+            "executed": self.executed,
+            "callbackFromWorker": self.callback_from_worker,
+            "responseTimeoutSeconds": self.response_timeout_seconds,
+            "workflowInstanceId": self.workflow_instance_id.as_str(),
+            "workflowType": self.workflow_type.as_str(),
+            "taskId": self.task_id.as_str(),
+            "reasonForIncompletion": self.reason_for_incompletion.as_str(),
+            "callbackAfterSeconds": self.callback_after_seconds,
+            "workerId": self.worker_id.as_str(),
+            "domain": self.domain.as_str(),
+            "inputMessage": self.input_message.to_json(),
+            "outputMessage": self.output_message.to_json(),
+            "rateLimitPerFrequency": self.rate_limit_per_frequency,
+            "rateLimitFrequencyInSeconds": self.rate_limit_frequency_in_seconds,
+            "externalInputPayloadStoragePath": self.external_input_payload_storage_path.as_str(),
+            "externalOutputPayloadStoragePath": self.external_output_payload_storage_path.as_str(),
+            "workflowPriority": self.workflow_priority,
+            "executionNameSpace": self.execution_name_space.as_str(),
+            "isolationGroupId": self.isolation_group_id.as_str(),
+            "iteration": self.iteration,
+            "subWorkflowId": self.sub_workflow_id.as_str(),
+            "waitTimeout": self.wait_timeout,
+            "subWorkflowChanged": self.sub_workflow_changed,
+            "inputPayload": Object::convert_hashmap_to_json(&self.input_payload),
+            "outputPayload": Object::convert_hashmap_to_json(&self.output_payload),
+            "inputData": Object::convert_hashmap_to_json(&self.input_data),
+            "outputData": Object::convert_hashmap_to_json(&self.output_data),
+        });
+        if let Some(workflow_task) = self.workflow_task.as_ref() {
+            json["workflowTask"] = workflow_task.to_json();
+        }
+        json
+    }
+
+    fn str_from_json(value: &serde_json::Value, key: &str) -> InlineStr {
+        value.get(key).and_then(|x| x.as_str()).unwrap_or("").into()
+    }
+
+    fn i64_from_json(value: &serde_json::Value, key: &str) -> i64 {
+        value.get(key).and_then(|x| x.as_i64()).unwrap_or(0)
+    }
+
+    fn bool_from_json(value: &serde_json::Value, key: &str) -> bool {
+        value.get(key).and_then(|x| x.as_bool()).unwrap_or(false)
+    }
+
+    fn map_from_json(value: &serde_json::Value, key: &str) -> HashMap<InlineStr, Object> {
+        value
+            .get(key)
+            .and_then(|x| x.as_object())
+            .map(|x| Object::convert_jsonmap_to_hashmap(x))
+            .unwrap_or_default()
+    }
+}
+
+impl TryFrom<&serde_json::Value> for TaskModel {
+    type Error = ErrorCode;
+    fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
+        let status = TaskStatus::from_str(
+            value
+                .get("status")
+                .and_then(|x| x.as_str())
+                .ok_or_else(|| ErrorCode::IllegalArgument("TaskModel: status not found"))?,
+        )
+        .map_err(|_| ErrorCode::IllegalArgument("TaskModel: status invalid"))?;
+
+        let workflow_task = if let Some(workflow_task) = value.get("workflowTask") {
+            Some(WorkflowTask::try_from(workflow_task)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            task_type: Self::str_from_json(value, "taskType"),
+            status,
+            reference_task_name: Self::str_from_json(value, "referenceTaskName"),
+            retry_count: Self::i64_from_json(value, "retryCount") as i32,
+            seq: Self::i64_from_json(value, "seq") as i32,
+            correlation_id: Self::str_from_json(value, "correlationId"),
+            poll_count: Self::i64_from_json(value, "pollCount") as i32,
+            task_def_name: Self::str_from_json(value, "taskDefName"),
+            scheduled_time: Self::i64_from_json(value, "scheduledTime"),
+            start_time: Self::i64_from_json(value, "startTime"),
+            end_time: Self::i64_from_json(value, "endTime"),
+            update_time: Self::i64_from_json(value, "updateTime"),
+            start_delay_in_seconds: Self::i64_from_json(value, "startDelayInSeconds") as i32,
+            retried_task_id: Self::str_from_json(value, "retriedTaskId"),
+            retried: Self::bool_from_json(value, "retried"),
+            executed: Self::bool_from_json(value, "executed"),
+            callback_from_worker: Self::bool_from_json(value, "callbackFromWorker"),
+            response_timeout_seconds: Self::i64_from_json(value, "responseTimeoutSeconds"),
+            workflow_instance_id: Self::str_from_json(value, "workflowInstanceId"),
+            workflow_type: Self::str_from_json(value, "workflowType"),
+            task_id: Self::str_from_json(value, "taskId"),
+            reason_for_incompletion: Self::str_from_json(value, "reasonForIncompletion"),
+            callback_after_seconds: Self::i64_from_json(value, "callbackAfterSeconds"),
+            worker_id: Self::str_from_json(value, "workerId"),
+            workflow_task,
+            domain: Self::str_from_json(value, "domain"),
+            input_message: value
+                .get("inputMessage")
+                .map(|x| Object::from_json(x))
+                .unwrap_or(Object::Null),
+            output_message: value
+                .get("outputMessage")
+                .map(|x| Object::from_json(x))
+                .unwrap_or(Object::Null),
+            rate_limit_per_frequency: Self::i64_from_json(value, "rateLimitPerFrequency") as i32,
+            rate_limit_frequency_in_seconds: Self::i64_from_json(
+                value,
+                "rateLimitFrequencyInSeconds",
+            ) as i32,
+            external_input_payload_storage_path: Self::str_from_json(
+                value,
+                "externalInputPayloadStoragePath",
+            ),
+            external_output_payload_storage_path: Self::str_from_json(
+                value,
+                "externalOutputPayloadStoragePath",
+            ),
+            workflow_priority: Self::i64_from_json(value, "workflowPriority") as i32,
+            execution_name_space: Self::str_from_json(value, "executionNameSpace"),
+            isolation_group_id: Self::str_from_json(value, "isolationGroupId"),
+            iteration: Self::i64_from_json(value, "iteration") as i32,
+            sub_workflow_id: Self::str_from_json(value, "subWorkflowId"),
+            wait_timeout: Self::i64_from_json(value, "waitTimeout"),
+            sub_workflow_changed: Self::bool_from_json(value, "subWorkflowChanged"),
+            input_payload: Self::map_from_json(value, "inputPayload"),
+            output_payload: Self::map_from_json(value, "outputPayload"),
+            input_data: Self::map_from_json(value, "inputData"),
+            output_data: Self::map_from_json(value, "outputData"),
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, EnumString, AsRefStr, PartialEq, Eq)]
 pub enum TaskStatus {
     InProgress,
@@ -12,7 +12,7 @@ impl Workflow {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, AsRefStr)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AsRefStr, EnumString)]
 pub enum WorkflowStatus {
     Running,
     Completed,
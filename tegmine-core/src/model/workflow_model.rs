@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use chrono::Utc;
 use tegmine_common::prelude::*;
 use tegmine_common::WorkflowDef;
@@ -88,6 +90,12 @@ impl WorkflowModel {
         }
     }
 
+    /// Sets the status of the workflow, the current status becomes the previous status.
+    pub fn set_status(&mut self, status: WorkflowStatus) {
+        self.previous_status = Some(self.status);
+        self.status = status;
+    }
+
     pub fn has_parent(&self) -> bool {
         !self.parent_workflow_id.trim().is_empty()
     }
@@ -114,3 +122,154 @@ impl WorkflowModel {
         Ok(found.pop())
     }
 }
+
+/// json <-> WorkflowModel, tasks are not included and have to be loaded separately
+impl WorkflowModel {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut task_to_domain = serde_json::Map::with_capacity(self.task_to_domain.len());
+        for (k, v) in &self.task_to_domain {
+            task_to_domain.insert(k.to_string(), v.as_str().into());
+        }
+
+        let mut json = serde_json::json!({
+            "workflowId": self.workflow_id.as_str(),
+            "correlationId": self.correlation_id.as_str(),
+            "priority": self.priority,
+            "workflowDefinition": self.workflow_definition.to_json(),
+            "parentWorkflowId": self.parent_workflow_id.as_str(),
+            "parentWorkflowTaskId": self.parent_workflow_task_id.as_str(),
+            "taskToDomain": serde_json::Value::Object(task_to_domain),
+            "event": self.event.as_str(),
+            "variables": Object::convert_hashmap_to_json(&self.variables),
+            "input": Object::convert_hashmap_to_json(&self.input),
+            "output": Object::convert_hashmap_to_json(&self.output),
+            "inputPayload": Object::convert_hashmap_to_json(&self.input_payload),
+            "outputPayload": Object::convert_hashmap_to_json(&self.output_payload),
+            "externalInputPayloadStoragePath": self.external_input_payload_storage_path.as_str(),
+            "externalOutputPayloadStoragePath": self.external_output_payload_storage_path.as_str(),
+            "status": self.status.as_ref(),
+            "reasonForIncompletion": self.reason_for_incompletion.as_str(),
+            "failedTaskId": self.failed_task_id.as_str(),
+            "failedTaskNames": self.failed_task_names.iter().map(|x| x.as_str()).collect::<Vec<_>>(),
+            "failedReferenceTaskNames": self.failed_reference_task_names.iter().map(|x| x.as_str()).collect::<Vec<_>>(),
+            "reRunFromWorkflowId": self.re_run_from_workflow_id.as_str(),
+            "lastRetriedTime": self.last_retried_time,
+            "ownerApp": self.owner_app.as_str(),
+            "createTime": self.create_time,
+            "createdBy": self.created_by.as_str(),
+            "updatedTime": self.updated_time,
+            "updatedBy": self.updated_by.as_str(),
+            "endTime": self.end_time,
+        });
+        if let Some(previous_status) = self.previous_status {
+            json["previousStatus"] = previous_status.as_ref().into();
+        }
+        json
+    }
+
+    fn str_from_json(value: &serde_json::Value, key: &str) -> InlineStr {
+        value.get(key).and_then(|x| x.as_str()).unwrap_or("").into()
+    }
+
+    fn i64_from_json(value: &serde_json::Value, key: &str) -> i64 {
+        value.get(key).and_then(|x| x.as_i64()).unwrap_or(0)
+    }
+
+    fn map_from_json(value: &serde_json::Value, key: &str) -> HashMap<InlineStr, Object> {
+        value
+            .get(key)
+            .and_then(|x| x.as_object())
+            .map(|x| Object::convert_jsonmap_to_hashmap(x))
+            .unwrap_or_default()
+    }
+
+    fn set_from_json(value: &serde_json::Value, key: &str) -> HashSet<InlineStr> {
+        value
+            .get(key)
+            .and_then(|x| x.as_array())
+            .map(|x| {
+                x.iter()
+                    .filter_map(|x| x.as_str())
+                    .map(|x| x.into())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl TryFrom<&serde_json::Value> for WorkflowModel {
+    type Error = ErrorCode;
+    fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
+        let status = WorkflowStatus::from_str(
+            value
+                .get("status")
+                .and_then(|x| x.as_str())
+                .ok_or_else(|| ErrorCode::IllegalArgument("WorkflowModel: status not found"))?,
+        )
+        .map_err(|_| ErrorCode::IllegalArgument("WorkflowModel: status invalid"))?;
+        let previous_status =
+            if let Some(previous_status) = value.get("previousStatus").and_then(|x| x.as_str()) {
+                Some(WorkflowStatus::from_str(previous_status).map_err(|_| {
+                    ErrorCode::IllegalArgument("WorkflowModel: previousStatus invalid")
+                })?)
+            } else {
+                None
+            };
+
+        let workflow_definition =
+            WorkflowDef::try_from(value.get("workflowDefinition").ok_or_else(|| {
+                ErrorCode::IllegalArgument("WorkflowModel: workflowDefinition not found")
+            })?)?;
+
+        let mut task_to_domain = HashMap::default();
+        if let Some(json) = value.get("taskToDomain").and_then(|x| x.as_object()) {
+            for (k, v) in json {
+                if let Some(v) = v.as_str() {
+                    task_to_domain.insert(k.into(), v.into());
+                }
+            }
+        }
+
+        Ok(Self {
+            workflow_id: Self::str_from_json(value, "workflowId"),
+            correlation_id: Self::str_from_json(value, "correlationId"),
+            priority: Self::i64_from_json(value, "priority") as i32,
+            workflow_definition,
+            parent_workflow_id: Self::str_from_json(value, "parentWorkflowId"),
+            parent_workflow_task_id: Self::str_from_json(value, "parentWorkflowTaskId"),
+            tasks: LinkedList::default(),
+            task_to_domain,
+
+            event: Self::str_from_json(value, "event"),
+            variables: Self::map_from_json(value, "variables"),
+            input: Self::map_from_json(value, "input"),
+            output: Self::map_from_json(value, "output"),
+            input_payload: Self::map_from_json(value, "inputPayload"),
+            output_payload: Self::map_from_json(value, "outputPayload"),
+            external_input_payload_storage_path: Self::str_from_json(
+                value,
+                "externalInputPayloadStoragePath",
+            ),
+            external_output_payload_storage_path: Self::str_from_json(
+                value,
+                "externalOutputPayloadStoragePath",
+            ),
+
+            status,
+            previous_status,
+            reason_for_incompletion: Self::str_from_json(value, "reasonForIncompletion"),
+            failed_task_id: Self::str_from_json(value, "failedTaskId"),
+            failed_task_names: Self::set_from_json(value, "failedTaskNames"),
+            failed_reference_task_names: Self::set_from_json(value, "failedReferenceTaskNames"),
+            re_run_from_workflow_id: Self::str_from_json(value, "reRunFromWorkflowId"),
+            last_retried_time: Self::i64_from_json(value, "lastRetriedTime"),
+
+            owner_app: Self::str_from_json(value, "ownerApp"),
+            create_time: Self::i64_from_json(value, "createTime"),
+            created_by: Self::str_from_json(value, "createdBy"),
+            updated_time: Self::i64_from_json(value, "updatedTime"),
+            updated_by: Self::str_from_json(value, "updatedBy"),
+            end_time: Self::i64_from_json(value, "endTime"),
+        })
+    }
+}
@@ -1,18 +1,21 @@
+use std::fs::File;
+use std::path::PathBuf;
+
 use chrono::Utc;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use tegmine_common::prelude::*;
-use tegmine_common::{TaskDef, TaskExecLog};
+use tegmine_common::TaskExecLog;
 
 use crate::config::Properties;
-use crate::dao::{
-    ConcurrentExecutionLimitDao, ExecutionDao, IndexDao, PollDataDao, QueueDao, RateLimitingDao,
-};
+use crate::dao::{Daos, DECIDER_QUEUE};
 use crate::metrics::Monitors;
 use crate::model::{Task, TaskModel, TaskSummary, Workflow, WorkflowModel, WorkflowSummary};
 use crate::utils::QueueUtils;
 use crate::WorkflowStatus;
 
 /// Service that acts as a facade for accessing execution data from the `ExecutionDao`,
-/// `RateLimitingDao` and `IndexDao` storage layers
+/// `RateLimitingDao` and `IndexDao` storage layers of the current engine
 pub struct ExecutionDaoFacade;
 
 impl ExecutionDaoFacade {
@@ -31,8 +34,8 @@ impl ExecutionDaoFacade {
         Ok(workflow_model)
     }
 
-    pub fn get_workflow_status(workflow_id: &InlineStr) -> Option<WorkflowStatus> {
-        ExecutionDao::get_workflow_status(workflow_id)
+    pub fn get_workflow_status(workflow_id: &InlineStr) -> TegResult<Option<WorkflowStatus>> {
+        Daos::get()?.execution_dao.get_workflow_status(workflow_id)
     }
 
     /// Fetches the `Workflow` object from the data store given the id. Attempts to fetch from
@@ -50,7 +53,9 @@ impl ExecutionDaoFacade {
         workflow_id: &InlineStr,
         include_task: bool,
     ) -> TegResult<WorkflowModel> {
-        if let Some(workflow) = ExecutionDao::get_workflow_include_tasks(workflow_id, include_task)
+        if let Some(workflow) = Daos::get()?
+            .execution_dao
+            .get_workflow_include_tasks(workflow_id, include_task)?
         {
             Ok(workflow)
         } else {
@@ -73,36 +78,41 @@ impl ExecutionDaoFacade {
 
     /// Creates a new workflow in the data store
     /// return the id of the created workflow
-    pub fn create_workflow(workflow_model: &mut WorkflowModel) {
+    pub fn create_workflow(workflow_model: &mut WorkflowModel) -> TegResult<()> {
+        let daos = Daos::get()?;
         Self::externalize_workflow_data(workflow_model);
-        ExecutionDao::create_workflow(workflow_model);
+        daos.execution_dao.create_workflow(workflow_model)?;
 
         // Add to decider queue
-        QueueDao::push(
-            QueueDao::DECIDER_QUEUE,
+        daos.queue_dao.push(
+            DECIDER_QUEUE,
             &workflow_model.workflow_id,
             workflow_model.priority,
-            Properties::default().workflow_offset_timeout_sec,
-        );
-        if Properties::default().async_indexing_enabled {
-            IndexDao::async_index_workflow(WorkflowSummary::new(workflow_model));
+            Properties::get()?.workflow_offset_timeout_sec,
+        )?;
+        if Properties::get()?.async_indexing_enabled {
+            daos.index_dao
+                .async_index_workflow(WorkflowSummary::new(workflow_model))
         } else {
-            IndexDao::index_workflow(WorkflowSummary::new(workflow_model));
+            daos.index_dao
+                .index_workflow(WorkflowSummary::new(workflow_model))
         }
     }
 
     /// Updates the given workflow in the data store
-    pub fn update_workflow(workflow_model: &mut WorkflowModel) {
+    pub fn update_workflow(workflow_model: &mut WorkflowModel) -> TegResult<()> {
         workflow_model.updated_time = Utc::now().timestamp_millis();
         if workflow_model.status.is_terminal() {
             workflow_model.end_time = Utc::now().timestamp_millis();
         }
         Self::externalize_workflow_data(&workflow_model);
-        ExecutionDao::update_workflow(workflow_model);
-        if Properties::default().async_indexing_enabled {
+        Daos::get()?.execution_dao.update_workflow(workflow_model)?;
+        if Properties::get()?.async_indexing_enabled {
             unimplemented!()
         } else {
-            IndexDao::index_workflow(WorkflowSummary::new(workflow_model));
+            Daos::get()?
+                .index_dao
+                .index_workflow(WorkflowSummary::new(workflow_model))
         }
     }
 
@@ -113,45 +123,43 @@ impl ExecutionDaoFacade {
         // PayloadType.WORKFLOW_OUTPUT);
     }
 
-    pub fn remove_from_pending_workflow(workflow_type: &InlineStr, workflow_id: &InlineStr) {
-        ExecutionDao::remove_from_pending_workflow(workflow_type, workflow_id);
+    pub fn remove_from_pending_workflow(
+        workflow_type: &InlineStr,
+        workflow_id: &InlineStr,
+    ) -> TegResult<()> {
+        Daos::get()?
+            .execution_dao
+            .remove_from_pending_workflow(workflow_type, workflow_id)
     }
 
-    /// Removes the workflow from the data store.
+    /// Removes the workflow and its tasks from the data store and the queues, the terminal workflow
+    /// is archived first if `archive_workflow` is set.
     pub fn remove_workflow(workflow_id: &InlineStr, archive_workflow: bool) -> TegResult<()> {
         let workflow = Self::get_workflow_model_from_data_store(workflow_id, true)?;
 
-        ExecutionDao::remove_workflow(workflow_id);
-
-        // TODO:
-        if let Err(_e) = Self::remove_workflow_index(&workflow, archive_workflow) {
-            unimplemented!()
+        // archive before purging, the execution would be lost if the archival failed afterwards
+        if archive_workflow {
+            Self::archive_workflow(&workflow)?;
         }
-        // removeTaskIndex
 
-        if let ControlFlow::Break(e) = workflow.tasks.iter().try_for_each(|x| {
-            if let Err(_e) = Self::remove_task_index(&workflow, x, archive_workflow) {
-                unimplemented!()
-            }
+        Daos::get()?.execution_dao.remove_workflow(workflow_id)?;
 
-            if let Err(e) =
-                QueueDao::remove(&QueueUtils::get_queue_name_by_task_model(x), &x.task_id)
-            {
+        for task in &workflow.tasks {
+            if let Err(e) = Daos::get()?.queue_dao.remove(
+                &QueueUtils::get_queue_name_by_task_model(task),
+                &task.task_id,
+            ) {
                 info!(
                     "Error removing task: {} of workflow: {} from {} queue, error: {}",
+                    task.task_id,
                     workflow_id,
-                    x.task_id,
-                    QueueUtils::get_queue_name_by_task_model(x),
+                    QueueUtils::get_queue_name_by_task_model(task),
                     e
                 )
             }
-
-            ControlFlow::Continue(())
-        }) {
-            return Err(e);
         }
 
-        if let Err(e) = QueueDao::remove(QueueDao::DECIDER_QUEUE, workflow_id) {
+        if let Err(e) = Daos::get()?.queue_dao.remove(DECIDER_QUEUE, workflow_id) {
             info!(
                 "Error removing workflow: {} from decider queue, error: {}",
                 workflow_id, e
@@ -160,11 +168,49 @@ impl ExecutionDaoFacade {
         Ok(())
     }
 
-    fn remove_workflow_index(_workflow: &WorkflowModel, _archive_workflow: bool) -> TegResult<()> {
-        // TODO:
+    /// Writes the full execution record of the terminal workflow, including its tasks, to the
+    /// gzip compressed json file `<workflow id>.json.gz` of the archive directory.
+    fn archive_workflow(workflow: &WorkflowModel) -> TegResult<()> {
+        if !workflow.status.is_terminal() {
+            return fmt_err!(
+                IllegalArgument,
+                "Cannot archive workflow: {} with non-terminal status: {}",
+                workflow.workflow_id,
+                workflow.status.as_ref()
+            );
+        }
+
+        let mut record = workflow.to_json();
+        record["tasks"] = workflow.tasks.iter().map(|x| x.to_json()).collect();
+
+        let archive_path = Self::get_archive_path(&workflow.workflow_id)?;
+        if let Some(archive_dir) = archive_path.parent() {
+            std::fs::create_dir_all(archive_dir)?;
+        }
+        let mut encoder = GzEncoder::new(File::create(&archive_path)?, Compression::default());
+        serde_json::to_writer(&mut encoder, &record).map_err(ErrorCode::from_std_error)?;
+        encoder.finish()?;
+        debug!(
+            "Workflow: {} archived to {}",
+            workflow.workflow_id,
+            archive_path.display()
+        );
         Ok(())
     }
 
+    /// Returns the path of the archived execution record of the workflow, an error if no archive
+    /// directory is configured.
+    fn get_archive_path(workflow_id: &InlineStr) -> TegResult<PathBuf> {
+        match &Properties::get()?.workflow_archive_dir {
+            Some(archive_dir) => Ok(archive_dir.join(format!("{}.json.gz", workflow_id))),
+            None => fmt_err!(
+                IllegalState,
+                "No workflow archive directory configured, can not archive workflow: {}",
+                workflow_id
+            ),
+        }
+    }
+
     // removeWorkflowWithExpiry
 
     // resetWorkflow
@@ -175,34 +221,40 @@ impl ExecutionDaoFacade {
 
     // getTasksForWorkflow
 
-    pub fn get_task_model(task_id: &InlineStr) -> Option<TaskModel> {
-        let task_model = Self::get_task_from_datastore(task_id);
+    pub fn get_task_model(task_id: &InlineStr) -> TegResult<Option<TaskModel>> {
+        let task_model = Self::get_task_from_datastore(task_id)?;
         if let Some(task_model) = &task_model {
             Self::populate_task_data(task_model);
         }
-        task_model
+        Ok(task_model)
     }
 
     // getTask
-    pub fn get_task(task_id: &InlineStr) -> Option<Task> {
-        let task_model = Self::get_task_from_datastore(task_id);
-        task_model.map(|v| v.to_task())
+    pub fn get_task(task_id: &InlineStr) -> TegResult<Option<Task>> {
+        let task_model = Self::get_task_from_datastore(task_id)?;
+        Ok(task_model.map(|v| v.to_task()))
     }
 
     // getTaskFromDatastore
-    fn get_task_from_datastore(task_id: &InlineStr) -> Option<TaskModel> {
-        ExecutionDao::get_task(task_id)
+    fn get_task_from_datastore(task_id: &InlineStr) -> TegResult<Option<TaskModel>> {
+        Daos::get()?.execution_dao.get_task(task_id)
     }
 
     // getTasksByName
 
-    // getPendingTasksForTaskType
+    pub fn get_pending_tasks_for_task_type(task_type: &str) -> TegResult<Vec<TaskModel>> {
+        let task_models = Daos::get()?
+            .execution_dao
+            .get_pending_tasks_for_task_type(task_type)?;
+        task_models.iter().for_each(Self::populate_task_data);
+        Ok(task_models)
+    }
 
     // getInProgressTaskCount
 
     pub fn create_tasks(tasks: &mut [&mut TaskModel]) -> TegResult<()> {
         tasks.iter().for_each(|x| Self::externalize_task_data(x));
-        ExecutionDao::create_tasks(tasks)
+        Daos::get()?.execution_dao.create_tasks(tasks)
     }
 
     pub fn update_tasks(tasks: &[*mut TaskModel]) {
@@ -225,15 +277,17 @@ impl ExecutionDaoFacade {
         }
 
         Self::externalize_task_data(&task_model);
-        ExecutionDao::update_task(task_model)?;
+        Daos::get()?.execution_dao.update_task(task_model)?;
 
         // Indexing a task for every update adds a lot of volume. That is ok but if async indexing
         // is enabled and tasks are stored in memory until a block has completed, we would lose a
         // lot of tasks on a system failure. So only index for each update if async indexing is not
         // enabled. If it *is* enabled, tasks will be indexed only when a workflow is in
         // terminal state.
-        if !Properties::default().async_indexing_enabled {
-            IndexDao::index_task(TaskSummary::new(task_model));
+        if !Properties::get()?.async_indexing_enabled {
+            Daos::get()?
+                .index_dao
+                .index_task(TaskSummary::new(task_model))?;
         }
         Ok(())
     }
@@ -247,18 +301,14 @@ impl ExecutionDaoFacade {
 
     // removeTask
 
-    fn remove_task_index(
-        _workflow: &WorkflowModel,
-        _task: &TaskModel,
-        _archive_task: bool,
-    ) -> TegResult<()> {
-        // TODO
+    pub fn remove_task(task_id: &InlineStr) -> TegResult<()> {
+        Daos::get()?.execution_dao.remove_task(task_id)?;
         Ok(())
     }
 
     pub fn extend_lease(task_model: &mut TaskModel) -> TegResult<()> {
         task_model.update_time = Utc::now().timestamp_millis();
-        ExecutionDao::update_task(task_model)
+        Daos::get()?.execution_dao.update_task(task_model)
     }
 
     // getTaskPollData
@@ -279,7 +329,10 @@ impl ExecutionDaoFacade {
     /// ******************************************
 
     pub fn update_task_last_poll(task_name: &str, domain: &str, worker_id: &str) {
-        if let Err(e) = PollDataDao::update_last_poll_data(task_name, domain, worker_id) {
+        if let Err(e) = Daos::get().and_then(|daos| {
+            daos.poll_data_dao
+                .update_last_poll_data(task_name, domain, worker_id)
+        }) {
             error!(
                 "Error updating PollData for task: {} in domain: {} from worker: {}, error: {}",
                 task_name, domain, worker_id, e
@@ -289,12 +342,21 @@ impl ExecutionDaoFacade {
         }
     }
 
-    pub fn exceeds_in_progress_limit(task: &TaskModel) -> bool {
-        ConcurrentExecutionLimitDao::exceeds_limit(task)
+    pub fn exceeds_in_progress_limit(task: &TaskModel) -> TegResult<bool> {
+        Ok(Daos::get()?
+            .concurrent_execution_limit_dao
+            .exceeds_limit(task))
     }
 
-    pub fn exceeds_rate_limit_per_frequency(task: &TaskModel, task_def: Option<&TaskDef>) -> bool {
-        RateLimitingDao::exceeds_rate_limit_per_frequency(task, task_def)
+    /// The current definition of the task is checked, so that a changed rate limit applies to the
+    /// tasks already scheduled, the definition of the workflow task is checked if there is none.
+    pub fn exceeds_rate_limit_per_frequency(task: &TaskModel) -> TegResult<bool> {
+        let daos = Daos::get()?;
+        let task_def = daos.metadata_dao.get_task_def(&task.task_def_name)?;
+        Ok(daos.rate_limiting_dao.exceeds_rate_limit_per_frequency(
+            task,
+            task_def.as_ref().or_else(|| task.get_task_definition()),
+        ))
     }
 
     pub fn add_task_exec_log(_logs: Vec<TaskExecLog>) {
@@ -1,7 +1,7 @@
 use chrono::Utc;
 use tegmine_common::prelude::*;
 
-use crate::dao::{MetadataDao, QueueDao};
+use crate::dao::Daos;
 use crate::metrics::Monitors;
 use crate::runtime::{ExecutionDaoFacade, WorkflowExecutor};
 use crate::utils::QueueUtils;
@@ -41,7 +41,7 @@ impl AsyncSystemTaskExecutor {
                     task.reason_for_incompletion =
                         InlineStr::from(format!("Workflow is in {:?} state", workflow.status));
                 }
-                QueueDao::remove(&queue_name, &task.task_id)?;
+                Daos::get()?.queue_dao.remove(&queue_name, &task.task_id)?;
                 return Ok(());
             }
 
@@ -66,16 +66,16 @@ impl AsyncSystemTaskExecutor {
             // Update message in Task queue based on Task status
             // Remove asyncComplete system tasks from the queue that are not in SCHEDULED state
             if is_task_async_complete && task.status != TaskStatus::Scheduled {
-                QueueDao::remove(queue_name, &task.task_id)?;
+                Daos::get()?.queue_dao.remove(queue_name, &task.task_id)?;
                 *has_task_execution_completed = true;
             } else if task.status.is_terminal() {
                 task.end_time = Utc::now().timestamp_millis();
-                QueueDao::remove(queue_name, &task.task_id)?;
+                Daos::get()?.queue_dao.remove(queue_name, &task.task_id)?;
                 *has_task_execution_completed = true;
                 debug!("{:?} removed from queue: {}", task, queue_name);
             } else {
                 task.callback_after_seconds = SYSTEM_TASK_CALLBACK_TIME;
-                QueueDao::postpone(
+                Daos::get()?.queue_dao.postpone(
                     queue_name,
                     &task.task_id,
                     task.workflow_priority,
@@ -118,12 +118,12 @@ impl AsyncSystemTaskExecutor {
                 "Task {}/{} was already completed.",
                 task.task_type, task.task_id
             );
-            QueueDao::remove(&queue_name, &task.task_id)?;
+            Daos::get()?.queue_dao.remove(&queue_name, &task.task_id)?;
             return Ok(());
         }
 
         if task.status == TaskStatus::Scheduled {
-            if ExecutionDaoFacade::exceeds_in_progress_limit(&task) {
+            if ExecutionDaoFacade::exceeds_in_progress_limit(&task)? {
                 warn!(
                     "Concurrent Execution limited for {}:{}",
                     task_id, task.task_def_name
@@ -132,15 +132,10 @@ impl AsyncSystemTaskExecutor {
                 return Ok(());
             }
 
-            let task_def_guard = MetadataDao::get_task_def(&task.task_def_name);
-            let task_def = task_def_guard.as_ref().map(|x| x.value());
-
-            if task.rate_limit_per_frequency > 0
-                && ExecutionDaoFacade::exceeds_rate_limit_per_frequency(&task, task_def)
-            {
+            if ExecutionDaoFacade::exceeds_rate_limit_per_frequency(&task)? {
                 warn!(
-                    "RateLimit Execution limited for {}:{}, limit:{}",
-                    task_id, task.task_def_name, task.rate_limit_per_frequency
+                    "RateLimit Execution limited for {}:{}",
+                    task_id, task.task_def_name
                 );
                 Self::postpone_quietly(&queue_name, &task);
                 return Ok(());
@@ -179,13 +174,16 @@ impl AsyncSystemTaskExecutor {
     }
 
     fn postpone_quietly(queue_name: &str, task: &TaskModel) {
-        if QueueDao::postpone(
-            queue_name,
-            &task.task_id,
-            task.workflow_priority,
-            QUEUE_TASK_MESSAGE_POSTPONE_SECS,
-        )
-        .is_err()
+        if Daos::get()
+            .and_then(|daos| {
+                daos.queue_dao.postpone(
+                    queue_name,
+                    &task.task_id,
+                    task.workflow_priority,
+                    QUEUE_TASK_MESSAGE_POSTPONE_SECS,
+                )
+            })
+            .is_err()
         {
             error!(
                 "Error postponing task: {} in queue: {}",
@@ -195,6 +193,9 @@ impl AsyncSystemTaskExecutor {
     }
 
     fn load_task_quietly(task_id: &InlineStr) -> Option<TaskModel> {
-        ExecutionDaoFacade::get_task_model(task_id)
+        ExecutionDaoFacade::get_task_model(task_id).unwrap_or_else(|e| {
+            error!("Error loading task: {}, error: {}", task_id, e);
+            None
+        })
     }
 }
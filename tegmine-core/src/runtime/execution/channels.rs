@@ -3,54 +3,110 @@ use std::time::Duration;
 use crossbeam_channel::{Receiver, Sender};
 use dashmap::DashMap;
 use futures::executor::{ThreadPool, ThreadPoolBuilder};
+use once_cell::sync::OnceCell;
 use tegmine_common::prelude::*;
 use tegmine_common::StartWorkflowRequest;
 
 use super::WorkflowExecutor;
+use crate::engine::EngineContext;
 use crate::runtime::event::{WorkflowCreationEvent, WorkflowEvaluationEvent};
 use crate::runtime::StartWorkflowOperation;
 use crate::ExecutionService;
 use crate::WorkflowService;
 
-pub static CREATE_EVENT_CHANNEL: Lazy<(
-    Sender<WorkflowCreationEvent>,
-    Receiver<WorkflowCreationEvent>,
-)> = Lazy::new(|| crossbeam_channel::unbounded());
+const THREAD_POOL_SIZE: usize = 20;
+/// The time (in milliseconds) the event loop blocks on a channel before checking whether the
+/// engine is shut down.
+const RECV_TIMEOUT_MS: u64 = 100;
+
+/// The event channels of an engine and the thread pool evaluating the workflows.
+pub struct Channel {
+    create_event_channel: (
+        Sender<WorkflowCreationEvent>,
+        Receiver<WorkflowCreationEvent>,
+    ),
+    eval_event_channel: (
+        Sender<WorkflowEvaluationEvent>,
+        Receiver<WorkflowEvaluationEvent>,
+    ),
+    pool: OnceCell<ThreadPool>,
+    waiting_queue: DashMap<InlineStr, Sender<()>>,
+    async_waiting_queue: DashMap<InlineStr, tokio::sync::oneshot::Sender<()>>,
+}
 
-pub static EVAL_EVENT_CHANNEL: Lazy<(
-    Sender<WorkflowEvaluationEvent>,
-    Receiver<WorkflowEvaluationEvent>,
-)> = Lazy::new(|| crossbeam_channel::unbounded());
+impl Default for Channel {
+    fn default() -> Self {
+        Self {
+            create_event_channel: crossbeam_channel::unbounded(),
+            eval_event_channel: crossbeam_channel::unbounded(),
+            pool: OnceCell::new(),
+            waiting_queue: DashMap::new(),
+            async_waiting_queue: DashMap::new(),
+        }
+    }
+}
 
-const THREAD_POOL_SIZE: usize = 20;
-static POOL: Lazy<ThreadPool> = Lazy::new(|| {
-    ThreadPoolBuilder::new()
-        .pool_size(THREAD_POOL_SIZE)
-        .create()
-        .expect("thread pool create failed")
-});
+impl Channel {
+    /// The thread pool evaluating the workflows, it is only created once the event loop of the
+    /// engine runs, see `TegmineEngine::spawn_event_loop`.
+    fn pool(&self) -> TegResult<&ThreadPool> {
+        self.pool.get_or_try_init(|| {
+            Ok(ThreadPoolBuilder::new()
+                .pool_size(THREAD_POOL_SIZE)
+                .create()?)
+        })
+    }
 
-pub static WAITING_QUEUE: Lazy<DashMap<InlineStr, Sender<()>>> = Lazy::new(|| DashMap::new());
-pub static ASYNC_WAITING_QUEUE: Lazy<DashMap<InlineStr, tokio::sync::oneshot::Sender<()>>> =
-    Lazy::new(|| DashMap::new());
+    pub fn send_creation_event(wce: WorkflowCreationEvent) -> TegResult<()> {
+        EngineContext::current()?
+            .channel
+            .create_event_channel
+            .0
+            .send(wce)?;
+        Ok(())
+    }
 
-pub struct Channel;
+    pub fn send_evaluation_event(wee: WorkflowEvaluationEvent) -> TegResult<()> {
+        EngineContext::current()?
+            .channel
+            .eval_event_channel
+            .0
+            .send(wee)?;
+        Ok(())
+    }
 
-impl Channel {
-    pub fn handle_creation_event() {
-        if let Ok(wce) = CREATE_EVENT_CHANNEL.1.recv() {
+    pub fn handle_creation_event() -> TegResult<()> {
+        let context = EngineContext::current()?;
+        if let Ok(wce) = context
+            .channel
+            .create_event_channel
+            .1
+            .recv_timeout(Duration::from_millis(RECV_TIMEOUT_MS))
+        {
             let _ = StartWorkflowOperation::handle_workflow_creation_event(wce);
         }
+        Ok(())
     }
 
-    pub fn handle_evaluation_event_paralle() {
-        if let Ok(wee) = EVAL_EVENT_CHANNEL.1.recv() {
-            POOL.spawn_ok(async {
+    pub fn handle_evaluation_event_paralle() -> TegResult<()> {
+        let context = EngineContext::current()?;
+        let pool = context.channel.pool()?;
+        if let Ok(wee) = context
+            .channel
+            .eval_event_channel
+            .1
+            .recv_timeout(Duration::from_millis(RECV_TIMEOUT_MS))
+        {
+            let task_context = Arc::clone(&context);
+            pool.spawn_ok(async move {
+                let _guard = task_context.enter();
+                let channel = &task_context.channel;
+
                 let id = wee.workflow_model.workflow_id.clone();
                 let _ = WorkflowExecutor::handle_workflow_evaluation_event(wee);
 
                 // notify caller if using async_execute
-                if let Some((_, sender)) = ASYNC_WAITING_QUEUE.remove(&id) {
+                if let Some((_, sender)) = channel.async_waiting_queue.remove(&id) {
                     if let Err(_) = sender.send(()) {
                         error!(
                             "failed to send to caller after workflow finished, workflow id: {}",
@@ -61,7 +117,7 @@ impl Channel {
                 }
 
                 // notify caller if using block_execute
-                if let Some((_, sender)) = WAITING_QUEUE.remove(&id) {
+                if let Some((_, sender)) = channel.waiting_queue.remove(&id) {
                     if let Err(e) = sender.send(()) {
                         error!(
                             "failed to send to caller after workflow finished, workflow id: {}, {}",
@@ -72,10 +128,13 @@ impl Channel {
                 }
             });
         }
+        Ok(())
     }
 
     pub fn evaluate_once() -> TegResult<()> {
-        let wee = EVAL_EVENT_CHANNEL
+        let wee = EngineContext::current()?
+            .channel
+            .eval_event_channel
             .1
             .try_recv()
             .map_err(|_| ErrorCode::NotFound("Evaluation Event not found"))?;
@@ -90,7 +149,10 @@ impl Channel {
         let (sender, receiver) = crossbeam_channel::bounded(0);
 
         let workflow_instance_id = WorkflowService::start_workflow(request)?;
-        WAITING_QUEUE.insert(workflow_instance_id.clone(), sender);
+        EngineContext::current()?
+            .channel
+            .waiting_queue
+            .insert(workflow_instance_id.clone(), sender);
 
         receiver.recv_timeout(timeout).map_err(|e| {
             warn!(
@@ -105,14 +167,24 @@ impl Channel {
         Self::fetch_execute_output(workflow_instance_id.as_str())
     }
 
-    pub async fn async_execute(
+    /// Starts the workflow and returns the receiver notified once its evaluation is finished.
+    pub fn async_start(
         request: StartWorkflowRequest,
-    ) -> TegResult<HashMap<InlineStr, Object>> {
+    ) -> TegResult<(InlineStr, tokio::sync::oneshot::Receiver<()>)> {
         let (sender, receiver) = tokio::sync::oneshot::channel();
 
         let workflow_instance_id = WorkflowService::start_workflow(request)?;
-        ASYNC_WAITING_QUEUE.insert(workflow_instance_id.clone(), sender);
+        EngineContext::current()?
+            .channel
+            .async_waiting_queue
+            .insert(workflow_instance_id.clone(), sender);
+        Ok((workflow_instance_id, receiver))
+    }
 
+    pub async fn async_wait(
+        workflow_instance_id: &str,
+        receiver: tokio::sync::oneshot::Receiver<()>,
+    ) -> TegResult<()> {
         receiver.await.map_err(|e| {
             warn!(
                 "failed to execute workflow, workflow id: {}, {}",
@@ -122,11 +194,12 @@ impl Channel {
                 "failed to execute workflow, workflow id: {}",
                 workflow_instance_id
             ))
-        })?;
-        Self::fetch_execute_output(workflow_instance_id.as_str())
+        })
     }
 
-    fn fetch_execute_output(workflow_instance_id: &str) -> TegResult<HashMap<InlineStr, Object>> {
+    pub fn fetch_execute_output(
+        workflow_instance_id: &str,
+    ) -> TegResult<HashMap<InlineStr, Object>> {
         let (workflow_status, workflow) =
             ExecutionService::get_execution_status(workflow_instance_id, false)?;
 
@@ -9,7 +9,7 @@ use tegmine_common::{
 
 use super::tasks::SystemTaskRegistry;
 use crate::config::Properties;
-use crate::dao::MetadataDao;
+use crate::dao::Daos;
 use crate::metrics::Monitors;
 use crate::model::{TaskModel, TaskStatus, WorkflowModel, WorkflowStatus};
 use crate::runtime::execution::mapper::{TaskMapperContext, TaskMapperRegistry};
@@ -70,9 +70,11 @@ impl DeciderService {
         let mut has_successful_terminate_task = false;
         for task in &mut workflow.tasks {
             // Filter the list of tasks and include only tasks that are not retried, not executed
-            // marked to be skipped and not part of System tasks that is DECISION, FORK, JOIN
+            // and not part of System tasks that is DECISION, FORK, JOIN. The tasks skipped before
+            // being scheduled are created executed, while the tasks skipped once scheduled are
+            // processed as any finished task to schedule the tasks following them.
             // This list will be empty for a new workflow being started
-            if !task.retried && task.status != TaskStatus::Skipped && !task.executed {
+            if !task.retried && !task.executed {
                 pending_tasks.push(task);
             }
 
@@ -102,7 +104,7 @@ impl DeciderService {
         // A new workflow does not enter this code branch
         for pending_task_ptr in pending_tasks {
             let pending_task = from_addr_mut!(pending_task_ptr);
-            if SystemTaskRegistry::is_system_task(&pending_task.task_type)
+            if SystemTaskRegistry::is_system_task(&pending_task.task_type)?
                 && !pending_task.status.is_terminal()
             {
                 if !tasks_to_be_scheduled.contains_key(&pending_task.reference_task_name) {
@@ -128,7 +130,7 @@ impl DeciderService {
                 Self::check_task_poll_timeout(task_definition, from_addr_mut!(pending_task_ptr))?;
                 // If the task has not been updated for "responseTimeoutSeconds" then mark task as
                 // TIMED_OUT
-                if Self::is_response_timeout(task_definition, &pending_task) {
+                if Self::is_response_timeout(task_definition, &pending_task)? {
                     Self::timeout_task(task_definition, from_addr_mut!(pending_task_ptr));
                 }
             }
@@ -406,7 +408,7 @@ impl DeciderService {
         let workflow_def = &workflow.workflow_definition;
 
         // Get the following task after the last completed task
-        if SystemTaskRegistry::is_system_task(&task.task_type)
+        if SystemTaskRegistry::is_system_task(&task.task_type)?
             && (TaskType::Switch.as_ref().eq(&task.task_type))
         {
             if task
@@ -473,16 +475,14 @@ impl DeciderService {
     ) -> TegResult<Option<TaskModel>> {
         let retry_count = task.retry_count;
 
-        let task_def_guard = if task_def.is_none() {
-            MetadataDao::get_task_def(&task.task_def_name)
+        let stored_task_def = if task_def.is_none() {
+            Daos::get()?
+                .metadata_dao
+                .get_task_def(&task.task_def_name)?
         } else {
             None
         };
-        let task_def = if let Some(task_def) = task_def {
-            Some(task_def)
-        } else {
-            task_def_guard.as_ref().map(|x| x.value())
-        };
+        let task_def = task_def.or(stored_task_def.as_ref());
 
         let expected_retry_count = if let Some(task_def) = task_def {
             workflow_task
@@ -693,9 +693,9 @@ impl DeciderService {
         }
     }
 
-    fn is_response_timeout(task_def: &TaskDef, task: &TaskModel) -> bool {
-        if task.status.is_terminal() || Self::is_async_complete_system_task(task) {
-            return false;
+    fn is_response_timeout(task_def: &TaskDef, task: &TaskModel) -> TegResult<bool> {
+        if task.status.is_terminal() || Self::is_async_complete_system_task(task)? {
+            return Ok(false);
         }
 
         // calculate pendingTime
@@ -708,7 +708,7 @@ impl DeciderService {
         };
         let pending_time = now - (reference_time + callback_time);
         Monitors::record_task_pending_time(&task.task_type, &task.workflow_type, pending_time);
-        let threshold_ms = Properties::default().task_pending_time_threshold_sec * 1000;
+        let threshold_ms = Properties::get()?.task_pending_time_threshold_sec * 1000;
         if pending_time > threshold_ms {
             warn!(
                 "Task: {} of type: {} in workflow: {}/{} is in pending state for longer than {} ms",
@@ -721,7 +721,7 @@ impl DeciderService {
         }
 
         if task.status != TaskStatus::InProgress || task_def.response_timeout_seconds == 0 {
-            return false;
+            return Ok(false);
         }
 
         debug!(
@@ -734,11 +734,11 @@ impl DeciderService {
 
         if no_response_time < adjusted_response_timeout {
             debug!("Current responseTime: {} has not exceeded the configured responseTimeout of {} for the Task: {:?} with Task Definition: {:?}", pending_time,response_timeout,task,task_def);
-            return false;
+            return Ok(false);
         }
 
         Monitors::record_task_response_timeout(&task.task_def_name);
-        true
+        Ok(true)
     }
 
     fn timeout_task(task_def: &TaskDef, task: &mut TaskModel) {
@@ -800,11 +800,13 @@ impl DeciderService {
         // fork.
         // A new task must only be scheduled if a task, with the same reference name is not already
         // in this workflow instance
-        Ok(TaskMapperRegistry::get_task_mapper(&task_to_schedule.type_)
-            .get_mapped_tasks(task_mapper_context)?
-            .into_iter()
-            .filter(|x| !tasks_in_workflow.contains(&&x.reference_task_name))
-            .collect::<Vec<_>>())
+        Ok(
+            TaskMapperRegistry::get_task_mapper(&task_to_schedule.type_)?
+                .get_mapped_tasks(task_mapper_context)?
+                .into_iter()
+                .filter(|x| !tasks_in_workflow.contains(&&x.reference_task_name))
+                .collect::<Vec<_>>(),
+        )
     }
 
     fn is_task_skipped(
@@ -830,11 +832,9 @@ impl DeciderService {
         }
     }
 
-    fn is_async_complete_system_task(task: &TaskModel) -> bool {
-        SystemTaskRegistry::is_system_task(&task.task_type)
-            && SystemTaskRegistry::get(&task.task_type)
-                .expect("not none")
-                .is_async_complete(task)
+    fn is_async_complete_system_task(task: &TaskModel) -> TegResult<bool> {
+        Ok(SystemTaskRegistry::is_system_task(&task.task_type)?
+            && SystemTaskRegistry::get(&task.task_type)?.is_async_complete(task))
     }
 }
 
@@ -1,6 +1,4 @@
-use dashmap::mapref::one::Ref;
 use dashmap::DashMap;
-use once_cell::sync::Lazy;
 use tegmine_common::prelude::*;
 
 use super::javascript_evaluator::JavascriptEvaluator;
@@ -9,36 +7,47 @@ use super::value_param_evaluator::ValueParamEvaluator;
 // use super::javascript_evaluator_v8_tl::JavascriptEvaluatorV8Tl;
 // use super::rhai_evaluator::RhaiEvaluator;
 use super::Evaluator;
+use crate::engine::EngineContext;
 
-pub struct EvaluatorRegistry;
+/// Holds the evaluators of the SWITCH, INLINE and DO_WHILE expressions by their evaluator type.
+pub struct EvaluatorRegistry {
+    registry: DashMap<InlineStr, Arc<dyn Evaluator>>,
+}
+
+impl Default for EvaluatorRegistry {
+    fn default() -> Self {
+        let registry = DashMap::new();
+        registry.insert(
+            InlineStr::from("value-param"),
+            Arc::new(ValueParamEvaluator) as Arc<dyn Evaluator>,
+        );
+        registry.insert(
+            InlineStr::from("javascript"),
+            Arc::new(JavascriptEvaluator) as Arc<dyn Evaluator>,
+        );
+        // registry.insert(
+        //     InlineStr::from("javascript"),
+        //     Arc::new(JavascriptEvaluatorV8) as Arc<dyn Evaluator>,
+        // );
+        // registry.insert(
+        //     InlineStr::from("javascript"),
+        //     Arc::new(JavascriptEvaluatorV8Tl) as Arc<dyn Evaluator>,
+        // );
+        // registry.insert(
+        //     InlineStr::from("rhai"),
+        //     Arc::new(RhaiEvaluator) as Arc<dyn Evaluator>,
+        // );
 
-static REGISTRY: Lazy<DashMap<InlineStr, Box<dyn Evaluator>>> = Lazy::new(|| {
-    let map = DashMap::new();
-    map.insert(
-        InlineStr::from("value-param"),
-        Box::new(ValueParamEvaluator) as Box<dyn Evaluator>,
-    );
-    map.insert(
-        InlineStr::from("javascript"),
-        Box::new(JavascriptEvaluator) as Box<dyn Evaluator>,
-    );
-    // map.insert(
-    //     InlineStr::from("javascript"),
-    //     Box::new(JavascriptEvaluatorV8) as Box<dyn Evaluator>,
-    // );
-    // map.insert(
-    //     InlineStr::from("javascript"),
-    //     Box::new(JavascriptEvaluatorV8Tl) as Box<dyn Evaluator>,
-    // );
-    // map.insert(
-    //     InlineStr::from("rhai"),
-    //     Box::new(RhaiEvaluator) as Box<dyn Evaluator>,
-    // );
-    map
-});
+        Self { registry }
+    }
+}
 
 impl EvaluatorRegistry {
-    pub fn get_evaluator(evaluator_type: &InlineStr) -> Option<Ref<InlineStr, Box<dyn Evaluator>>> {
-        REGISTRY.get(evaluator_type)
+    pub fn get_evaluator(evaluator_type: &InlineStr) -> TegResult<Option<Arc<dyn Evaluator>>> {
+        Ok(EngineContext::current()?
+            .evaluator_registry
+            .registry
+            .get(evaluator_type)
+            .map(|x| Arc::clone(x.value())))
     }
 }
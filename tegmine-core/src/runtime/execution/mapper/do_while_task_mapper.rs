@@ -3,7 +3,7 @@ use tegmine_common::prelude::*;
 use tegmine_common::{TaskType, WorkflowTask};
 
 use super::{TaskMapper, TaskMapperContext};
-use crate::dao::MetadataDao;
+use crate::dao::Daos;
 use crate::model::{TaskModel, TaskStatus};
 use crate::utils::ParametersUtils;
 
@@ -43,16 +43,14 @@ impl TaskMapper for DoWhileTaskMapper {
             }
         }
 
-        let task_def_guard;
+        let stored_task_def;
         let task_def = if let Some(task_def) = workflow_task.task_definition.as_ref() {
             Some(task_def)
         } else {
-            if let Some(task_def_ref) = MetadataDao::get_task_def(&workflow_task.name) {
-                task_def_guard = task_def_ref;
-                Some(task_def_guard.value())
-            } else {
-                None
-            }
+            stored_task_def = Daos::get()?
+                .metadata_dao
+                .get_task_def(&workflow_task.name)?;
+            stored_task_def.as_ref()
         };
 
         let mut do_while_task = task_mapper_context.create_task_model(TaskStatus::InProgress);
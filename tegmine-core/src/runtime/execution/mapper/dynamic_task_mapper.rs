@@ -2,7 +2,7 @@ use tegmine_common::prelude::*;
 use tegmine_common::{TaskDef, TaskType, WorkflowTask};
 
 use super::{TaskMapper, TaskMapperContext};
-use crate::dao::MetadataDao;
+use crate::dao::Daos;
 use crate::model::{TaskModel, TaskStatus};
 use crate::utils::ParametersUtils;
 
@@ -87,8 +87,11 @@ impl DynamicTaskMapper {
         if let Some(task_def) = workflow_task.task_definition.as_ref() {
             Ok(task_def.clone())
         } else {
-            if let Some(task_def) = MetadataDao::get_task_def(&workflow_task.name) {
-                Ok(task_def.clone())
+            if let Some(task_def) = Daos::get()?
+                .metadata_dao
+                .get_task_def(&workflow_task.name)?
+            {
+                Ok(task_def)
             } else {
                 fmt_err!(
                     TerminateWorkflow,
@@ -1,9 +1,12 @@
+use chrono::Utc;
 use tegmine_common::prelude::*;
-use tegmine_common::TaskType;
+use tegmine_common::{TaskType, WorkflowTask};
 
 use super::TaskMapper;
-use crate::model::TaskModel;
+use crate::dao::Daos;
+use crate::model::{TaskModel, TaskStatus};
 use crate::runtime::execution::mapper::TaskMapperContext;
+use crate::runtime::execution::DeciderService;
 
 /// An implementation of `TaskMapper` to map a `WorkflowTask` of type `TaskType::FORK_JOIN_DYNAMIC`
 /// to a LinkedList of `TaskModel` beginning with a `TaskType::TASK_TYPE_FORK`, followed by the user
@@ -12,9 +15,19 @@ pub struct ForkJoinDynamicTaskMapper;
 
 impl TaskMapper for ForkJoinDynamicTaskMapper {
     fn get_task_type(&self) -> &str {
-        TaskType::ForkJoin.as_ref()
+        TaskType::ForkJoinDynamic.as_ref()
     }
 
+    /// This method gets the list of tasks that need to scheduled when the task to scheduled is of
+    /// type `TaskType::ForkJoinDynamic`. The dynamic tasks and their input are read from the
+    /// parameters named by `WorkflowTask::dynamic_fork_tasks_param` and
+    /// `WorkflowTask::dynamic_fork_tasks_input_param_name`.
+    ///
+    /// return List of tasks in the following order:
+    /// `TaskType::TASK_TYPE_FORK` with `TaskStatus::Completed`
+    /// Might be any kind of task, but in most cases is a UserDefinedTask with
+    /// `TaskStatus::Scheduled`
+    /// `TaskType::Join` with `TaskStatus::InProgress`, waiting on all the dynamic tasks
     fn get_mapped_tasks(
         &self,
         task_mapper_context: TaskMapperContext,
@@ -24,6 +37,181 @@ impl TaskMapper for ForkJoinDynamicTaskMapper {
             task_mapper_context
         );
 
-        todo!()
+        let workflow_task = from_addr!(task_mapper_context.workflow_task);
+        let workflow_model = task_mapper_context.workflow_model;
+
+        // Get the list of dynamic tasks and the input for the tasks
+        let (dynamic_fork_tasks, tasks_input) =
+            Self::get_dynamic_fork_tasks_and_input(workflow_task, &task_mapper_context.task_input)?;
+
+        // Create Fork Task which needs to be followed by the dynamic tasks
+        let mut fork_task = task_mapper_context.create_task_model(TaskStatus::Completed);
+        fork_task.task_type = TaskType::TASK_TYPE_FORK.into();
+        fork_task.task_def_name = TaskType::TASK_TYPE_FORK.into();
+        let epoch_millis = Utc::now().timestamp_millis();
+        fork_task.start_time = epoch_millis;
+        fork_task.end_time = epoch_millis;
+        let joined_task_ref_names = dynamic_fork_tasks
+            .iter()
+            .map(|x| x.task_reference_name.clone())
+            .collect::<Vec<_>>();
+        fork_task.input_data.insert(
+            "forkedTasks".into(),
+            joined_task_ref_names
+                .iter()
+                .map(Object::from)
+                .collect::<Vec<_>>()
+                .into(),
+        );
+        fork_task.input_data.insert(
+            "forkedTaskDefs".into(),
+            Object::from_json(&WorkflowTask::to_jsonlist(&dynamic_fork_tasks)),
+        );
+
+        let mut mapped_tasks = vec![fork_task];
+        // Add each dynamic task to the mapped tasks, with its input from the dynamic tasks input
+        for dynamic_fork_task in &dynamic_fork_tasks {
+            let forked_tasks = DeciderService::get_tasks_to_be_scheduled(
+                workflow_model,
+                dynamic_fork_task,
+                task_mapper_context.retry_count,
+            )?;
+
+            // It's an error state if no forkedTasks can be decided upon. In the cases where we've
+            // seen this happen is when the same dynamic task is attempted to be created twice in
+            // the same workflow run.
+            if forked_tasks.is_empty() {
+                let existing_task = workflow_model.tasks.iter().any(|x| {
+                    (x.status == TaskStatus::InProgress || x.status.is_terminal())
+                        && x.reference_task_name
+                            .eq(&dynamic_fork_task.task_reference_name)
+                });
+                let reason = if existing_task {
+                    format!(
+                        "Task {} within the FORK_JOIN_DYNAMIC {} has already been scheduled, make sure the task reference names are unique",
+                        dynamic_fork_task.task_reference_name, workflow_task.task_reference_name
+                    )
+                } else {
+                    format!(
+                        "Cannot schedule task {} within the FORK_JOIN_DYNAMIC {}",
+                        dynamic_fork_task.task_reference_name, workflow_task.task_reference_name
+                    )
+                };
+                error!("{}", reason);
+                return str_err!(TerminateWorkflow, reason);
+            }
+
+            for mut forked_task in forked_tasks {
+                if let Some(forked_task_input) = tasks_input.get(&forked_task.reference_task_name) {
+                    forked_task.input_data.extend(forked_task_input.clone());
+                }
+                mapped_tasks.push(forked_task);
+            }
+        }
+
+        // From the workflow definition get the next task and make sure that it is a JOIN task.
+        // The dynamic fork tasks need to be followed by a join task
+        let join_workflow_task = workflow_model
+            .workflow_definition
+            .get_next_task(&workflow_task.task_reference_name)
+            .filter(|x| x.type_.eq(TaskType::Join.as_ref()))
+            .ok_or_else(|| {
+                ErrorCode::TerminateWorkflow(
+                    "Dynamic join definition is not followed by a join task. Check the workflow definition.",
+                )
+            })?;
+
+        // The JOIN waits on the dynamic tasks, which are only known now
+        let mut join_workflow_task = join_workflow_task.clone();
+        join_workflow_task.join_on = joined_task_ref_names;
+        mapped_tasks.extend(DeciderService::get_tasks_to_be_scheduled(
+            workflow_model,
+            &join_workflow_task,
+            task_mapper_context.retry_count,
+        )?);
+
+        Ok(mapped_tasks)
+    }
+}
+
+impl ForkJoinDynamicTaskMapper {
+    /// This method is used to get the List of dynamic workflow tasks and their input based on the
+    /// `WorkflowTask::dynamic_fork_tasks_param`
+    ///
+    /// return a tuple, the first element is the list of dynamic tasks, the second is the map from
+    /// the reference name of every dynamic task to its input
+    fn get_dynamic_fork_tasks_and_input(
+        workflow_task: &WorkflowTask,
+        task_input: &HashMap<InlineStr, Object>,
+    ) -> TegResult<(
+        Vec<WorkflowTask>,
+        HashMap<InlineStr, HashMap<InlineStr, Object>>,
+    )> {
+        let dynamic_fork_tasks_json = match task_input.get(&workflow_task.dynamic_fork_tasks_param)
+        {
+            Some(Object::List(list)) => list,
+            other => {
+                return fmt_err!(
+                    TerminateWorkflow,
+                    "Dynamic tasks could not be created. The value of {} is not a list, found: {:?}",
+                    workflow_task.dynamic_fork_tasks_param,
+                    other
+                );
+            }
+        };
+
+        let mut dynamic_fork_tasks = Vec::with_capacity(dynamic_fork_tasks_json.len());
+        for dynamic_fork_task_json in dynamic_fork_tasks_json {
+            let mut json = dynamic_fork_task_json.to_json();
+            // the dynamic tasks are usually generated without input parameters, their input is
+            // provided by the dynamic tasks input
+            if let Some(json) = json.as_object_mut() {
+                json.entry("inputParameters")
+                    .or_insert_with(|| serde_json::json!({}));
+            }
+            let mut dynamic_fork_task = WorkflowTask::try_from(&json).map_err(|e| {
+                ErrorCode::TerminateWorkflow(format!(
+                    "Dynamic tasks could not be created. The value of {} is invalid: {}",
+                    workflow_task.dynamic_fork_tasks_param,
+                    e.message()
+                ))
+            })?;
+            if dynamic_fork_task.task_definition.is_none() {
+                dynamic_fork_task.task_definition = Daos::get()?
+                    .metadata_dao
+                    .get_task_def(&dynamic_fork_task.name)?;
+            }
+            dynamic_fork_tasks.push(dynamic_fork_task);
+        }
+
+        let mut tasks_input = HashMap::default();
+        match task_input.get(&workflow_task.dynamic_fork_tasks_input_param_name) {
+            Some(Object::Map(input)) => {
+                for (task_ref_name, forked_task_input) in input {
+                    match forked_task_input {
+                        Object::Map(forked_task_input) => {
+                            tasks_input.insert(task_ref_name.clone(), forked_task_input.clone());
+                        }
+                        Object::Null => {}
+                        other => {
+                            return fmt_err!(
+                                TerminateWorkflow,
+                                "Input to the dynamically forked task {} is not a map -> expecting a map of K,V but found {:?}",
+                                task_ref_name,
+                                other
+                            );
+                        }
+                    }
+                }
+            }
+            other => {
+                return fmt_err!(
+                    TerminateWorkflow,
+                    "Input to the dynamically forked tasks is not a map -> expecting a map of K,V but found {:?}",
+                    other
+                );
+            }
+        }
+        Ok((dynamic_fork_tasks, tasks_input))
     }
 }
@@ -0,0 +1,54 @@
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::{TaskMapper, TaskMapperContext};
+use crate::dao::Daos;
+use crate::model::{TaskModel, TaskStatus};
+
+/// An implementation of `TaskMapper` to map a `WorkflowTask` of type `TaskType::Http` to a
+/// `TaskModel` of type `TaskType::Http` with `TaskStatus::Scheduled`
+pub struct HttpTaskMapper;
+
+impl TaskMapper for HttpTaskMapper {
+    fn get_task_type(&self) -> &str {
+        TaskType::Http.as_ref()
+    }
+
+    /// This method maps a `WorkflowTask` of type `TaskType::Http` to a `TaskModel` in a
+    /// `TaskStatus::Scheduled` state. The task definition is optional, it is only used for the
+    /// retries and the rate limits of the task.
+    ///
+    /// return a List with just one HTTP task
+    fn get_mapped_tasks(
+        &self,
+        task_mapper_context: TaskMapperContext,
+    ) -> TegResult<Vec<TaskModel>> {
+        debug!(
+            "TaskMapperContext {:?} in HttpTaskMapper",
+            task_mapper_context
+        );
+
+        let workflow_task = from_addr!(task_mapper_context.workflow_task);
+        let stored_task_def;
+        let task_def = if workflow_task.task_definition.is_some() {
+            workflow_task.task_definition.as_ref()
+        } else {
+            stored_task_def = Daos::get()?
+                .metadata_dao
+                .get_task_def(&workflow_task.name)?;
+            stored_task_def.as_ref()
+        };
+
+        let mut http_task = task_mapper_context.create_task_model(TaskStatus::Scheduled);
+        http_task.task_type = TaskType::Http.as_ref().into();
+        http_task.input_data = task_mapper_context.task_input.clone();
+        http_task.retry_count = task_mapper_context.retry_count;
+        http_task.callback_after_seconds = workflow_task.start_delay as i64;
+        if let Some(task_def) = task_def {
+            http_task.rate_limit_per_frequency = task_def.rate_limit_per_frequency.unwrap_or(0);
+            http_task.rate_limit_frequency_in_seconds =
+                task_def.rate_limit_frequency_in_seconds.unwrap_or(1);
+        }
+        Ok(vec![http_task])
+    }
+}
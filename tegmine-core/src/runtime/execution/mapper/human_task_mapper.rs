@@ -0,0 +1,67 @@
+use chrono::Utc;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::{TaskMapper, TaskMapperContext};
+use crate::model::{TaskModel, TaskStatus};
+use crate::runtime::execution::tasks::Human;
+use crate::utils::DateTimeUtils;
+
+/// An implementation of `TaskMapper` to map a `WorkflowTask` of type `TaskType::Human` to a
+/// `TaskModel` of type `TaskType::Human` with `TaskStatus::InProgress`. A task without any
+/// `assignee` nor `candidateGroups` input could be claimed by anyone, it fails with a terminal
+/// error instead.
+pub struct HumanTaskMapper;
+
+impl HumanTaskMapper {
+    pub const EXPIRY_INPUT: &'static str = "expiry";
+}
+
+impl TaskMapper for HumanTaskMapper {
+    fn get_task_type(&self) -> &str {
+        TaskType::Human.as_ref()
+    }
+
+    fn get_mapped_tasks(
+        &self,
+        task_mapper_context: TaskMapperContext,
+    ) -> TegResult<Vec<TaskModel>> {
+        debug!(
+            "TaskMapperContext {:?} in HumanTaskMapper",
+            task_mapper_context
+        );
+
+        let mut human_task = task_mapper_context.create_task_model(TaskStatus::InProgress);
+        human_task.task_type = TaskType::Human.as_ref().into();
+        human_task.start_time = Utc::now().timestamp_millis();
+        human_task.input_data = task_mapper_context.task_input.clone();
+
+        if !Human::has_candidates(&human_task) {
+            human_task.status = TaskStatus::FailedWithTerminalError;
+            human_task.reason_for_incompletion = format!(
+                "Missing '{}' or '{}' in HUMAN task's input parameters",
+                Human::ASSIGNEE,
+                Human::CANDIDATE_GROUPS
+            )
+            .into();
+            return Ok(vec![human_task]);
+        }
+
+        // the task expires after the optional expiry duration
+        if let Some(expiry) = human_task
+            .input_data
+            .get(Self::EXPIRY_INPUT)
+            .and_then(|x| x.as_string().ok())
+            .filter(|x| !x.trim().is_empty())
+        {
+            match DateTimeUtils::parse_duration(expiry) {
+                Ok(expiry) => human_task.wait_timeout = human_task.start_time + expiry * 1000,
+                Err(e) => {
+                    human_task.status = TaskStatus::FailedWithTerminalError;
+                    human_task.reason_for_incompletion = e.message().into();
+                }
+            }
+        }
+        Ok(vec![human_task])
+    }
+}
@@ -0,0 +1,31 @@
+use chrono::Utc;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::TaskMapper;
+use crate::model::{TaskModel, TaskStatus};
+use crate::runtime::execution::mapper::TaskMapperContext;
+
+pub struct InlineTaskMapper;
+
+impl TaskMapper for InlineTaskMapper {
+    fn get_task_type(&self) -> &str {
+        TaskType::Inline.as_ref()
+    }
+
+    fn get_mapped_tasks(
+        &self,
+        task_mapper_context: TaskMapperContext,
+    ) -> TegResult<Vec<TaskModel>> {
+        debug!(
+            "TaskMapperContext {:?} in InlineTaskMapper",
+            task_mapper_context
+        );
+
+        let mut inline_task = task_mapper_context.create_task_model(TaskStatus::InProgress);
+        inline_task.start_time = Utc::now().timestamp_millis();
+        inline_task.input_data = task_mapper_context.task_input.clone();
+
+        Ok(vec![inline_task])
+    }
+}
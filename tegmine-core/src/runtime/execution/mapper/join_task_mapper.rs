@@ -1,6 +1,6 @@
 use chrono::Utc;
 use tegmine_common::prelude::*;
-use tegmine_common::{TaskType, WorkflowTask};
+use tegmine_common::{JoinMode, TaskType, WorkflowTask};
 
 use super::TaskMapper;
 use crate::model::{TaskModel, TaskStatus};
@@ -12,7 +12,7 @@ pub struct JoinTaskMapper;
 
 impl TaskMapper for JoinTaskMapper {
     fn get_task_type(&self) -> &str {
-        TaskType::ForkJoin.as_ref()
+        TaskType::Join.as_ref()
     }
 
     /// This method maps `TaskMapper` to map a `WorkflowTask` of type `TaskType::Join` to a
@@ -42,6 +42,20 @@ impl TaskMapper for JoinTaskMapper {
                 .collect::<Vec<Object>>()
                 .into(),
         );
+        match workflow_task.join_mode {
+            JoinMode::All => {}
+            JoinMode::Any => {
+                join_input.insert("join_mode".into(), workflow_task.join_mode.as_ref().into());
+            }
+            JoinMode::FirstN => {
+                join_input.insert("join_mode".into(), workflow_task.join_mode.as_ref().into());
+                join_input.insert("join_count".into(), workflow_task.join_count.into());
+            }
+            JoinMode::Quorum => {
+                join_input.insert("join_mode".into(), workflow_task.join_mode.as_ref().into());
+                join_input.insert("join_quorum".into(), workflow_task.join_quorum.into());
+            }
+        }
 
         let mut join_task = task_mapper_context.create_task_model(TaskStatus::InProgress);
         join_task.task_type = TaskType::Join.as_ref().into();
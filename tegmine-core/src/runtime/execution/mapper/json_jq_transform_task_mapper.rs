@@ -0,0 +1,32 @@
+use chrono::Utc;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::TaskMapper;
+use crate::model::{TaskModel, TaskStatus};
+use crate::runtime::execution::mapper::TaskMapperContext;
+
+pub struct JsonJqTransformTaskMapper;
+
+impl TaskMapper for JsonJqTransformTaskMapper {
+    fn get_task_type(&self) -> &str {
+        TaskType::JsonJqTransform.as_ref()
+    }
+
+    fn get_mapped_tasks(
+        &self,
+        task_mapper_context: TaskMapperContext,
+    ) -> TegResult<Vec<TaskModel>> {
+        debug!(
+            "TaskMapperContext {:?} in JsonJqTransformTaskMapper",
+            task_mapper_context
+        );
+
+        let mut json_jq_transform_task =
+            task_mapper_context.create_task_model(TaskStatus::InProgress);
+        json_jq_transform_task.start_time = Utc::now().timestamp_millis();
+        json_jq_transform_task.input_data = task_mapper_context.task_input.clone();
+
+        Ok(vec![json_jq_transform_task])
+    }
+}
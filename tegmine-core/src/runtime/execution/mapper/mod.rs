@@ -7,7 +7,11 @@ mod dynamic_task_mapper;
 mod exclusive_join_task_mapper;
 mod fork_join_dynamic_task_mapper;
 mod fork_join_task_mapper;
+mod http_task_mapper;
+mod human_task_mapper;
+mod inline_task_mapper;
 mod join_task_mapper;
+mod json_jq_transform_task_mapper;
 mod set_variable_task_mapper;
 mod simple_task_mapper;
 mod start_workflow_task_mapper;
@@ -15,6 +19,7 @@ mod sub_workflow_task_mapper;
 mod switch_task_mapper;
 mod terminate_task_mapper;
 mod user_defined_task_mapper;
+mod wait_task_mapper;
 
 pub use task_mapper::TaskMapper;
 pub use task_mapper_context::TaskMapperContext;
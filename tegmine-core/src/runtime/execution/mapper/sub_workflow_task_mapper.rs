@@ -1,8 +1,9 @@
 use tegmine_common::prelude::*;
-use tegmine_common::TaskType;
+use tegmine_common::{SubWorkflowParams, TaskType, WorkflowTask};
 
 use super::{TaskMapper, TaskMapperContext};
-use crate::model::TaskModel;
+use crate::dao::Daos;
+use crate::model::{TaskModel, TaskStatus};
 
 pub struct SubWorkflowTaskMapper;
 
@@ -20,13 +21,79 @@ impl TaskMapper for SubWorkflowTaskMapper {
             task_mapper_context
         );
 
-        // let workflow_task = from_addr!(task_mapper_context.workflow_task);
+        let workflow_task = from_addr!(task_mapper_context.workflow_task);
 
         // Check if there are sub workflow parameters, if not throw an exception, cannot initiate a
         // sub-workflow without workflow params
+        let sub_workflow_params = Self::get_sub_workflow_params(workflow_task)?;
+        let sub_workflow_version = Self::get_sub_workflow_version(sub_workflow_params)?;
 
-        todo!()
+        let mut sub_workflow_task = task_mapper_context.create_task_model(TaskStatus::Scheduled);
+        sub_workflow_task.task_type = TaskType::SubWorkflow.as_ref().into();
+        sub_workflow_task.input_data.insert(
+            "subWorkflowName".into(),
+            sub_workflow_params.name.clone().into(),
+        );
+        sub_workflow_task
+            .input_data
+            .insert("subWorkflowVersion".into(), sub_workflow_version.into());
+        sub_workflow_task.input_data.insert(
+            "subWorkflowTaskToDomain".into(),
+            sub_workflow_params
+                .task_to_domain
+                .iter()
+                .map(|(k, v)| (k.clone(), Object::from(v)))
+                .collect::<HashMap<_, _>>()
+                .into(),
+        );
+        if let Some(workflow_definition) = sub_workflow_params.workflow_definition.as_ref() {
+            sub_workflow_task.input_data.insert(
+                "subWorkflowDefinition".into(),
+                Object::from_json(&workflow_definition.to_json()),
+            );
+        }
+        sub_workflow_task.input_data.insert(
+            "workflowInput".into(),
+            task_mapper_context.task_input.into(),
+        );
+        sub_workflow_task.callback_after_seconds = workflow_task.start_delay as i64;
+        debug!("SubWorkflowTask {:?} created", sub_workflow_task);
+        Ok(vec![sub_workflow_task])
     }
 }
 
-impl SubWorkflowTaskMapper {}
+impl SubWorkflowTaskMapper {
+    fn get_sub_workflow_params(workflow_task: &WorkflowTask) -> TegResult<&SubWorkflowParams> {
+        workflow_task.sub_workflow_param.as_ref().ok_or_else(|| {
+            let reason = format!(
+                "Task {} is defined as sub-workflow and is missing subWorkflowParams. Please check the workflow definition",
+                workflow_task.name
+            );
+            error!("{}", reason);
+            ErrorCode::TerminateWorkflow(reason)
+        })
+    }
+
+    /// The version of the inline workflow definition, else the version set in the parameters,
+    /// else the latest version of the registered workflow definition.
+    fn get_sub_workflow_version(sub_workflow_params: &SubWorkflowParams) -> TegResult<i32> {
+        if let Some(workflow_definition) = sub_workflow_params.workflow_definition.as_ref() {
+            return Ok(workflow_definition.version);
+        }
+        if let Some(version) = sub_workflow_params.version {
+            return Ok(version);
+        }
+        Daos::get()?
+            .metadata_dao
+            .get_latest_workflow_def(&sub_workflow_params.name)?
+            .map(|x| x.version)
+            .ok_or_else(|| {
+                let reason = format!(
+                    "The Task {} defined as a sub-workflow has no workflow definition available",
+                    sub_workflow_params.name
+                );
+                error!("{}", reason);
+                ErrorCode::TerminateWorkflow(reason)
+            })
+    }
+}
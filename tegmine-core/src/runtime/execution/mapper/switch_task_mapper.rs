@@ -41,7 +41,7 @@ impl TaskMapper for SwitchTaskMapper {
         let task_input = std::mem::take(&mut task_mapper_context.task_input).into();
 
         // get the expression to be evaluated
-        let evaluator = EvaluatorRegistry::get_evaluator(&workflow_task.evaluator_type);
+        let evaluator = EvaluatorRegistry::get_evaluator(&workflow_task.evaluator_type)?;
         if evaluator.is_none() {
             error!(
                 "No evaluator registered for type: {}",
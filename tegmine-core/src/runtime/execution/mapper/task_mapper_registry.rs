@@ -1,58 +1,132 @@
-use dashmap::mapref::one::Ref;
 use dashmap::DashMap;
-use once_cell::sync::Lazy;
 use tegmine_common::prelude::*;
 use tegmine_common::TaskType;
 
+use super::do_while_task_mapper::DoWhileTaskMapper;
 use super::dynamic_task_mapper::DynamicTaskMapper;
+use super::exclusive_join_task_mapper::ExclusiveJoinTaskMapper;
+use super::fork_join_dynamic_task_mapper::ForkJoinDynamicTaskMapper;
+use super::fork_join_task_mapper::ForkJoinTaskMapper;
+use super::http_task_mapper::HttpTaskMapper;
+use super::human_task_mapper::HumanTaskMapper;
+use super::inline_task_mapper::InlineTaskMapper;
+use super::join_task_mapper::JoinTaskMapper;
+use super::json_jq_transform_task_mapper::JsonJqTransformTaskMapper;
 use super::set_variable_task_mapper::SetVariableTaskMapper;
 use super::start_workflow_task_mapper::StartWorkflowTaskMapper;
+use super::sub_workflow_task_mapper::SubWorkflowTaskMapper;
 use super::switch_task_mapper::SwitchTaskMapper;
 use super::terminate_task_mapper::TerminateTaskMapper;
+use super::wait_task_mapper::WaitTaskMapper;
 use super::TaskMapper;
+use crate::engine::EngineContext;
 
-static REGISTRY: Lazy<DashMap<InlineStr, Box<dyn TaskMapper>>> = Lazy::new(|| {
-    let map = DashMap::new();
-    map.insert(
-        InlineStr::from(TaskType::Switch.as_ref()),
-        Box::new(SwitchTaskMapper) as Box<dyn TaskMapper>,
-    );
-    map.insert(
-        InlineStr::from(TaskType::SetVariable.as_ref()),
-        Box::new(SetVariableTaskMapper) as Box<dyn TaskMapper>,
-    );
-    map.insert(
-        InlineStr::from(TaskType::Dynamic.as_ref()),
-        Box::new(DynamicTaskMapper) as Box<dyn TaskMapper>,
-    );
-    map.insert(
-        InlineStr::from(TaskType::Terminate.as_ref()),
-        Box::new(TerminateTaskMapper) as Box<dyn TaskMapper>,
-    );
-    map.insert(
-        InlineStr::from(TaskType::StartWorkflow.as_ref()),
-        Box::new(StartWorkflowTaskMapper) as Box<dyn TaskMapper>,
-    );
-    map
-});
+/// Holds the `TaskMapper` of every task type, the decider maps a `WorkflowTask` to the tasks to
+/// schedule with it. A registered mapper does not replace the built-in mapper of its type.
+pub struct TaskMapperRegistry {
+    registry: DashMap<InlineStr, Arc<dyn TaskMapper>>,
+    custom_registry: DashMap<InlineStr, Arc<dyn TaskMapper>>,
+}
 
-static CUSTOM_REGISTRY: Lazy<DashMap<InlineStr, Box<dyn TaskMapper>>> =
-    Lazy::new(|| DashMap::new());
+impl Default for TaskMapperRegistry {
+    fn default() -> Self {
+        let registry = DashMap::new();
+        registry.insert(
+            InlineStr::from(TaskType::Switch.as_ref()),
+            Arc::new(SwitchTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::SetVariable.as_ref()),
+            Arc::new(SetVariableTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::Dynamic.as_ref()),
+            Arc::new(DynamicTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::Terminate.as_ref()),
+            Arc::new(TerminateTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::StartWorkflow.as_ref()),
+            Arc::new(StartWorkflowTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::SubWorkflow.as_ref()),
+            Arc::new(SubWorkflowTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::ForkJoin.as_ref()),
+            Arc::new(ForkJoinTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::ForkJoinDynamic.as_ref()),
+            Arc::new(ForkJoinDynamicTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::Join.as_ref()),
+            Arc::new(JoinTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::DoWhile.as_ref()),
+            Arc::new(DoWhileTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::ExclusiveJoin.as_ref()),
+            Arc::new(ExclusiveJoinTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::Http.as_ref()),
+            Arc::new(HttpTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::Inline.as_ref()),
+            Arc::new(InlineTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::JsonJqTransform.as_ref()),
+            Arc::new(JsonJqTransformTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::Wait.as_ref()),
+            Arc::new(WaitTaskMapper) as Arc<dyn TaskMapper>,
+        );
+        registry.insert(
+            InlineStr::from(TaskType::Human.as_ref()),
+            Arc::new(HumanTaskMapper) as Arc<dyn TaskMapper>,
+        );
 
-pub struct TaskMapperRegistry;
+        Self {
+            registry,
+            custom_registry: DashMap::new(),
+        }
+    }
+}
 
 impl TaskMapperRegistry {
-    pub fn get_task_mapper(typ_: &InlineStr) -> Ref<InlineStr, Box<dyn TaskMapper>> {
+    pub fn get_task_mapper(typ_: &InlineStr) -> TegResult<Arc<dyn TaskMapper>> {
+        let context = EngineContext::current()?;
+        let registry = &context.task_mapper_registry;
         // FIXME not none check, CUSTOM_REGISTRY
-        REGISTRY
+        Ok(registry
+            .registry
             .get(typ_)
-            .or(CUSTOM_REGISTRY.get(typ_))
-            .expect("USER_DEFINE not none")
+            .or(registry.custom_registry.get(typ_))
+            .map(|x| Arc::clone(x.value()))
+            .expect("USER_DEFINE not none"))
     }
-    pub fn register(typ_: &str, task_mapper: Box<dyn TaskMapper>) {
-        CUSTOM_REGISTRY.insert(InlineStr::from(typ_), task_mapper);
+    pub fn register(typ_: &str, task_mapper: Box<dyn TaskMapper>) -> TegResult<()> {
+        EngineContext::current()?
+            .task_mapper_registry
+            .custom_registry
+            .insert(InlineStr::from(typ_), Arc::from(task_mapper));
+        Ok(())
     }
-    pub fn unregister(typ_: &str) {
-        CUSTOM_REGISTRY.remove(&InlineStr::from(typ_));
+    pub fn unregister(typ_: &str) -> TegResult<()> {
+        EngineContext::current()?
+            .task_mapper_registry
+            .custom_registry
+            .remove(&InlineStr::from(typ_));
+        Ok(())
     }
 }
@@ -2,7 +2,7 @@ use tegmine_common::prelude::*;
 use tegmine_common::{TaskType, WorkflowTask};
 
 use super::TaskMapper;
-use crate::dao::MetadataDao;
+use crate::dao::Daos;
 use crate::model::{TaskModel, TaskStatus};
 use crate::runtime::execution::mapper::TaskMapperContext;
 use crate::utils::ParametersUtils;
@@ -33,13 +33,15 @@ impl TaskMapper for UserDefinedTaskMapper {
             task_mapper_context.workflow_task as *const WorkflowTask as *mut WorkflowTask
         );
 
-        let task_def_guard;
+        let stored_task_def;
         let task_def = if let Some(task_def) = workflow_task.task_definition.as_ref() {
             task_def
         } else {
-            if let Some(task_def_ref) = MetadataDao::get_task_def(&workflow_task.name) {
-                task_def_guard = task_def_ref;
-                task_def_guard.value()
+            stored_task_def = Daos::get()?
+                .metadata_dao
+                .get_task_def(&workflow_task.name)?;
+            if let Some(task_def) = stored_task_def.as_ref() {
+                task_def
             } else {
                 return fmt_err!(
                     TerminateWorkflow,
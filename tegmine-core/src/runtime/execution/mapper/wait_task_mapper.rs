@@ -0,0 +1,77 @@
+use chrono::Utc;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::{TaskMapper, TaskMapperContext};
+use crate::model::{TaskModel, TaskStatus};
+use crate::utils::DateTimeUtils;
+
+/// An implementation of `TaskMapper` to map a `WorkflowTask` of type `TaskType::Wait` to a
+/// `TaskModel` of type `TaskType::Wait` with `TaskStatus::InProgress`
+pub struct WaitTaskMapper;
+
+impl WaitTaskMapper {
+    pub const DURATION_INPUT: &'static str = "duration";
+    pub const UNTIL_INPUT: &'static str = "until";
+
+    /// Gets the time (epoch milliseconds) the task waits until, 0 when it waits for an external
+    /// update.
+    fn get_wait_timeout(task_input: &HashMap<InlineStr, Object>) -> TegResult<i64> {
+        let duration = task_input
+            .get(Self::DURATION_INPUT)
+            .and_then(|x| x.as_string().ok())
+            .filter(|x| !x.trim().is_empty());
+        let until = task_input
+            .get(Self::UNTIL_INPUT)
+            .and_then(|x| x.as_string().ok())
+            .filter(|x| !x.trim().is_empty());
+
+        match (duration, until) {
+            (Some(_), Some(_)) => str_err!(
+                IllegalArgument,
+                "Both 'duration' and 'until' specified. Please provide only one input"
+            ),
+            (Some(duration), None) => {
+                Ok(Utc::now().timestamp_millis() + DateTimeUtils::parse_duration(duration)? * 1000)
+            }
+            (None, Some(until)) => DateTimeUtils::parse_date(until),
+            (None, None) => Ok(0),
+        }
+    }
+}
+
+impl TaskMapper for WaitTaskMapper {
+    fn get_task_type(&self) -> &str {
+        TaskType::Wait.as_ref()
+    }
+
+    fn get_mapped_tasks(
+        &self,
+        task_mapper_context: TaskMapperContext,
+    ) -> TegResult<Vec<TaskModel>> {
+        debug!(
+            "TaskMapperContext {:?} in WaitTaskMapper",
+            task_mapper_context
+        );
+
+        let mut wait_task = task_mapper_context.create_task_model(TaskStatus::InProgress);
+        wait_task.task_type = TaskType::Wait.as_ref().into();
+        wait_task.start_time = Utc::now().timestamp_millis();
+        wait_task.input_data = task_mapper_context.task_input.clone();
+
+        match Self::get_wait_timeout(&wait_task.input_data) {
+            Ok(wait_timeout) => {
+                wait_task.wait_timeout = wait_timeout;
+                if wait_timeout > 0 {
+                    wait_task.callback_after_seconds =
+                        ((wait_timeout - wait_task.start_time) / 1000).max(0);
+                }
+            }
+            Err(e) => {
+                wait_task.status = TaskStatus::FailedWithTerminalError;
+                wait_task.reason_for_incompletion = e.message().into();
+            }
+        }
+        Ok(vec![wait_task])
+    }
+}
@@ -8,11 +8,16 @@ mod decider_service;
 mod start_workflow_input;
 mod terminate_workflow_exception;
 mod workflow_executor;
+mod workflow_sweeper;
 
 pub use async_system_task_executor::AsyncSystemTaskExecutor;
-pub use channels::{Channel, CREATE_EVENT_CHANNEL, EVAL_EVENT_CHANNEL};
+pub use channels::Channel;
 pub use decider_service::{DeciderOutcome, DeciderService};
+pub use evaluators::EvaluatorRegistry;
 pub use mapper::{TaskMapper, TaskMapperContext, TaskMapperRegistry};
 pub use start_workflow_input::StartWorkflowInput;
-pub use tasks::{SystemTaskRegistry, SystemTaskWorkerCoordinator, WorkflowSystemTask};
+pub use tasks::{
+    SystemTaskRegistry, SystemTaskWorker, SystemTaskWorkerCoordinator, WorkflowSystemTask,
+};
 pub use workflow_executor::WorkflowExecutor;
+pub use workflow_sweeper::WorkflowSweeper;
@@ -0,0 +1,290 @@
+use linked_hash_map::LinkedHashMap;
+use numtoa::NumToA;
+use tegmine_common::prelude::*;
+use tegmine_common::{TaskType, TaskUtils, WorkflowTask};
+
+use super::workflow_system_task::WorkflowSystemTask;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::runtime::execution::evaluators::EvaluatorRegistry;
+use crate::runtime::{ExecutionDaoFacade, WorkflowExecutor};
+use crate::utils::ParametersUtils;
+
+/// The DO_WHILE task executes the `WorkflowTask::loop_over` tasks, and decides after every
+/// iteration whether to schedule the next one by evaluating `WorkflowTask::loop_condition`. The
+/// outputs of the tasks of every iteration are kept in the output of the DO_WHILE task, keyed by
+/// the iteration number. The `keepLastN` input parameter limits the number of kept iterations.
+pub struct DoWhile;
+
+impl DoWhile {
+    const ITERATION: &'static str = "iteration";
+    const KEEP_LAST_N: &'static str = "keepLastN";
+    const EVALUATOR_TYPE: &'static str = "javascript";
+
+    /// Gets the latest attempt (the one with the highest retry count) of every task of the current
+    /// iteration. The tasks which have already failed are not evaluated if there is a more
+    /// current one.
+    fn get_relevant_tasks<'a>(
+        workflow: &'a WorkflowModel,
+        do_while_task: &TaskModel,
+        workflow_task: &WorkflowTask,
+    ) -> LinkedHashMap<InlineStr, &'a TaskModel> {
+        let mut relevant_tasks: LinkedHashMap<InlineStr, &TaskModel> = LinkedHashMap::new();
+        for task in workflow.tasks.iter() {
+            if task.iteration == do_while_task.iteration
+                && !task
+                    .reference_task_name
+                    .eq(&do_while_task.reference_task_name)
+                && workflow_task.has(TaskUtils::remove_iteration_from_task_ref_name(
+                    &task.reference_task_name,
+                ))
+            {
+                match relevant_tasks.get_mut(&task.reference_task_name) {
+                    Some(relevant_task) => {
+                        if task.retry_count > relevant_task.retry_count {
+                            *relevant_task = task;
+                        }
+                    }
+                    None => {
+                        relevant_tasks.insert(task.reference_task_name.clone(), task);
+                    }
+                }
+            }
+        }
+        relevant_tasks
+    }
+
+    /// Evaluates the loop condition against the input of the DO_WHILE task, its output and the
+    /// outputs of the loop over tasks.
+    fn evaluate_condition(
+        workflow: &WorkflowModel,
+        do_while_task: &TaskModel,
+        workflow_task: &WorkflowTask,
+    ) -> TegResult<bool> {
+        if workflow_task.loop_condition.is_empty() {
+            return Ok(false);
+        }
+
+        let mut condition_input = ParametersUtils::get_task_input(
+            &workflow_task.input_parameters,
+            workflow,
+            workflow_task.task_definition.as_ref(),
+            Some(&do_while_task.task_id),
+        )?;
+        condition_input.insert(
+            do_while_task.reference_task_name.clone(),
+            do_while_task.output_data.clone().into(),
+        );
+        for task in workflow.tasks.iter() {
+            let task_ref_name =
+                TaskUtils::remove_iteration_from_task_ref_name(&task.reference_task_name);
+            if workflow_task.has(task_ref_name)
+                && !task
+                    .reference_task_name
+                    .eq(&do_while_task.reference_task_name)
+            {
+                condition_input.insert(task_ref_name.into(), task.output_data.clone().into());
+            }
+        }
+
+        let evaluator = EvaluatorRegistry::get_evaluator(&InlineStr::from(Self::EVALUATOR_TYPE))?
+            .ok_or_else(|| {
+            ErrorCode::NotFound(format!(
+                "No evaluator registered for type: {}",
+                Self::EVALUATOR_TYPE
+            ))
+        })?;
+        evaluator
+            .evaluate(
+                &workflow_task.loop_condition,
+                &Object::from(condition_input),
+            )?
+            .as_bool()
+    }
+
+    /// Removes the output and the tasks of the iteration which is no longer one of the last
+    /// `keepLastN` completed ones, the older iterations have been removed as the loop progressed.
+    fn remove_iteration(
+        workflow: &mut WorkflowModel,
+        do_while_task: &mut TaskModel,
+        workflow_task: &WorkflowTask,
+    ) {
+        let keep_last_n = match do_while_task.input_data.get(Self::KEEP_LAST_N) {
+            Some(Object::Int(n)) if *n > 0 => *n,
+            Some(Object::Long(n)) if *n > 0 => *n as i32,
+            _ => return,
+        };
+        let iteration = do_while_task.iteration - keep_last_n;
+        if iteration <= 0 {
+            return;
+        }
+        do_while_task
+            .output_data
+            .remove(iteration.numtoa_str(10, &mut [0; 16]));
+
+        let is_removed = |task: &TaskModel| {
+            task.iteration == iteration
+                && !task
+                    .reference_task_name
+                    .eq(&do_while_task.reference_task_name)
+                && workflow_task.has(TaskUtils::remove_iteration_from_task_ref_name(
+                    &task.reference_task_name,
+                ))
+        };
+        // the removed tasks are unlinked from the list one by one, so that the other tasks are
+        // not moved while they are referenced by the decider
+        let mut remaining = std::mem::take(&mut workflow.tasks);
+        while let Some(position) = remaining.iter().position(is_removed) {
+            let mut tail = remaining.split_off(position);
+            workflow.tasks.append(&mut remaining);
+            if let Some(task) = tail.pop_front() {
+                if let Err(e) = ExecutionDaoFacade::remove_task(&task.task_id) {
+                    error!(
+                        "Failed to remove task {} of iteration {}, error: {}",
+                        task.task_id,
+                        iteration,
+                        e.message()
+                    );
+                }
+            }
+            remaining = tail;
+        }
+        workflow.tasks.append(&mut remaining);
+    }
+
+    fn schedule_next_iteration(
+        workflow: &mut WorkflowModel,
+        do_while_task: &mut TaskModel,
+    ) -> bool {
+        debug!(
+            "Scheduling loop tasks for task {} as condition {} evaluated to true",
+            do_while_task.task_id,
+            do_while_task
+                .workflow_task
+                .as_ref()
+                .map(|x| x.loop_condition.as_str())
+                .unwrap_or_default()
+        );
+        if let Err(e) = WorkflowExecutor::schedule_next_iteration(do_while_task, workflow) {
+            Self::mark_task_failure(
+                do_while_task,
+                TaskStatus::Failed,
+                format!(
+                    "Unable to schedule the iteration {} of the loop, error: {}",
+                    do_while_task.iteration,
+                    e.message()
+                ),
+            );
+        }
+        // Return true even though status not changed. Iteration has to be updated in execution
+        // DAO.
+        true
+    }
+
+    fn mark_task_failure(do_while_task: &mut TaskModel, status: TaskStatus, reason: String) {
+        error!(
+            "Marking task {} failed with error: {}",
+            do_while_task.task_id, reason
+        );
+        do_while_task.reason_for_incompletion = reason.into();
+        do_while_task.status = status;
+    }
+}
+
+impl WorkflowSystemTask for DoWhile {
+    fn get_task_type(&self) -> &str {
+        TaskType::DoWhile.as_ref()
+    }
+
+    fn execute(&self, workflow: &mut WorkflowModel, do_while_task: &mut TaskModel) -> bool {
+        let workflow_task = match do_while_task.workflow_task.clone() {
+            Some(workflow_task) => workflow_task,
+            None => {
+                Self::mark_task_failure(
+                    do_while_task,
+                    TaskStatus::FailedWithTerminalError,
+                    "The loop task has no workflow task".into(),
+                );
+                return true;
+            }
+        };
+
+        // the first iteration is scheduled when the loop task is executed for the first time
+        if do_while_task.iteration == 0 {
+            do_while_task.iteration = 1;
+            do_while_task
+                .output_data
+                .insert(Self::ITERATION.into(), do_while_task.iteration.into());
+            return Self::schedule_next_iteration(workflow, do_while_task);
+        }
+
+        let relevant_tasks = Self::get_relevant_tasks(workflow, do_while_task, &workflow_task);
+        debug!(
+            "Workflow {} waiting for tasks {:?} to complete iteration {}",
+            workflow.workflow_id,
+            relevant_tasks.keys().collect::<Vec<_>>(),
+            do_while_task.iteration
+        );
+
+        let mut failure_reason = None;
+        let mut output = HashMap::with_capacity(relevant_tasks.len());
+        for loop_over_task in relevant_tasks.values() {
+            output.insert(
+                TaskUtils::remove_iteration_from_task_ref_name(&loop_over_task.reference_task_name)
+                    .into(),
+                loop_over_task.output_data.clone().into(),
+            );
+            if !loop_over_task.status.is_successful() {
+                failure_reason = Some(loop_over_task.reason_for_incompletion.clone());
+                break;
+            }
+        }
+        let iteration_complete = relevant_tasks.values().all(|x| x.status.is_terminal());
+        do_while_task.output_data.insert(
+            do_while_task.iteration.numtoa_str(10, &mut [0; 16]).into(),
+            output.into(),
+        );
+
+        if let Some(failure_reason) = failure_reason {
+            Self::mark_task_failure(do_while_task, TaskStatus::Failed, failure_reason.into());
+            return true;
+        }
+
+        if !iteration_complete {
+            // current iteration is not complete (all loop over tasks haven't reached terminal
+            // state)
+            return false;
+        }
+
+        match Self::evaluate_condition(workflow, do_while_task, &workflow_task) {
+            Ok(true) => {
+                Self::remove_iteration(workflow, do_while_task, &workflow_task);
+                do_while_task.iteration += 1;
+                do_while_task
+                    .output_data
+                    .insert(Self::ITERATION.into(), do_while_task.iteration.into());
+                Self::schedule_next_iteration(workflow, do_while_task)
+            }
+            Ok(false) => {
+                debug!(
+                    "Task {} took {} iterations to complete",
+                    do_while_task.task_id, do_while_task.iteration
+                );
+                Self::remove_iteration(workflow, do_while_task, &workflow_task);
+                do_while_task.status = TaskStatus::Completed;
+                true
+            }
+            Err(e) => {
+                Self::mark_task_failure(
+                    do_while_task,
+                    TaskStatus::FailedWithTerminalError,
+                    format!(
+                        "Unable to evaluate condition {}, error: {}",
+                        workflow_task.loop_condition,
+                        e.message()
+                    ),
+                );
+                true
+            }
+        }
+    }
+}
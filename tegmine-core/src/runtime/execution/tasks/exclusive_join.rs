@@ -0,0 +1,108 @@
+use tegmine_common::prelude::*;
+use tegmine_common::{TaskType, TaskUtils};
+
+use super::workflow_system_task::WorkflowSystemTask;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+
+/// The EXCLUSIVE_JOIN task joins the branches of a SWITCH task, of which only one is executed. It
+/// completes with the output of the first executed task of `exclusive_join_on`, or of the
+/// `default_exclusive_join_task` when none of them has been executed.
+pub struct ExclusiveJoin;
+
+impl ExclusiveJoin {
+    const EXCLUSIVE_JOIN_ON: &'static str = "exclusiveJoinOn";
+    const DEFAULT_EXCLUSIVE_JOIN_TASK: &'static str = "defaultExclusiveJoinTask";
+
+    /// Gets the reference names in the input `key`, the tasks of a loop iteration have the
+    /// iteration appended to their reference names.
+    fn get_task_refs(task: &TaskModel, key: &str) -> Vec<InlineStr> {
+        let task_refs = match task.input_data.get(key) {
+            Some(Object::List(task_refs)) => task_refs,
+            _ => return Vec::default(),
+        };
+        task_refs
+            .iter()
+            .filter_map(|x| x.as_string().ok())
+            .map(|x| {
+                let mut task_ref = x.clone();
+                if task.iteration > 0 {
+                    TaskUtils::append_iteration(&mut task_ref, task.iteration);
+                }
+                task_ref
+            })
+            .collect()
+    }
+}
+
+impl WorkflowSystemTask for ExclusiveJoin {
+    fn get_task_type(&self) -> &str {
+        TaskType::ExclusiveJoin.as_ref()
+    }
+
+    fn execute(&self, workflow: &mut WorkflowModel, task: &mut TaskModel) -> bool {
+        let mut found_exclusive_join_on_task = false;
+        let mut has_failures = false;
+        let mut failure_reason = String::new();
+        let mut exclusive_task = None;
+
+        for join_on_ref in Self::get_task_refs(task, Self::EXCLUSIVE_JOIN_ON) {
+            match workflow.get_task_by_ref_name(&join_on_ref) {
+                Ok(Some(joined_task)) if joined_task.status != TaskStatus::Skipped => {
+                    found_exclusive_join_on_task = joined_task.status.is_terminal();
+                    has_failures = !joined_task.status.is_successful();
+                    if has_failures {
+                        failure_reason.push_str(&joined_task.reason_for_incompletion);
+                        failure_reason.push(' ');
+                    }
+                    exclusive_task = Some(joined_task);
+                    break;
+                }
+                _ => continue,
+            }
+        }
+
+        if exclusive_task.is_none() {
+            let default_exclusive_join_tasks =
+                Self::get_task_refs(task, Self::DEFAULT_EXCLUSIVE_JOIN_TASK);
+            if default_exclusive_join_tasks.is_empty() {
+                debug!(
+                    "Default exclusive join task is not set for task: {}",
+                    task.task_id
+                );
+            }
+            for default_exclusive_join_task in default_exclusive_join_tasks {
+                match workflow.get_task_by_ref_name(&default_exclusive_join_task) {
+                    Ok(Some(joined_task)) => {
+                        found_exclusive_join_on_task = joined_task.status.is_terminal();
+                        has_failures = !joined_task.status.is_successful();
+                        if has_failures {
+                            failure_reason.push_str(&joined_task.reason_for_incompletion);
+                            failure_reason.push(' ');
+                        }
+                        exclusive_task = Some(joined_task);
+                    }
+                    _ => {
+                        debug!(
+                            "Could not find the default exclusive join task: {}",
+                            default_exclusive_join_task
+                        );
+                    }
+                }
+            }
+        }
+
+        if found_exclusive_join_on_task || has_failures {
+            if has_failures {
+                task.reason_for_incompletion = failure_reason.trim_end().into();
+                task.status = TaskStatus::Failed;
+            } else {
+                task.output_data = exclusive_task
+                    .map(|x| x.output_data.clone())
+                    .unwrap_or_default();
+                task.status = TaskStatus::Completed;
+            }
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,13 @@
+use tegmine_common::TaskType;
+
+use super::workflow_system_task::WorkflowSystemTask;
+
+/// The FORK task is created completed by the FORK_JOIN and FORK_JOIN_DYNAMIC mappers, the forked
+/// tasks are waited for by the JOIN task following it.
+pub struct Fork;
+
+impl WorkflowSystemTask for Fork {
+    fn get_task_type(&self) -> &str {
+        TaskType::TASK_TYPE_FORK
+    }
+}
@@ -0,0 +1,293 @@
+use std::time::Duration;
+
+use hyper::client::HttpConnector;
+use hyper::header::{ACCEPT, CONTENT_TYPE};
+use hyper::{Body, Client, Method, Request};
+use once_cell::sync::Lazy;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+use tokio::runtime::Runtime;
+
+use super::workflow_system_task::WorkflowSystemTask;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::utils::RuntimeUtils;
+
+/// The system task workers are plain threads, so every HTTP call is executed on a small dedicated
+/// tokio runtime.
+static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("tegmine-http")
+        .enable_all()
+        .build()
+        .expect("create tokio runtime for the HTTP task failed")
+});
+
+/// The HTTP task calls the service described by the `http_request` input, the response is kept
+/// in the `response` output. The task completes when the response status code is one of the
+/// accepted status codes, a 2xx one by default. It fails when the service can not be reached or
+/// answers with an unexpected status code, a 4xx response can not be retried.
+pub struct Http;
+
+impl WorkflowSystemTask for Http {
+    fn get_task_type(&self) -> &str {
+        TaskType::Http.as_ref()
+    }
+
+    fn start(&self, _workflow: &WorkflowModel, task: &mut TaskModel) -> TegResult<()> {
+        let request = match HttpRequest::try_from_task(task) {
+            Ok(request) => request,
+            Err(e) => {
+                task.reason_for_incompletion = e.message().into();
+                task.status = TaskStatus::Failed;
+                return Ok(());
+            }
+        };
+
+        match request.call() {
+            Ok(response) => {
+                if request.accepts(response.status_code) {
+                    task.status = if self.is_async_complete(task) {
+                        TaskStatus::InProgress
+                    } else {
+                        TaskStatus::Completed
+                    };
+                } else {
+                    task.reason_for_incompletion = match &response.body {
+                        Object::Null => "No response from the remote service".into(),
+                        Object::String(body) => body.clone(),
+                        body => body.to_json().to_string().into(),
+                    };
+                    // a client error will not be fixed by calling the service again
+                    task.status = if (400..500).contains(&response.status_code) {
+                        TaskStatus::FailedWithTerminalError
+                    } else {
+                        TaskStatus::Failed
+                    };
+                }
+                task.output_data
+                    .insert(Self::RESPONSE.into(), response.into_object());
+            }
+            Err(e) => {
+                error!(
+                    "Failed to invoke HTTP task: {} - uri: {}, error: {}",
+                    task.task_id, request.uri, e
+                );
+                task.reason_for_incompletion =
+                    format!("Failed to invoke HTTP task due to: {}", e.message()).into();
+                task.status = TaskStatus::Failed;
+                task.output_data
+                    .insert(Self::RESPONSE.into(), e.message().into());
+            }
+        }
+        Ok(())
+    }
+
+    fn is_async(&self) -> bool {
+        true
+    }
+}
+
+impl Http {
+    const REQUEST_PARAMETER: &'static str = "http_request";
+    const RESPONSE: &'static str = "response";
+}
+
+/// The `http_request` input of the HTTP task.
+struct HttpRequest {
+    uri: InlineStr,
+    method: Method,
+    headers: HashMap<InlineStr, Object>,
+    body: Object,
+    accept: InlineStr,
+    content_type: InlineStr,
+    /// Connection timeout in milliseconds
+    connection_time_out: u64,
+    /// Read timeout in milliseconds, from sending the request until the whole body of the
+    /// response is received
+    read_time_out: u64,
+    /// The status codes the task completes with, any 2xx one if empty
+    accepted_status_codes: Vec<u16>,
+}
+
+impl HttpRequest {
+    const DEFAULT_CONTENT_TYPE: &'static str = "application/json";
+    const DEFAULT_CONNECTION_TIME_OUT: u64 = 100;
+    const DEFAULT_READ_TIME_OUT: u64 = 150;
+
+    fn try_from_task(task: &TaskModel) -> TegResult<Self> {
+        let request = match task.input_data.get(Http::REQUEST_PARAMETER) {
+            Some(Object::Map(request)) => request,
+            _ => {
+                return fmt_err!(
+                    IllegalArgument,
+                    "Missing HTTP request. Task input MUST have a '{}' key with the HTTP request as value",
+                    Http::REQUEST_PARAMETER
+                );
+            }
+        };
+
+        let uri = match request.get("uri") {
+            Some(Object::String(uri)) if !uri.trim().is_empty() => uri.trim().into(),
+            _ => return str_err!(IllegalArgument, "Missing HTTP URI in the HTTP request"),
+        };
+        let method = match request.get("method") {
+            Some(Object::String(method)) => {
+                Method::from_bytes(method.trim().to_uppercase().as_bytes()).map_err(|_| {
+                    ErrorCode::IllegalArgument(format!("Invalid HTTP method: {}", method))
+                })?
+            }
+            _ => return str_err!(IllegalArgument, "No HTTP method specified"),
+        };
+        let headers = match request.get("headers") {
+            Some(Object::Map(headers)) => headers.clone(),
+            Some(Object::Null) | None => HashMap::default(),
+            Some(_) => return str_err!(IllegalArgument, "HTTP headers is not a map"),
+        };
+        let accepted_status_codes = match request.get("acceptedStatusCodes") {
+            Some(Object::List(codes)) => codes
+                .iter()
+                .map(|x| {
+                    Self::get_positive_int(x)
+                        .map(|x| x as u16)
+                        .ok_or_else(|| ErrorCode::IllegalArgument("Invalid accepted status code"))
+                })
+                .collect::<TegResult<Vec<_>>>()?,
+            Some(Object::Null) | None => Vec::default(),
+            Some(_) => return str_err!(IllegalArgument, "HTTP acceptedStatusCodes is not a list"),
+        };
+
+        Ok(Self {
+            uri,
+            method,
+            headers,
+            body: request.get("body").cloned().unwrap_or(Object::Null),
+            accept: Self::get_str(request, "accept", Self::DEFAULT_CONTENT_TYPE),
+            content_type: Self::get_str(request, "contentType", Self::DEFAULT_CONTENT_TYPE),
+            connection_time_out: request
+                .get("connectionTimeOut")
+                .and_then(Self::get_positive_int)
+                .unwrap_or(Self::DEFAULT_CONNECTION_TIME_OUT),
+            read_time_out: request
+                .get("readTimeOut")
+                .and_then(Self::get_positive_int)
+                .unwrap_or(Self::DEFAULT_READ_TIME_OUT),
+            accepted_status_codes,
+        })
+    }
+
+    fn get_str(request: &HashMap<InlineStr, Object>, key: &str, default: &str) -> InlineStr {
+        match request.get(key) {
+            Some(Object::String(value)) if !value.trim().is_empty() => value.trim().into(),
+            _ => default.into(),
+        }
+    }
+
+    fn get_positive_int(value: &Object) -> Option<u64> {
+        match value {
+            Object::Int(n) if *n > 0 => Some(*n as u64),
+            Object::Long(n) if *n > 0 => Some(*n as u64),
+            _ => None,
+        }
+    }
+
+    fn accepts(&self, status_code: u16) -> bool {
+        if self.accepted_status_codes.is_empty() {
+            (200..300).contains(&status_code)
+        } else {
+            self.accepted_status_codes.contains(&status_code)
+        }
+    }
+
+    fn call(&self) -> TegResult<HttpResponse> {
+        let mut builder = Request::builder()
+            .method(self.method.clone())
+            .uri(self.uri.as_str())
+            .header(ACCEPT, self.accept.as_str())
+            .header(CONTENT_TYPE, self.content_type.as_str());
+        for (name, value) in &self.headers {
+            builder = match value {
+                Object::String(value) => builder.header(name.as_str(), value.as_str()),
+                value => builder.header(name.as_str(), value.to_json().to_string()),
+            };
+        }
+        let body = match &self.body {
+            Object::Null => Body::empty(),
+            Object::String(body) => Body::from(body.to_string()),
+            body => Body::from(body.to_json().to_string()),
+        };
+        let request = builder
+            .body(body)
+            .map_err(|e| ErrorCode::IllegalArgument(format!("Invalid HTTP request: {}", e)))?;
+
+        let mut connector = HttpConnector::new();
+        connector.set_connect_timeout(Some(Duration::from_millis(self.connection_time_out)));
+        let client = Client::builder().build::<_, Body>(connector);
+        let read_time_out = Duration::from_millis(self.read_time_out);
+
+        RuntimeUtils::block_on(&RUNTIME, async move {
+            let response = tokio::time::timeout(read_time_out, async {
+                let (parts, body) = client.request(request).await?.into_parts();
+                let body = hyper::body::to_bytes(body).await?;
+                Ok::<_, hyper::Error>((parts, body))
+            })
+            .await
+            .map_err(|_| {
+                ErrorCode::TransientException(format!("Read timed out after {:?}", read_time_out))
+            })?
+            .map_err(|e| ErrorCode::TransientException(e.to_string()))?;
+            Ok::<_, ErrorCode>(HttpResponse::from(response))
+        })
+    }
+}
+
+/// The `response` output of the HTTP task.
+struct HttpResponse {
+    status_code: u16,
+    reason_phrase: InlineStr,
+    headers: HashMap<InlineStr, Object>,
+    body: Object,
+}
+
+impl HttpResponse {
+    fn into_object(self) -> Object {
+        let mut response = HashMap::with_capacity(4);
+        response.insert("statusCode".into(), (self.status_code as i32).into());
+        response.insert("reasonPhrase".into(), self.reason_phrase.into());
+        response.insert("headers".into(), self.headers.into());
+        response.insert("body".into(), self.body);
+        response.into()
+    }
+}
+
+impl From<(hyper::http::response::Parts, hyper::body::Bytes)> for HttpResponse {
+    fn from((parts, body): (hyper::http::response::Parts, hyper::body::Bytes)) -> Self {
+        let mut headers: HashMap<InlineStr, Object> = HashMap::default();
+        for (name, value) in parts.headers.iter() {
+            let value = Object::from(String::from_utf8_lossy(value.as_bytes()).as_ref());
+            match headers.get_mut(name.as_str()) {
+                Some(Object::List(values)) => values.push(value),
+                _ => {
+                    headers.insert(name.as_str().into(), vec![value].into());
+                }
+            }
+        }
+
+        // the body is kept as json when it can be parsed, as text otherwise
+        let body = if body.is_empty() {
+            Object::Null
+        } else {
+            match serde_json::from_slice::<serde_json::Value>(&body) {
+                Ok(json) => Object::from_json(&json),
+                Err(_) => String::from_utf8_lossy(&body).as_ref().into(),
+            }
+        };
+
+        Self {
+            status_code: parts.status.as_u16(),
+            reason_phrase: parts.status.canonical_reason().unwrap_or_default().into(),
+            headers,
+            body,
+        }
+    }
+}
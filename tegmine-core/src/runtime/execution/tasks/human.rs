@@ -0,0 +1,88 @@
+use chrono::Utc;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::workflow_system_task::WorkflowSystemTask;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::runtime::execution::WorkflowSweeper;
+
+/// The HUMAN task stays in progress until a user completes it through `TaskService`. The task is
+/// offered to the `assignee` input, or to the members of the `candidateGroups` input when it has
+/// no assignee, one of them claims the task before completing it. When the optional `expiry` of
+/// the task elapses first, the task completes with the `timedOut` output set, so that the
+/// workflow can branch on it.
+pub struct Human;
+
+impl Human {
+    pub const ASSIGNEE: &'static str = "assignee";
+    pub const CANDIDATE_GROUPS: &'static str = "candidateGroups";
+    pub const CLAIMED_BY: &'static str = "claimedBy";
+    pub const TIMED_OUT: &'static str = "timedOut";
+
+    fn get_input_str<'a>(task: &'a TaskModel, key: &str) -> Option<&'a InlineStr> {
+        task.input_data
+            .get(key)
+            .and_then(|x| x.as_string().ok())
+            .filter(|x| !x.trim().is_empty())
+    }
+
+    pub fn get_claimed_by(task: &TaskModel) -> Option<&InlineStr> {
+        task.output_data
+            .get(Self::CLAIMED_BY)
+            .and_then(|x| x.as_string().ok())
+    }
+
+    fn get_candidate_groups(task: &TaskModel) -> impl Iterator<Item = &InlineStr> {
+        let candidate_groups: &[Object] = match task.input_data.get(Self::CANDIDATE_GROUPS) {
+            Some(Object::List(candidate_groups)) => candidate_groups.as_slice(),
+            _ => &[],
+        };
+        candidate_groups.iter().filter_map(|x| x.as_string().ok())
+    }
+
+    /// Whether the task is offered to an assignee or to some candidate groups.
+    pub fn has_candidates(task: &TaskModel) -> bool {
+        Self::get_input_str(task, Self::ASSIGNEE).is_some()
+            || Self::get_candidate_groups(task).next().is_some()
+    }
+
+    /// Whether the user, a member of the groups, may claim the task or has claimed it.
+    pub fn is_candidate(task: &TaskModel, user: &str, groups: &[&str]) -> bool {
+        if let Some(claimed_by) = Self::get_claimed_by(task) {
+            return claimed_by.eq(user);
+        }
+        if let Some(assignee) = Self::get_input_str(task, Self::ASSIGNEE) {
+            return assignee.eq(user);
+        }
+        Self::get_candidate_groups(task).any(|x| groups.contains(&x.as_str()))
+    }
+}
+
+impl WorkflowSystemTask for Human {
+    fn get_task_type(&self) -> &str {
+        TaskType::Human.as_ref()
+    }
+
+    fn execute(&self, workflow: &mut WorkflowModel, task: &mut TaskModel) -> bool {
+        // no expiry, waits to be completed by a user
+        if task.wait_timeout == 0 {
+            return false;
+        }
+
+        if Utc::now().timestamp_millis() >= task.wait_timeout {
+            task.output_data
+                .insert(Self::TIMED_OUT.into(), Object::Boolean(true));
+            task.reason_for_incompletion = "Human task expired".into();
+            task.status = TaskStatus::Completed;
+            return true;
+        }
+
+        if let Err(e) = WorkflowSweeper::requeue(workflow) {
+            error!(
+                "Error requeueing workflow: {} of HUMAN task: {}, error: {}",
+                workflow.workflow_id, task.task_id, e
+            );
+        }
+        false
+    }
+}
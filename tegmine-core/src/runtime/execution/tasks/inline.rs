@@ -0,0 +1,74 @@
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::workflow_system_task::WorkflowSystemTask;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::runtime::execution::evaluators::EvaluatorRegistry;
+
+/// The INLINE task evaluates the `expression` input with the evaluator of the `evaluatorType`
+/// input, the whole input of the task is bound as `$`. The value is kept in the `result` output,
+/// an evaluation error fails the task with the error message.
+pub struct Inline;
+
+impl Inline {
+    const QUERY_EVALUATOR_TYPE: &'static str = "evaluatorType";
+    const QUERY_EXPRESSION: &'static str = "expression";
+    const RESULT: &'static str = "result";
+
+    fn evaluate(task: &TaskModel) -> TegResult<Object> {
+        let evaluator_type = match task.input_data.get(Self::QUERY_EVALUATOR_TYPE) {
+            Some(Object::String(evaluator_type)) if !evaluator_type.trim().is_empty() => {
+                evaluator_type
+            }
+            _ => {
+                return fmt_err!(
+                    IllegalArgument,
+                    "Empty '{}' in INLINE task's input parameters. A non-empty String value must be provided.",
+                    Self::QUERY_EVALUATOR_TYPE
+                );
+            }
+        };
+        let expression = match task.input_data.get(Self::QUERY_EXPRESSION) {
+            Some(Object::String(expression)) if !expression.trim().is_empty() => expression,
+            _ => {
+                return fmt_err!(
+                    IllegalArgument,
+                    "Empty '{}' in INLINE task's input parameters. A non-empty String value must be provided.",
+                    Self::QUERY_EXPRESSION
+                );
+            }
+        };
+
+        let evaluator = EvaluatorRegistry::get_evaluator(evaluator_type)?.ok_or_else(|| {
+            ErrorCode::IllegalArgument(format!(
+                "Unknown evaluator '{}' in INLINE task",
+                evaluator_type
+            ))
+        })?;
+        evaluator.evaluate(expression, &Object::from(task.input_data.clone()))
+    }
+}
+
+impl WorkflowSystemTask for Inline {
+    fn get_task_type(&self) -> &str {
+        TaskType::Inline.as_ref()
+    }
+
+    fn execute(&self, workflow: &mut WorkflowModel, task: &mut TaskModel) -> bool {
+        match Self::evaluate(task) {
+            Ok(result) => {
+                task.output_data.insert(Self::RESULT.into(), result);
+                task.status = TaskStatus::Completed;
+            }
+            Err(e) => {
+                error!(
+                    "Failed to execute INLINE task: {} in workflow: {}, error: {}",
+                    task.task_id, workflow.workflow_id, e
+                );
+                task.reason_for_incompletion = e.message().into();
+                task.status = TaskStatus::Failed;
+            }
+        }
+        true
+    }
+}
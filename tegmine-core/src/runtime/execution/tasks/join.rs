@@ -0,0 +1,223 @@
+use std::str::FromStr;
+
+use tegmine_common::prelude::*;
+use tegmine_common::{JoinMode, TaskType, TaskUtils, WorkflowTask};
+
+use super::workflow_system_task::WorkflowSystemTask;
+use super::SystemTaskRegistry;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::runtime::ExecutionDaoFacade;
+
+/// The JOIN task waits for the forked tasks listed in its `join_on` input, the output of every
+/// forked task is collected in the output of the JOIN keyed by its reference name. Depending on
+/// the `JoinMode`, the JOIN may be satisfied before all the forked tasks complete, the remaining
+/// branches are then canceled.
+pub struct Join;
+
+impl Join {
+    const JOIN_ON: &'static str = "join_on";
+    const JOIN_MODE: &'static str = "join_mode";
+    const JOIN_COUNT: &'static str = "join_count";
+    const JOIN_QUORUM: &'static str = "join_quorum";
+
+    /// Gets the reference names of the joined tasks, the tasks of a loop iteration have the
+    /// iteration appended to their reference names.
+    fn get_join_on(task: &TaskModel) -> Vec<InlineStr> {
+        let join_on = match task.input_data.get(Self::JOIN_ON) {
+            Some(Object::List(join_on)) => join_on,
+            _ => return Vec::default(),
+        };
+        join_on
+            .iter()
+            .filter_map(|x| x.as_string().ok())
+            .map(|x| {
+                let mut join_on_ref = x.clone();
+                if task.iteration > 0 {
+                    TaskUtils::append_iteration(&mut join_on_ref, task.iteration);
+                }
+                join_on_ref
+            })
+            .collect()
+    }
+
+    fn get_input_int(task: &TaskModel, key: &str) -> usize {
+        match task.input_data.get(key) {
+            Some(Object::Int(n)) if *n > 0 => *n as usize,
+            Some(Object::Long(n)) if *n > 0 => *n as usize,
+            _ => 0,
+        }
+    }
+
+    /// Gets the number of joined tasks which have to complete to satisfy the JOIN.
+    fn get_required_count(task: &TaskModel, join_on_count: usize) -> usize {
+        let join_mode = task
+            .input_data
+            .get(Self::JOIN_MODE)
+            .and_then(|x| x.as_string().ok())
+            .and_then(|x| JoinMode::from_str(x).ok())
+            .unwrap_or(JoinMode::All);
+        let required_count = match join_mode {
+            JoinMode::All => join_on_count,
+            JoinMode::Any => 1,
+            JoinMode::FirstN => Self::get_input_int(task, Self::JOIN_COUNT).max(1),
+            JoinMode::Quorum => {
+                let join_quorum = Self::get_input_int(task, Self::JOIN_QUORUM).min(100);
+                ((join_on_count * join_quorum + 99) / 100).max(1)
+            }
+        };
+        required_count.min(join_on_count)
+    }
+
+    /// Gets the reference names of the tasks of the branch the joined task belongs to, the
+    /// branch is only the joined task itself for a FORK_JOIN_DYNAMIC task.
+    fn get_branch_task_refs(workflow: &WorkflowModel, join_on_ref: &str) -> Vec<InlineStr> {
+        for workflow_task in workflow.workflow_definition.collect_tasks() {
+            if !workflow_task.type_.eq(TaskType::ForkJoin.as_ref()) {
+                continue;
+            }
+            if let Some(branch) = workflow_task
+                .fork_tasks
+                .iter()
+                .find(|x| x.iter().any(|x| x.has(join_on_ref)))
+            {
+                return branch
+                    .iter()
+                    .flat_map(WorkflowTask::collect_tasks)
+                    .map(|x| x.task_reference_name.clone())
+                    .collect();
+            }
+        }
+        vec![join_on_ref.into()]
+    }
+
+    /// Cancels the non terminal tasks of the branches of the joined tasks which haven't completed
+    /// when the JOIN is satisfied. The canceled tasks are marked executed, so that the decider
+    /// neither retries them nor schedules the tasks following them.
+    fn cancel_remaining_branches(
+        workflow: &mut WorkflowModel,
+        join_task: &TaskModel,
+        remaining_join_on: &[InlineStr],
+    ) {
+        let mut branch_task_refs = Vec::default();
+        for join_on_ref in remaining_join_on {
+            branch_task_refs.extend(Self::get_branch_task_refs(
+                workflow,
+                TaskUtils::remove_iteration_from_task_ref_name(join_on_ref),
+            ));
+        }
+
+        let workflow_ptr = addr_of_mut!(workflow);
+        for task in workflow.tasks.iter_mut() {
+            if task.status.is_terminal()
+                || task.iteration != join_task.iteration
+                || !branch_task_refs.iter().any(|x| {
+                    x.eq(TaskUtils::remove_iteration_from_task_ref_name(
+                        &task.reference_task_name,
+                    ))
+                })
+            {
+                continue;
+            }
+
+            match SystemTaskRegistry::is_system_task(&task.task_type) {
+                Ok(true) => match SystemTaskRegistry::get(&task.task_type) {
+                    Ok(workflow_system_task) => {
+                        if let Err(e) =
+                            workflow_system_task.cancel(from_addr_mut!(workflow_ptr), task)
+                        {
+                            error!(
+                                "Error canceling system task:{}/{} in workflow: {}, error: {}",
+                                workflow_system_task.get_task_type(),
+                                task.task_id,
+                                workflow.workflow_id,
+                                e
+                            );
+                        }
+                    }
+                    Err(e) => error!("{}", e),
+                },
+                Ok(false) => {}
+                Err(e) => error!("{}", e),
+            }
+            task.status = TaskStatus::Canceled;
+            task.executed = true;
+            task.reason_for_incompletion = format!(
+                "Canceled as JOIN {} is satisfied",
+                join_task.reference_task_name
+            )
+            .into();
+            if let Err(e) = ExecutionDaoFacade::update_task(task) {
+                error!(
+                    "Error updating canceled task: {} in workflow: {}, error: {}",
+                    task.task_id, workflow.workflow_id, e
+                );
+            }
+        }
+    }
+}
+
+impl WorkflowSystemTask for Join {
+    fn get_task_type(&self) -> &str {
+        TaskType::Join.as_ref()
+    }
+
+    fn execute(&self, workflow: &mut WorkflowModel, task: &mut TaskModel) -> bool {
+        let join_on = Self::get_join_on(task);
+        let required_count = Self::get_required_count(task, join_on.len());
+
+        let mut completed_count = 0;
+        let mut failed_count = 0;
+        let mut failure_reason = String::new();
+        let mut remaining_join_on = Vec::default();
+        for join_on_ref in join_on.iter() {
+            let forked_task = match workflow.get_task_by_ref_name(join_on_ref) {
+                Ok(Some(forked_task)) => forked_task,
+                _ => {
+                    // Task is not even scheduled yet
+                    remaining_join_on.push(join_on_ref.clone());
+                    continue;
+                }
+            };
+
+            task.output_data
+                .insert(join_on_ref.clone(), forked_task.output_data.clone().into());
+            if !forked_task.status.is_terminal() {
+                remaining_join_on.push(join_on_ref.clone());
+                continue;
+            }
+
+            let optional = forked_task
+                .workflow_task
+                .as_ref()
+                .map(|x| x.optional)
+                .unwrap_or(false);
+            if forked_task.status.is_successful() || optional {
+                completed_count += 1;
+            } else {
+                failed_count += 1;
+                failure_reason.push_str(&forked_task.reason_for_incompletion);
+                failure_reason.push(' ');
+            }
+        }
+
+        // The JOIN fails as soon as the required number of tasks can no longer complete
+        if failed_count > join_on.len() - required_count {
+            task.reason_for_incompletion = failure_reason.trim_end().into();
+            task.status = TaskStatus::Failed;
+            return true;
+        }
+
+        if completed_count >= required_count {
+            if !remaining_join_on.is_empty() {
+                debug!(
+                    "JOIN {} is satisfied, canceling the remaining tasks {:?}",
+                    task.reference_task_name, remaining_join_on
+                );
+                Self::cancel_remaining_branches(workflow, task, &remaining_join_on);
+            }
+            task.status = TaskStatus::Completed;
+            return true;
+        }
+        false
+    }
+}
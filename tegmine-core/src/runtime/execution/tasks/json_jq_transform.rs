@@ -0,0 +1,162 @@
+use jaq_interpret::{Ctx, Filter, FilterT, ParseCtx, RcIter, Val};
+use linked_hash_map::LinkedHashMap;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::workflow_system_task::WorkflowSystemTask;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+
+/// The maximum number of compiled queries kept by a thread.
+const QUERY_CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    /// The compiled queries keyed by their expressions, least recently used first, a `Filter` is
+    /// not shared across threads.
+    static QUERY_CACHE: RefCell<LinkedHashMap<InlineStr, Filter>> = RefCell::default();
+}
+
+/// The JSON_JQ_TRANSFORM task applies the jq `queryExpression` input to the whole input of the
+/// task. The first value produced by the query is kept in the `result` output and all of them in
+/// the `resultList` output. An invalid query fails the task when it is started.
+pub struct JsonJqTransform;
+
+impl JsonJqTransform {
+    const QUERY_EXPRESSION_PARAMETER: &'static str = "queryExpression";
+    const OUTPUT_RESULT: &'static str = "result";
+    const OUTPUT_RESULT_LIST: &'static str = "resultList";
+    const OUTPUT_ERROR: &'static str = "error";
+
+    fn get_query_expression(task: &TaskModel) -> TegResult<&InlineStr> {
+        match task.input_data.get(Self::QUERY_EXPRESSION_PARAMETER) {
+            Some(Object::String(query_expression)) if !query_expression.trim().is_empty() => {
+                Ok(query_expression)
+            }
+            _ => fmt_err!(
+                IllegalArgument,
+                "Missing '{}' in JSON_JQ_TRANSFORM task's input parameters",
+                Self::QUERY_EXPRESSION_PARAMETER
+            ),
+        }
+    }
+
+    fn compile(query_expression: &str) -> TegResult<Filter> {
+        let mut defs = ParseCtx::new(Vec::new());
+        defs.insert_natives(jaq_core::core());
+        defs.insert_defs(jaq_std::std());
+
+        let (filter, errs) = jaq_parse::parse(query_expression, jaq_parse::main());
+        if !errs.is_empty() {
+            return fmt_err!(
+                IllegalArgument,
+                "Invalid queryExpression: {}, error: {}",
+                query_expression,
+                errs.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        let filter = match filter {
+            Some(filter) => defs.compile(filter),
+            None => return fmt_err!(IllegalArgument, "Empty queryExpression"),
+        };
+        if !defs.errs.is_empty() {
+            return fmt_err!(
+                IllegalArgument,
+                "Invalid queryExpression: {}, error: {:?}",
+                query_expression,
+                defs.errs
+            );
+        }
+        Ok(filter)
+    }
+
+    /// Compiles the query expression of the task, unless it has been recently compiled by this
+    /// thread. The least recently used query is evicted once the cache is full.
+    fn compile_cached(query_expression: &InlineStr) -> TegResult<()> {
+        QUERY_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.get_refresh(query_expression).is_none() {
+                let filter = Self::compile(query_expression)?;
+                cache.insert(query_expression.clone(), filter);
+                if cache.len() > QUERY_CACHE_CAPACITY {
+                    cache.pop_front();
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn transform(task: &TaskModel) -> TegResult<Vec<serde_json::Value>> {
+        let query_expression = Self::get_query_expression(task)?;
+        Self::compile_cached(query_expression)?;
+
+        let input = Object::from(task.input_data.clone()).to_json();
+        QUERY_CACHE.with(|cache| {
+            let cache = cache.borrow();
+            let filter = cache.get(query_expression).expect("already compiled");
+            let inputs = RcIter::new(core::iter::empty());
+            filter
+                .run((Ctx::new([], &inputs), Val::from(input)))
+                .map(|x| {
+                    x.map(serde_json::Value::from).map_err(|e| {
+                        ErrorCode::IllegalArgument(format!(
+                            "Error executing queryExpression: {}, error: {}",
+                            query_expression, e
+                        ))
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn mark_failed(task: &mut TaskModel, e: ErrorCode) {
+        error!(
+            "Error executing task: {} in workflow: {}, error: {}",
+            task.task_id, task.workflow_instance_id, e
+        );
+        task.reason_for_incompletion = e.message().into();
+        task.status = TaskStatus::Failed;
+        task.output_data
+            .insert(Self::OUTPUT_ERROR.into(), e.message().into());
+    }
+}
+
+impl WorkflowSystemTask for JsonJqTransform {
+    fn get_task_type(&self) -> &str {
+        TaskType::JsonJqTransform.as_ref()
+    }
+
+    fn start(&self, _workflow: &WorkflowModel, task: &mut TaskModel) -> TegResult<()> {
+        if let Err(e) = Self::get_query_expression(task).and_then(Self::compile_cached) {
+            Self::mark_failed(task, e);
+        }
+        Ok(())
+    }
+
+    fn execute(&self, _workflow: &mut WorkflowModel, task: &mut TaskModel) -> bool {
+        match Self::transform(task) {
+            Ok(result_list) => {
+                let result = result_list
+                    .first()
+                    .map(Object::from_json)
+                    .unwrap_or(Object::Null);
+                let result_list = if result_list.is_empty() {
+                    Object::Null
+                } else {
+                    result_list
+                        .iter()
+                        .map(Object::from_json)
+                        .collect::<Vec<_>>()
+                        .into()
+                };
+                task.output_data.insert(Self::OUTPUT_RESULT.into(), result);
+                task.output_data
+                    .insert(Self::OUTPUT_RESULT_LIST.into(), result_list);
+                task.status = TaskStatus::Completed;
+            }
+            Err(e) => Self::mark_failed(task, e),
+        }
+        true
+    }
+}
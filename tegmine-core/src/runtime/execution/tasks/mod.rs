@@ -1,7 +1,11 @@
 mod do_while;
 mod exclusive_join;
 mod fork;
+mod http;
+mod human;
+mod inline;
 mod join;
+mod json_jq_transform;
 mod set_variable;
 mod start_workflow;
 mod sub_workflow;
@@ -10,9 +14,12 @@ mod system_task_registry;
 mod system_task_worker;
 mod system_task_worker_coordinator;
 mod terminate;
+mod wait;
 mod workflow_system_task;
 
+pub use human::Human;
 pub use system_task_registry::SystemTaskRegistry;
+pub use system_task_worker::SystemTaskWorker;
 pub use system_task_worker_coordinator::SystemTaskWorkerCoordinator;
 pub use terminate::Terminate;
 pub use workflow_system_task::WorkflowSystemTask;
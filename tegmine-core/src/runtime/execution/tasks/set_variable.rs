@@ -11,16 +11,16 @@ use crate::runtime::ExecutionDaoFacade;
 pub struct SetVariable;
 
 impl SetVariable {
-    fn validate_variables_size(workflow: &WorkflowModel) -> Option<String> {
-        let max_threshold = Properties::default().max_workflow_variables_payload_size_threshold;
+    fn validate_variables_size(workflow: &WorkflowModel) -> TegResult<()> {
+        let max_threshold = Properties::get()?.max_workflow_variables_payload_size_threshold;
 
         let payload_size = Object::estimate_map_memory_used(&workflow.variables);
         if payload_size > max_threshold * 1024 {
             let error_msg = format!("The variables payload size: {} of workflow: {} is greater than the permissible limit: {} bytes", payload_size,workflow.workflow_id,max_threshold);
             error!("{}", error_msg);
-            Some(error_msg)
+            Err(ErrorCode::IllegalArgument(error_msg))
         } else {
-            None
+            Ok(())
         }
     }
 }
@@ -44,8 +44,8 @@ impl WorkflowSystemTask for SetVariable {
                 workflow.variables.insert(k.clone(), v.clone());
                 debug!("Task: {} setting value for variable: {}", task.task_id, k);
             });
-            if let Some(error_msg) = Self::validate_variables_size(&workflow) {
-                task.reason_for_incompletion = error_msg.into();
+            if let Err(e) = Self::validate_variables_size(&workflow) {
+                task.reason_for_incompletion = e.message().into();
 
                 // restore previous variables
                 previous_values.into_iter().for_each(|(k, v)| {
@@ -59,8 +59,16 @@ impl WorkflowSystemTask for SetVariable {
             }
         }
 
+        if let Err(e) = ExecutionDaoFacade::update_workflow(workflow) {
+            error!(
+                "Error updating variables of workflow: {}, error: {}",
+                workflow.workflow_id, e
+            );
+            task.reason_for_incompletion = e.message().into();
+            task.status = TaskStatus::Failed;
+            return true;
+        }
         task.status = TaskStatus::Completed;
-        ExecutionDaoFacade::update_workflow(workflow);
         true
     }
 }
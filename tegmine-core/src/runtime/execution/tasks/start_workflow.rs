@@ -1,8 +1,9 @@
 use tegmine_common::prelude::*;
-use tegmine_common::TaskType;
+use tegmine_common::{StartWorkflowRequest, TaskType};
 
 use super::workflow_system_task::WorkflowSystemTask;
-use crate::model::{TaskModel, WorkflowModel};
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::runtime::{StartWorkflowInput, StartWorkflowOperation};
 
 /// The START_WORKFLOW task starts another workflow. Unlike SUB_WORKFLOW, START_WORKFLOW does not
 /// create a relationship between starter and the started workflow. It also does not wait for the
@@ -17,11 +18,91 @@ impl WorkflowSystemTask for StartWorkflow {
         TaskType::StartWorkflow.as_ref()
     }
 
-    fn start(&self, _workflow: &WorkflowModel, _task: &mut TaskModel) -> TegResult<()> {
-        todo!()
+    fn start(&self, workflow: &WorkflowModel, task: &mut TaskModel) -> TegResult<()> {
+        let mut request = match Self::get_request(task) {
+            Ok(request) => request,
+            Err(e) => {
+                task.status = TaskStatus::Failed;
+                task.reason_for_incompletion = e.message().into();
+                return Ok(());
+            }
+        };
+
+        // the started workflow inherits the domain mappings and the correlation id of the
+        // starter workflow, unless set in the request
+        if request.task_to_domain.is_empty() {
+            request.task_to_domain = workflow.task_to_domain.clone();
+        }
+        if request.correlation_id.trim().is_empty() {
+            request.correlation_id = workflow.correlation_id.clone();
+        }
+
+        let name = request.name.clone();
+        let mut start_workflow_input = StartWorkflowInput::from(request);
+        start_workflow_input.triggering_workflow_id = workflow.workflow_id.clone();
+        match StartWorkflowOperation::execute(start_workflow_input) {
+            Ok(workflow_id) => {
+                task.output_data
+                    .insert(Self::WORKFLOW_ID.into(), workflow_id.into());
+                task.status = TaskStatus::Completed;
+            }
+            Err(e) if e.code() == ErrorCode::transient_exception_code() => {
+                info!(
+                    "A transient backend error happened when task {} in {} tried to start workflow {}, error: {}",
+                    task.task_id, workflow.to_short_string(), name, e
+                );
+            }
+            Err(e) => {
+                task.status = TaskStatus::Failed;
+                task.reason_for_incompletion =
+                    format!("Error starting workflow {}: {}", name, e.message()).into();
+                error!(
+                    "Error starting workflow {} from workflow {}, error: {}",
+                    name,
+                    workflow.to_short_string(),
+                    e
+                );
+            }
+        }
+        Ok(())
     }
 
     fn is_async(&self) -> bool {
         true
     }
 }
+
+impl StartWorkflow {
+    const START_WORKFLOW_PARAMETER: &'static str = "startWorkflow";
+    const WORKFLOW_ID: &'static str = "workflowId";
+
+    /// Reads the `StartWorkflowRequest` from the `startWorkflow` input of the task.
+    fn get_request(task: &TaskModel) -> TegResult<StartWorkflowRequest> {
+        let request = task
+            .input_data
+            .get(Self::START_WORKFLOW_PARAMETER)
+            .filter(|x| !x.is_null())
+            .ok_or_else(|| {
+                ErrorCode::IllegalArgument(format!(
+                    "Missing '{}' in input data.",
+                    Self::START_WORKFLOW_PARAMETER
+                ))
+            })?;
+
+        let request = StartWorkflowRequest::try_from(request.to_json()).map_err(|e| {
+            ErrorCode::IllegalArgument(format!(
+                "Error reading the '{}' input data: {}",
+                Self::START_WORKFLOW_PARAMETER,
+                e.message()
+            ))
+        })?;
+        if request.name.is_empty() {
+            return fmt_err!(
+                IllegalArgument,
+                "Workflow name is required in '{}'",
+                Self::START_WORKFLOW_PARAMETER
+            );
+        }
+        Ok(request)
+    }
+}
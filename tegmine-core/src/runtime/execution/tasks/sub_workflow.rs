@@ -0,0 +1,192 @@
+use tegmine_common::prelude::*;
+use tegmine_common::{TaskType, WorkflowDef};
+
+use super::workflow_system_task::WorkflowSystemTask;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel, WorkflowStatus};
+use crate::runtime::{
+    ExecutionDaoFacade, StartWorkflowInput, StartWorkflowOperation, WorkflowExecutor,
+};
+
+/// The SUB_WORKFLOW task starts another workflow as the child of the current workflow, and waits
+/// for it to finish. The status and the output of the child are propagated back to the task.
+pub struct SubWorkflow;
+
+impl SubWorkflow {
+    fn start_sub_workflow(workflow: &WorkflowModel, task: &TaskModel) -> TegResult<InlineStr> {
+        let input = &task.input_data;
+        let mut name = input
+            .get("subWorkflowName")
+            .ok_or_else(|| ErrorCode::IllegalArgument("subWorkflowName not found"))?
+            .to_string();
+        let version = match input.get("subWorkflowVersion") {
+            Some(Object::Int(version)) => Some(*version),
+            _ => None,
+        };
+
+        let workflow_definition = match input.get("subWorkflowDefinition") {
+            Some(definition) if !definition.is_null() => {
+                let workflow_def = WorkflowDef::try_from(&definition.to_json())?;
+                name = workflow_def.name.clone();
+                Some(workflow_def)
+            }
+            _ => None,
+        };
+
+        // the sub workflow inherits the domain mappings of the parent, unless overridden
+        let mut task_to_domain = workflow.task_to_domain.clone();
+        if let Some(Object::Map(sub_workflow_task_to_domain)) = input.get("subWorkflowTaskToDomain")
+        {
+            if !sub_workflow_task_to_domain.is_empty() {
+                task_to_domain = sub_workflow_task_to_domain
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.to_string()))
+                    .collect();
+            }
+        }
+
+        let workflow_input = match input.get("workflowInput") {
+            Some(Object::Map(workflow_input)) if !workflow_input.is_empty() => {
+                workflow_input.clone()
+            }
+            _ => input.clone(),
+        };
+
+        let mut start_workflow_input = StartWorkflowInput::new(
+            name,
+            workflow_input,
+            workflow.correlation_id.clone(),
+            task_to_domain,
+            InlineStr::new(),
+            InlineStr::new(),
+        );
+        start_workflow_input.version = version;
+        start_workflow_input.workflow_definition = workflow_definition;
+        start_workflow_input.priority = Some(workflow.priority);
+        start_workflow_input.parent_workflow_id = workflow.workflow_id.clone();
+        start_workflow_input.parent_workflow_task_id = task.task_id.clone();
+        StartWorkflowOperation::execute(start_workflow_input)
+    }
+
+    /// Syncs the status and the output of the task with the sub workflow.
+    fn update_task_status(sub_workflow: &WorkflowModel, task: &mut TaskModel) {
+        let status = sub_workflow.status;
+        task.status = match status {
+            WorkflowStatus::Running | WorkflowStatus::Paused => TaskStatus::InProgress,
+            WorkflowStatus::Completed => TaskStatus::Completed,
+            WorkflowStatus::Failed => TaskStatus::Failed,
+            WorkflowStatus::Terminated => TaskStatus::Canceled,
+            WorkflowStatus::TimedOut => TaskStatus::TimedOut,
+        };
+
+        if !sub_workflow
+            .external_output_payload_storage_path
+            .trim()
+            .is_empty()
+        {
+            task.external_output_payload_storage_path =
+                sub_workflow.external_output_payload_storage_path.clone();
+        } else {
+            task.output_data.extend(sub_workflow.output.clone());
+        }
+
+        if !status.is_successful() {
+            task.reason_for_incompletion = format!(
+                "Sub workflow {} failed with status {} and reason {}",
+                sub_workflow.to_short_string(),
+                status.as_ref(),
+                sub_workflow.reason_for_incompletion
+            )
+            .into();
+        }
+    }
+}
+
+impl WorkflowSystemTask for SubWorkflow {
+    fn get_task_type(&self) -> &str {
+        TaskType::SubWorkflow.as_ref()
+    }
+
+    fn start(&self, workflow: &WorkflowModel, task: &mut TaskModel) -> TegResult<()> {
+        match Self::start_sub_workflow(workflow, task) {
+            Ok(sub_workflow_id) => {
+                task.sub_workflow_id = sub_workflow_id.clone();
+                task.output_data
+                    .insert("subWorkflowId".into(), sub_workflow_id.clone().into());
+
+                // Set task status based on current sub-workflow status, as the status can change
+                // by the time we update here.
+                task.status = TaskStatus::InProgress;
+                if let Ok(sub_workflow) =
+                    ExecutionDaoFacade::get_workflow_model(&sub_workflow_id, false)
+                {
+                    Self::update_task_status(&sub_workflow, task);
+                }
+            }
+            Err(e) => {
+                error!(
+                    "Error starting sub workflow for task: {}, error: {}",
+                    task.task_id, e
+                );
+                task.status = TaskStatus::Failed;
+                task.reason_for_incompletion = e.message().into();
+            }
+        }
+        Ok(())
+    }
+
+    fn execute(&self, _workflow: &mut WorkflowModel, task: &mut TaskModel) -> bool {
+        if task.sub_workflow_id.is_empty() {
+            return false;
+        }
+
+        match ExecutionDaoFacade::get_workflow_model(&task.sub_workflow_id, false) {
+            Ok(sub_workflow) => {
+                if !sub_workflow.status.is_terminal() {
+                    return false;
+                }
+                Self::update_task_status(&sub_workflow, task);
+                true
+            }
+            Err(e) => {
+                error!(
+                    "Error loading sub workflow: {} of task: {}, error: {}",
+                    task.sub_workflow_id, task.task_id, e
+                );
+                false
+            }
+        }
+    }
+
+    fn cancel(&self, workflow: &WorkflowModel, task: &mut TaskModel) -> TegResult<()> {
+        if task.sub_workflow_id.is_empty() {
+            return Ok(());
+        }
+
+        let sub_workflow = ExecutionDaoFacade::get_workflow_model(&task.sub_workflow_id, false)?;
+        if sub_workflow.status.is_terminal() {
+            return Ok(());
+        }
+        let reason = if workflow.reason_for_incompletion.is_empty() {
+            format!(
+                "Parent workflow has been terminated with status {}",
+                workflow.status.as_ref()
+            )
+        } else {
+            format!(
+                "Parent workflow has been terminated with reason: {}",
+                workflow.reason_for_incompletion
+            )
+        };
+        WorkflowExecutor::terminate_workflow(&sub_workflow.workflow_id, reason.into())
+    }
+
+    /// Keep SubWorkflow task asyncComplete. The SubWorkflow task is started once to move to
+    /// InProgress state, and is moved to termination by the sub workflow's completion logic.
+    fn is_async_complete(&self, _task: &TaskModel) -> bool {
+        true
+    }
+
+    fn is_task_retrieval_required(&self) -> bool {
+        false
+    }
+}
@@ -1,64 +1,147 @@
-use dashmap::mapref::one::Ref;
 use dashmap::DashMap;
-use once_cell::sync::Lazy;
 use tegmine_common::prelude::*;
 use tegmine_common::TaskType;
 
+use super::do_while::DoWhile;
+use super::exclusive_join::ExclusiveJoin;
+use super::fork::Fork;
+use super::http::Http;
+use super::human::Human;
+use super::inline::Inline;
+use super::join::Join;
+use super::json_jq_transform::JsonJqTransform;
 use super::set_variable::SetVariable;
 use super::start_workflow::StartWorkflow;
+use super::sub_workflow::SubWorkflow;
 use super::switch::Switch;
 use super::terminate::Terminate;
+use super::wait::Wait;
 use super::workflow_system_task::WorkflowSystemTask;
+use crate::engine::EngineContext;
 
 /// A container class that holds a mapping of system task types `TaskType` to `WorkflowSystemTask`
-/// instances.
-pub struct SystemTaskRegistry;
+/// instances, the built-in system tasks can not be replaced by the registered ones.
+pub struct SystemTaskRegistry {
+    registry: DashMap<InlineStr, Arc<Box<dyn WorkflowSystemTask>>>,
+    custom_registry: DashMap<InlineStr, Arc<Box<dyn WorkflowSystemTask>>>,
+}
 
-static REGISTRY: Lazy<DashMap<InlineStr, Box<dyn WorkflowSystemTask>>> = Lazy::new(|| {
-    let map = DashMap::new();
-    map.insert(
-        TaskType::Switch.as_ref().into(),
-        Box::new(Switch) as Box<dyn WorkflowSystemTask>,
-    );
-    map.insert(
-        TaskType::SetVariable.as_ref().into(),
-        Box::new(SetVariable) as Box<dyn WorkflowSystemTask>,
-    );
-    map.insert(
-        TaskType::Terminate.as_ref().into(),
-        Box::new(Terminate) as Box<dyn WorkflowSystemTask>,
-    );
-    map.insert(
-        TaskType::StartWorkflow.as_ref().into(),
-        Box::new(StartWorkflow) as Box<dyn WorkflowSystemTask>,
-    );
-    map
-});
+impl Default for SystemTaskRegistry {
+    fn default() -> Self {
+        let registry = DashMap::new();
+        registry.insert(
+            TaskType::Switch.as_ref().into(),
+            Arc::new(Box::new(Switch) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::SetVariable.as_ref().into(),
+            Arc::new(Box::new(SetVariable) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::Terminate.as_ref().into(),
+            Arc::new(Box::new(Terminate) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::StartWorkflow.as_ref().into(),
+            Arc::new(Box::new(StartWorkflow) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::SubWorkflow.as_ref().into(),
+            Arc::new(Box::new(SubWorkflow) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::DoWhile.as_ref().into(),
+            Arc::new(Box::new(DoWhile) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::TASK_TYPE_FORK.into(),
+            Arc::new(Box::new(Fork) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::Join.as_ref().into(),
+            Arc::new(Box::new(Join) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::ExclusiveJoin.as_ref().into(),
+            Arc::new(Box::new(ExclusiveJoin) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::Http.as_ref().into(),
+            Arc::new(Box::new(Http) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::Inline.as_ref().into(),
+            Arc::new(Box::new(Inline) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::JsonJqTransform.as_ref().into(),
+            Arc::new(Box::new(JsonJqTransform) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::Wait.as_ref().into(),
+            Arc::new(Box::new(Wait) as Box<dyn WorkflowSystemTask>),
+        );
+        registry.insert(
+            TaskType::Human.as_ref().into(),
+            Arc::new(Box::new(Human) as Box<dyn WorkflowSystemTask>),
+        );
 
-static CUSTOM_REGISTRY: Lazy<DashMap<InlineStr, Box<dyn WorkflowSystemTask>>> =
-    Lazy::new(|| DashMap::new());
+        Self {
+            registry,
+            custom_registry: DashMap::new(),
+        }
+    }
+}
 
 impl SystemTaskRegistry {
-    pub fn get(task_type: &str) -> TegResult<Ref<'static, InlineStr, Box<dyn WorkflowSystemTask>>> {
+    pub fn get(task_type: &str) -> TegResult<Arc<Box<dyn WorkflowSystemTask>>> {
         let task_type = InlineStr::from(task_type);
-        REGISTRY
+        let context = EngineContext::current()?;
+        let registry = &context.system_task_registry;
+        registry
+            .registry
             .get(&task_type)
-            .or(CUSTOM_REGISTRY.get(&task_type))
+            .or(registry.custom_registry.get(&task_type))
+            .map(|x| Arc::clone(x.value()))
             .ok_or_else(|| {
                 ErrorCode::IllegalArgument(format!("{} not found in SystemTaskRegistry", task_type))
             })
     }
 
-    pub fn is_system_task(task_type: &str) -> bool {
+    pub fn is_system_task(task_type: &str) -> TegResult<bool> {
         let task_type = InlineStr::from(task_type);
-        REGISTRY.contains_key(&task_type) || CUSTOM_REGISTRY.contains_key(&task_type)
+        let context = EngineContext::current()?;
+        let registry = &context.system_task_registry;
+        Ok(registry.registry.contains_key(&task_type)
+            || registry.custom_registry.contains_key(&task_type))
+    }
+
+    /// All the registered system tasks that are executed asynchronously.
+    pub fn async_system_tasks() -> TegResult<Vec<Arc<Box<dyn WorkflowSystemTask>>>> {
+        let context = EngineContext::current()?;
+        let registry = &context.system_task_registry;
+        Ok(registry
+            .registry
+            .iter()
+            .chain(registry.custom_registry.iter())
+            .filter(|x| x.value().is_async())
+            .map(|x| Arc::clone(x.value()))
+            .collect())
     }
 
-    pub fn register(task_type: &str, task: Box<dyn WorkflowSystemTask>) {
-        CUSTOM_REGISTRY.insert(InlineStr::from(task_type), task);
+    pub fn register(task_type: &str, task: Box<dyn WorkflowSystemTask>) -> TegResult<()> {
+        EngineContext::current()?
+            .system_task_registry
+            .custom_registry
+            .insert(InlineStr::from(task_type), Arc::new(task));
+        Ok(())
     }
 
-    pub fn unregister(task_type: &str) {
-        CUSTOM_REGISTRY.remove(&InlineStr::from(task_type));
+    pub fn unregister(task_type: &str) -> TegResult<()> {
+        EngineContext::current()?
+            .system_task_registry
+            .custom_registry
+            .remove(&InlineStr::from(task_type));
+        Ok(())
     }
 }
@@ -2,47 +2,70 @@ use std::thread;
 use std::time::Duration;
 
 use futures::executor::{ThreadPool, ThreadPoolBuilder};
+use once_cell::sync::OnceCell;
 use tegmine_common::prelude::*;
 
-use crate::dao::QueueDao;
+use crate::dao::Daos;
+use crate::engine::EngineContext;
 use crate::metrics::Monitors;
 use crate::runtime::execution::AsyncSystemTaskExecutor;
 use crate::utils::{QueueUtils, SemaphoreUtil};
 use crate::{ExecutionService, WorkflowSystemTask};
 
-const POLL_INTERVAL: u64 = 50;
+/// How long a worker waits for free slots, then for messages of its queue, before checking for
+/// the shutdown of the engine.
+const POLL_TIMEOUT_MS: i32 = 200;
 
 const THREAD_COUNT: i32 = 10;
-static SEMAPHORE_UTIL: Lazy<SemaphoreUtil> = Lazy::new(|| SemaphoreUtil::new(THREAD_COUNT));
-static POOL: Lazy<ThreadPool> = Lazy::new(|| {
-    ThreadPoolBuilder::new()
-        .pool_size(THREAD_COUNT as usize)
-        .create()
-        .expect("thread pool create failed")
-});
-
-pub struct SystemTaskWorker;
+
+/// The slots and thread pool executing the async system tasks of an engine.
+pub struct SystemTaskWorker {
+    semaphore_util: SemaphoreUtil,
+    pool: OnceCell<ThreadPool>,
+}
+
+impl Default for SystemTaskWorker {
+    fn default() -> Self {
+        Self {
+            semaphore_util: SemaphoreUtil::new(THREAD_COUNT),
+            pool: OnceCell::new(),
+        }
+    }
+}
+
 impl SystemTaskWorker {
-    pub fn start_polling(system_task: Arc<Box<dyn WorkflowSystemTask>>) {
+    /// The thread pool executing the polled tasks, it is only created once a queue is polled,
+    /// i.e. after `SystemTaskWorkerCoordinator::init_system_task_executor`.
+    fn pool(&self) -> TegResult<&ThreadPool> {
+        self.pool.get_or_try_init(|| {
+            Ok(ThreadPoolBuilder::new()
+                .pool_size(THREAD_COUNT as usize)
+                .create()?)
+        })
+    }
+
+    pub fn start_polling(system_task: Arc<Box<dyn WorkflowSystemTask>>) -> TegResult<()> {
         let task_type = InlineStr::from(system_task.get_task_type());
-        Self::start_polling_with_queue_name(system_task, &task_type);
+        Self::start_polling_with_queue_name(system_task, &task_type)
     }
 
     pub fn start_polling_with_queue_name(
         system_task: Arc<Box<dyn WorkflowSystemTask>>,
         queue_name: &InlineStr,
-    ) {
+    ) -> TegResult<()> {
         let queue_name = queue_name.clone();
-        thread::spawn(move || {
-            const DELAY: u64 = 1000;
-            thread::sleep(Duration::from_millis(DELAY));
-            loop {
-                if let Err(e) = Self::poll_and_execute(Arc::clone(&system_task), &queue_name) {
-                    error!("failed to poll_and_execute, {}", e);
+        let mut started = false;
+        EngineContext::current()?.spawn_worker(
+            &format!("tegmine-system-task-{}", queue_name),
+            move || {
+                if !started {
+                    const DELAY: u64 = 1000;
+                    thread::sleep(Duration::from_millis(DELAY));
+                    started = true;
                 }
-                thread::sleep(Duration::from_millis(POLL_INTERVAL));
-            }
-        });
+                Self::poll_and_execute(Arc::clone(&system_task), &queue_name)
+            },
+        )
     }
 
     pub fn poll_and_execute(
@@ -54,7 +77,11 @@ impl SystemTaskWorker {
             queue_name: &str,
             messages_to_acquire: i32,
         ) -> TegResult<()> {
-            if messages_to_acquire <= 0 || !SEMAPHORE_UTIL.acquire_slots(messages_to_acquire) {
+            let context = EngineContext::current()?;
+            let worker = &context.system_task_worker;
+            let pool = worker.pool()?;
+            if messages_to_acquire <= 0 || !worker.semaphore_util.acquire_slots(messages_to_acquire)
+            {
                 // no available slots, do not poll
                 Monitors::record_system_task_worker_polling_limited(queue_name);
                 return Ok(());
@@ -65,7 +92,9 @@ impl SystemTaskWorker {
                 queue_name, messages_to_acquire
             );
 
-            let polled_task_ids = QueueDao::pop(queue_name, messages_to_acquire, 200)?;
+            let polled_task_ids = Daos::get()?
+                .queue_dao
+                .pop(queue_name, messages_to_acquire, POLL_TIMEOUT_MS)?;
 
             Monitors::record_task_poll(queue_name);
             trace!(
@@ -78,7 +107,8 @@ impl SystemTaskWorker {
                 // Immediately release unused slots when number of messages acquired is less than
                 // acquired slots
                 if polled_task_ids.len() < messages_to_acquire as usize {
-                    SEMAPHORE_UTIL
+                    worker
+                        .semaphore_util
                         .complete_processing(messages_to_acquire - polled_task_ids.len() as i32);
                 }
 
@@ -89,25 +119,41 @@ impl SystemTaskWorker {
                             task_id, queue_name
                         );
                         Monitors::record_task_poll_count_no_domain(queue_name, 1);
-                        ExecutionService::ack_task_received_by_task_id(&task_id);
 
                         let system_task_arc = Arc::clone(&system_task);
-                        POOL.spawn_ok(async {
-                            if let Err(e) = AsyncSystemTaskExecutor::execute(
-                                system_task_arc,
-                                &InlineStr::from(task_id),
-                            ) {
-                                error!("AsyncSystemTaskExecutor execute failed, {}", e)
+                        let task_context = Arc::clone(&context);
+                        let queue_name = InlineStr::from(queue_name);
+                        pool.spawn_ok(async move {
+                            let _guard = task_context.enter();
+                            // the message is only acked once the task has been executed, a failed
+                            // execution is redelivered after the unack timeout
+                            match AsyncSystemTaskExecutor::execute(system_task_arc, &task_id) {
+                                Ok(()) => {
+                                    if let Err(e) =
+                                        ExecutionService::ack_task_received_by_task_id(&task_id)
+                                    {
+                                        warn!(
+                                            "Error acking task: {} from queue: {}, error: {}",
+                                            task_id, queue_name, e
+                                        );
+                                    }
+                                }
+                                Err(e) => error!("AsyncSystemTaskExecutor execute failed, {}", e),
                             }
-                            SEMAPHORE_UTIL.complete_processing(1);
+                            task_context
+                                .system_task_worker
+                                .semaphore_util
+                                .complete_processing(1);
                         });
                     } else {
-                        SEMAPHORE_UTIL.complete_processing(1);
+                        worker.semaphore_util.complete_processing(1);
                     }
                 }
             } else {
                 // no task polled, release permit
-                SEMAPHORE_UTIL.complete_processing(messages_to_acquire);
+                worker
+                    .semaphore_util
+                    .complete_processing(messages_to_acquire);
             }
 
             Ok(())
@@ -115,12 +161,15 @@ impl SystemTaskWorker {
 
         let task_name = QueueUtils::get_task_type(queue_name);
 
-        let messages_to_acquire = SEMAPHORE_UTIL.available_slots();
+        let context = EngineContext::current()?;
+        let semaphore_util = &context.system_task_worker.semaphore_util;
+        // the queue is polled as soon as some running tasks complete
+        let messages_to_acquire = semaphore_util.wait_available_slots(POLL_TIMEOUT_MS as u64);
 
         if let Err(e) = _poll_and_execute(system_task, queue_name, messages_to_acquire) {
             // release the permit if exception is thrown during polling, because the thread would
             // not be busy
-            SEMAPHORE_UTIL.complete_processing(messages_to_acquire);
+            semaphore_util.complete_processing(messages_to_acquire);
             Monitors::record_task_poll_error_no_domain(&task_name, "SystemTaskWorker");
             error!("Error polling system task in queue:{} {}", queue_name, e);
         }
@@ -1,26 +1,38 @@
 use dashmap::DashMap;
-use once_cell::sync::Lazy;
 use tegmine_common::prelude::*;
 
+use crate::engine::EngineContext;
+use crate::runtime::execution::tasks::system_task_registry::SystemTaskRegistry;
 use crate::runtime::execution::tasks::system_task_worker::SystemTaskWorker;
 use crate::WorkflowSystemTask;
 
-static ASYNC_SYSTEM_TASKS: Lazy<DashMap<InlineStr, Arc<Box<dyn WorkflowSystemTask>>>> =
-    Lazy::new(|| DashMap::new());
-
-pub struct SystemTaskWorkerCoordinator;
+/// Holds the async system tasks whose queues are polled by the `SystemTaskWorker`.
+#[derive(Default)]
+pub struct SystemTaskWorkerCoordinator {
+    async_system_tasks: DashMap<InlineStr, Arc<Box<dyn WorkflowSystemTask>>>,
+}
 
 impl SystemTaskWorkerCoordinator {
-    pub fn init_system_task_executor() {
-        ASYNC_SYSTEM_TASKS
-            .iter()
-            .for_each(move |task| SystemTaskWorker::start_polling(Arc::clone(task.value())));
+    pub fn init_system_task_executor() -> TegResult<()> {
+        let context = EngineContext::current()?;
+        let async_system_tasks = &context.system_task_worker_coordinator.async_system_tasks;
+        // the async tasks of the system task registry are polled as well, unless a task of the
+        // same type has been registered explicitly
+        for system_task in SystemTaskRegistry::async_system_tasks()? {
+            async_system_tasks
+                .entry(InlineStr::from(system_task.get_task_type()))
+                .or_insert(system_task);
+        }
+        for task in async_system_tasks.iter() {
+            SystemTaskWorker::start_polling(Arc::clone(task.value()))?;
+        }
 
         info!(
             "{} initialized with {} async tasks",
             "system_task_worker_coordinator",
-            ASYNC_SYSTEM_TASKS.len()
+            async_system_tasks.len()
         );
+        Ok(())
     }
 
     pub fn register_async_system_task(system_task: Box<dyn WorkflowSystemTask>) -> TegResult<()> {
@@ -29,7 +41,13 @@ impl SystemTaskWorkerCoordinator {
                 "The registered WorkflowSystemTask must be an asyn task",
             ));
         }
-        ASYNC_SYSTEM_TASKS.insert(InlineStr::from(system_task.get_task_type()), Arc::from(system_task));
+        EngineContext::current()?
+            .system_task_worker_coordinator
+            .async_system_tasks
+            .insert(
+                InlineStr::from(system_task.get_task_type()),
+                Arc::from(system_task),
+            );
         Ok(())
     }
 }
@@ -0,0 +1,38 @@
+use chrono::Utc;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::workflow_system_task::WorkflowSystemTask;
+use crate::model::{TaskModel, TaskStatus, WorkflowModel};
+use crate::runtime::execution::WorkflowSweeper;
+
+/// The WAIT task parks the workflow until its `wait_timeout` elapses, or until it is updated
+/// externally when it has none. The workflow is due in the decider queue when the wait elapses,
+/// the sweeper then decides it again and the task completes.
+pub struct Wait;
+
+impl WorkflowSystemTask for Wait {
+    fn get_task_type(&self) -> &str {
+        TaskType::Wait.as_ref()
+    }
+
+    fn execute(&self, workflow: &mut WorkflowModel, task: &mut TaskModel) -> bool {
+        // waits for an external update
+        if task.wait_timeout == 0 {
+            return false;
+        }
+
+        if Utc::now().timestamp_millis() >= task.wait_timeout {
+            task.status = TaskStatus::Completed;
+            return true;
+        }
+
+        if let Err(e) = WorkflowSweeper::requeue(workflow) {
+            error!(
+                "Error requeueing workflow: {} of WAIT task: {}, error: {}",
+                workflow.workflow_id, task.task_id, e
+            );
+        }
+        false
+    }
+}
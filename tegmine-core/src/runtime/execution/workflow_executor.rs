@@ -3,20 +3,23 @@ use std::time::Instant;
 use chrono::Utc;
 use numtoa::NumToA;
 use tegmine_common::prelude::*;
-use tegmine_common::{TaskResult, TaskResultStatus, TaskType, WorkflowDef};
+use tegmine_common::{
+    RerunWorkflowRequest, SkipTaskRequest, TaskResult, TaskResultStatus, TaskType, TaskUtils,
+    WorkflowDef,
+};
 
 use super::tasks::SystemTaskRegistry;
-use super::DeciderService;
-use crate::dao::QueueDao;
+use super::{DeciderService, WorkflowSweeper};
+use crate::dao::{Daos, DECIDER_QUEUE};
 use crate::metrics::Monitors;
 use crate::model::{TaskModel, TaskStatus, WorkflowModel, WorkflowStatus};
 use crate::runtime::dal::ExecutionDaoFacade;
 use crate::runtime::event::{WorkflowCreationEvent, WorkflowEvaluationEvent};
-use crate::runtime::execution::tasks::Terminate;
-use crate::runtime::execution::{terminate_workflow_exception, CREATE_EVENT_CHANNEL};
+use crate::runtime::execution::tasks::{Human, Terminate};
+use crate::runtime::execution::{terminate_workflow_exception, Channel};
 use crate::runtime::StartWorkflowInput;
 use crate::service::ExecutionLockService;
-use crate::utils::{IdGenerator, QueueUtils};
+use crate::utils::{IdGenerator, ParametersUtils, QueueUtils};
 
 /// Workflow services provider interface
 pub struct WorkflowExecutor;
@@ -81,7 +84,7 @@ impl WorkflowExecutor {
                 .into()
             };
             if WorkflowStatus::Failed.as_ref().eq(termination_status) {
-                workflow.status = WorkflowStatus::Failed;
+                workflow.set_status(WorkflowStatus::Failed);
                 Self::terminate(workflow, workflow.status, Some(terminate_task), reason)?;
             } else {
                 workflow.reason_for_incompletion = reason;
@@ -99,11 +102,13 @@ impl WorkflowExecutor {
 
         if workflow.status == WorkflowStatus::Completed {
             // remove from the sweep queue
-            QueueDao::remove(QueueDao::DECIDER_QUEUE, &workflow.workflow_id)?;
+            Daos::get()?
+                .queue_dao
+                .remove(DECIDER_QUEUE, &workflow.workflow_id)?;
             ExecutionDaoFacade::remove_from_pending_workflow(
                 &workflow.workflow_definition.name,
                 &workflow.workflow_id,
-            );
+            )?;
             debug!(
                 "Workflow: {} has already been completed.",
                 workflow.workflow_id
@@ -121,7 +126,7 @@ impl WorkflowExecutor {
 
         DeciderService::update_workflow_output(workflow, None)?;
 
-        workflow.status = WorkflowStatus::Completed;
+        workflow.set_status(WorkflowStatus::Completed);
 
         // update the failed reference task names
         let failed_tasks = workflow
@@ -146,7 +151,7 @@ impl WorkflowExecutor {
                 .collect::<HashSet<_>>(),
         );
 
-        ExecutionDaoFacade::update_workflow(workflow);
+        ExecutionDaoFacade::update_workflow(workflow)?;
         debug!(
             "Completed workflow execution for {}",
             workflow.workflow_id.clone()
@@ -159,7 +164,7 @@ impl WorkflowExecutor {
         );
 
         if workflow.has_parent() {
-            Self::update_parent_workflow_task(workflow);
+            Self::update_parent_workflow_task(workflow)?;
             info!(
                 "{} updated parent {} task {}",
                 workflow.to_short_string(),
@@ -169,18 +174,24 @@ impl WorkflowExecutor {
             Self::expedite_lazy_workflow_evaluation(&workflow.parent_workflow_id);
         }
 
-        ExecutionLockService::release_lock(&workflow.workflow_id);
-        ExecutionLockService::delete_lock(&workflow.workflow_id);
+        ExecutionLockService::release_lock(&workflow.workflow_id)?;
+        ExecutionLockService::delete_lock(&workflow.workflow_id)?;
         Ok(())
     }
 
-    #[allow(unused)]
-    pub fn terminate_workflow(workflow: &mut WorkflowModel, reason: InlineStr) -> TegResult<()> {
-        let mut workflow = ExecutionDaoFacade::get_workflow_model(&workflow.workflow_id, true)?;
-        if workflow.status == WorkflowStatus::Completed {
-            str_err!(Conflict, "Cannot terminate a Completed workflow.")
+    /// Terminates the workflow with the reason, its in-flight tasks are canceled, and so are its
+    /// running sub workflows.
+    pub fn terminate_workflow(workflow_id: &InlineStr, reason: InlineStr) -> TegResult<()> {
+        let mut workflow = ExecutionDaoFacade::get_workflow_model(workflow_id, true)?;
+        if workflow.status.is_terminal() {
+            fmt_err!(
+                Conflict,
+                "Cannot terminate a {} workflow: {}",
+                workflow.status.as_ref(),
+                workflow_id
+            )
         } else {
-            workflow.status = WorkflowStatus::Terminated;
+            workflow.set_status(WorkflowStatus::Terminated);
             Self::terminate_workflow_with_failure_workflow(&mut workflow, reason, InlineStr::new())
         }
     }
@@ -190,10 +201,10 @@ impl WorkflowExecutor {
         reason: InlineStr,
         failure_workflow: InlineStr,
     ) -> TegResult<()> {
-        ExecutionLockService::acquire_lock_try_time(&workflow.workflow_id, 60000);
+        ExecutionLockService::acquire_lock_try_time(&workflow.workflow_id, 60000)?;
 
         if !workflow.status.is_terminal() {
-            workflow.status = WorkflowStatus::Terminated;
+            workflow.set_status(WorkflowStatus::Terminated);
         }
 
         if let Err(e) = DeciderService::update_workflow_output(workflow, None) {
@@ -230,7 +241,7 @@ impl WorkflowExecutor {
 
         let workflow_id = workflow.workflow_id.clone();
         workflow.reason_for_incompletion = reason.clone();
-        ExecutionDaoFacade::update_workflow(workflow);
+        ExecutionDaoFacade::update_workflow(workflow)?;
         //  workflowStatusListener.onWorkflowTerminatedIfEnabled(workflow);
         Monitors::record_workflow_termination(
             &workflow.workflow_definition.name,
@@ -245,7 +256,7 @@ impl WorkflowExecutor {
         let tasks = &workflow.tasks;
         // Remove from the task queue if they were there
         if let Err(e) = tasks.iter().try_for_each(|x| {
-            QueueDao::remove(
+            Daos::get()?.queue_dao.remove(
                 QueueUtils::get_queue_name_by_task_model(x).as_str(),
                 &x.task_id,
             )
@@ -257,7 +268,7 @@ impl WorkflowExecutor {
         }
 
         if workflow.has_parent() {
-            Self::update_parent_workflow_task(workflow);
+            Self::update_parent_workflow_task(workflow)?;
             info!(
                 "{} updated parent {} task {}",
                 workflow.to_short_string(),
@@ -288,9 +299,8 @@ impl WorkflowExecutor {
                 workflow_id,
             );
 
-            if let Err(e) = CREATE_EVENT_CHANNEL
-                .0
-                .send(WorkflowCreationEvent::new(start_workflow_input))
+            if let Err(e) =
+                Channel::send_creation_event(WorkflowCreationEvent::new(start_workflow_input))
             {
                 error!("Failed to start error workflow, error: {}", e);
                 workflow.output.insert(
@@ -310,12 +320,12 @@ impl WorkflowExecutor {
             workflow
                 .output
                 .insert("tegmine.failure_workflow".into(), failure_wf_id.into());
-            ExecutionDaoFacade::update_workflow(workflow);
+            ExecutionDaoFacade::update_workflow(workflow)?;
         }
         ExecutionDaoFacade::remove_from_pending_workflow(
             &workflow.workflow_definition.name,
             &workflow.workflow_id,
-        );
+        )?;
 
         let result = match Self::cancel_non_terminal_tasks(workflow) {
             Ok(errored_tasks) => {
@@ -332,8 +342,8 @@ impl WorkflowExecutor {
             Err(e) => Err(e),
         };
 
-        ExecutionLockService::release_lock(&workflow.workflow_id);
-        ExecutionLockService::delete_lock(&workflow.workflow_id);
+        ExecutionLockService::release_lock(&workflow.workflow_id)?;
+        ExecutionLockService::delete_lock(&workflow.workflow_id)?;
         result
     }
 
@@ -347,7 +357,7 @@ impl WorkflowExecutor {
         let workflow_instance = ExecutionDaoFacade::get_workflow_model(&workflow_id, false)?;
 
         let mut task =
-            ExecutionDaoFacade::get_task_model(&task_result.task_id).ok_or_else(|| {
+            ExecutionDaoFacade::get_task_model(&task_result.task_id)?.ok_or_else(|| {
                 ErrorCode::NotFound(format!("No such task found by id: {}", task_result.task_id))
             })?;
 
@@ -360,7 +370,9 @@ impl WorkflowExecutor {
 
         if task.status.is_terminal() {
             // Task was already updated....
-            QueueDao::remove(&task_queue_name, &task_result.task_id)?;
+            Daos::get()?
+                .queue_dao
+                .remove(&task_queue_name, &task_result.task_id)?;
             info!("Task: {} has already finished execution with status: {} within workflow: {}. Removed task from queue: {}", task.task_id, task.status.as_ref(), task.workflow_instance_id, task_queue_name);
             Monitors::record_update_conflict(
                 &task.task_type,
@@ -372,7 +384,9 @@ impl WorkflowExecutor {
 
         if workflow_instance.status.is_terminal() {
             // Workflow is in terminal state
-            QueueDao::remove(&task_queue_name, &task_result.task_id)?;
+            Daos::get()?
+                .queue_dao
+                .remove(&task_queue_name, &task_result.task_id)?;
             info!(
                 "Workflow: {:?} has already finished execution. Task update for: {} ignored and removed from Queue: {}.",
                 workflow_instance, task_result.task_id, task_queue_name
@@ -387,7 +401,7 @@ impl WorkflowExecutor {
 
         // for system tasks, setting to SCHEDULED would mean restarting the task which is
         // undesirable for worker tasks, set status to SCHEDULED and push to the queue
-        if !SystemTaskRegistry::is_system_task(&task.task_type)
+        if !SystemTaskRegistry::is_system_task(&task.task_type)?
             && task_result.status == TaskResultStatus::InProgress
         {
             task.status = TaskStatus::Scheduled;
@@ -422,7 +436,10 @@ impl WorkflowExecutor {
             | TaskStatus::Failed
             | TaskStatus::FailedWithTerminalError
             | TaskStatus::TimedOut => {
-                if let Err(e) = QueueDao::remove(&task_queue_name, &task_result.task_id) {
+                if let Err(e) = Daos::get()?
+                    .queue_dao
+                    .remove(&task_queue_name, &task_result.task_id)
+                {
                     // Ignore exceptions on queue remove as it wouldn't impact task and workflow
                     // execution, and will be cleaned up eventually
                     let error_msg = format!(
@@ -445,7 +462,7 @@ impl WorkflowExecutor {
             }
             TaskStatus::InProgress | TaskStatus::Scheduled => {
                 let call_back = task_result.callback_after_seconds;
-                if let Err(e) = QueueDao::postpone(
+                if let Err(e) = Daos::get()?.queue_dao.postpone(
                     &task_queue_name,
                     &task.task_id,
                     task.workflow_priority,
@@ -516,7 +533,7 @@ impl WorkflowExecutor {
 
     fn extend_lease(task_result: TaskResult) -> TegResult<()> {
         let mut task =
-            ExecutionDaoFacade::get_task_model(&task_result.task_id).ok_or_else(|| {
+            ExecutionDaoFacade::get_task_model(&task_result.task_id)?.ok_or_else(|| {
                 ErrorCode::NotFound(format!("No such task found by id: {}", task_result.task_id))
             })?;
 
@@ -538,6 +555,932 @@ impl WorkflowExecutor {
         Ok(())
     }
 
+    /// Skips the task of the running workflow by its reference name, the task is skipped with the
+    /// input and output of the request, so that the tasks following it can refer to them. A task
+    /// which has already been scheduled is canceled, otherwise it is skipped when the workflow
+    /// reaches it.
+    pub fn skip_task_from_workflow(
+        workflow_id: &InlineStr,
+        task_reference_name: &str,
+        skip_task_request: SkipTaskRequest,
+    ) -> TegResult<()> {
+        if !ExecutionLockService::acquire_lock(workflow_id)? {
+            return fmt_err!(
+                TransientException,
+                "Error acquiring lock when skipping task of workflow: {}",
+                workflow_id
+            );
+        }
+        let result =
+            ExecutionDaoFacade::get_workflow_model(workflow_id, true).and_then(|mut workflow| {
+                Self::skip_task(&mut workflow, task_reference_name, skip_task_request)
+            });
+        ExecutionLockService::release_lock(workflow_id)?;
+        result?;
+
+        Self::decide_workflow_id(workflow_id)
+    }
+
+    /// Skips the task of the running workflow, the caller holds the lock of the workflow.
+    fn skip_task(
+        workflow: &mut WorkflowModel,
+        task_reference_name: &str,
+        skip_task_request: SkipTaskRequest,
+    ) -> TegResult<()> {
+        let workflow_id = workflow.workflow_id.clone();
+        // If the workflow is not running then cannot skip any task
+        if workflow.status != WorkflowStatus::Running {
+            return fmt_err!(
+                Conflict,
+                "The workflow: {} is not running so the task referenced by: {} cannot be skipped",
+                workflow_id,
+                task_reference_name
+            );
+        }
+        let workflow_task = workflow
+            .workflow_definition
+            .get_task_by_ref_name(TaskUtils::remove_iteration_from_task_ref_name(
+                task_reference_name,
+            ))
+            .cloned()
+            .ok_or_else(|| {
+                ErrorCode::IllegalArgument(format!(
+                    "The task referenced by: {} does not exist in the workflow definition: {}",
+                    task_reference_name, workflow.workflow_definition.name
+                ))
+            })?;
+
+        let workflow_ptr = addr_of_mut!(workflow);
+        match workflow
+            .tasks
+            .iter_mut()
+            .rev()
+            .find(|x| x.reference_task_name.eq(task_reference_name))
+        {
+            Some(task) if task.status.is_terminal() => {
+                return fmt_err!(
+                    Conflict,
+                    "The task referenced by: {} has already been processed, cannot be skipped",
+                    task_reference_name
+                );
+            }
+            Some(task) => {
+                if SystemTaskRegistry::is_system_task(&task.task_type)? {
+                    SystemTaskRegistry::get(&task.task_type)?
+                        .cancel(from_addr_mut!(workflow_ptr), task)?;
+                }
+                Daos::get()?.queue_dao.remove(
+                    &QueueUtils::get_queue_name_by_task_model(task),
+                    &task.task_id,
+                )?;
+                task.status = TaskStatus::Skipped;
+                task.input_data = skip_task_request.task_input;
+                task.output_data = skip_task_request.task_output;
+                task.end_time = Utc::now().timestamp_millis();
+                ExecutionDaoFacade::update_task(task)?;
+            }
+            None => {
+                let mut task = TaskModel::new(TaskStatus::Skipped);
+                task.task_id = IdGenerator::generate();
+                task.reference_task_name = task_reference_name.into();
+                // the type of a SIMPLE task is its name, as for the scheduled ones
+                task.task_type = if workflow_task.type_.eq(TaskType::Simple.as_ref()) {
+                    workflow_task.name.clone()
+                } else {
+                    workflow_task.type_.clone()
+                };
+                task.task_def_name = workflow_task.name.clone();
+                task.workflow_instance_id = workflow_id.clone();
+                task.workflow_type = workflow.workflow_definition.name.clone();
+                task.workflow_priority = workflow.priority;
+                task.correlation_id = workflow.correlation_id.clone();
+                task.seq = workflow.tasks.iter().map(|x| x.seq).max().unwrap_or(0) + 1;
+                task.end_time = Utc::now().timestamp_millis();
+                task.input_data = skip_task_request.task_input;
+                task.output_data = skip_task_request.task_output;
+                task.workflow_task = Some(workflow_task);
+                // never processed by the decider, it is skipped when the workflow reaches it
+                task.executed = true;
+                ExecutionDaoFacade::create_tasks(&mut [&mut task])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reruns the finished workflow from the task of the request, which may be a task of one of
+    /// its sub workflows, or from the beginning when no task is given.
+    ///
+    /// return the id of the rerun workflow.
+    pub fn rerun(request: RerunWorkflowRequest) -> TegResult<InlineStr> {
+        let workflow_id = request.re_run_from_workflow_id;
+        let rerun_path = Self::find_rerun_path(&workflow_id, &request.re_run_from_task_id)?;
+        let mut workflow_input = request.workflow_input;
+
+        // the workflow of the task is rerun first, then each parent waits for its rerun sub
+        // workflow again, up to the requested workflow
+        for (i, (rerun_workflow_id, rerun_from_task_id)) in rerun_path.iter().enumerate() {
+            let (rerun_input, correlation_id) = if rerun_workflow_id.eq(&workflow_id) {
+                (workflow_input.take(), request.correlation_id.clone())
+            } else {
+                (None, InlineStr::new())
+            };
+            Self::rerun_workflow(
+                rerun_workflow_id,
+                rerun_from_task_id,
+                request.task_input.as_ref().filter(|_| i == 0),
+                i > 0,
+                rerun_input,
+                &correlation_id,
+            )?;
+        }
+
+        let workflow = ExecutionDaoFacade::get_workflow_model(&workflow_id, false)?;
+        Self::update_and_push_parents(&workflow)?;
+        Ok(workflow_id)
+    }
+
+    /// Finds the workflow owning the task to rerun from, which is the requested workflow or one of
+    /// its sub workflows, all of them must be terminal.
+    ///
+    /// return the workflows from the one of the task up to the requested one, along with the task
+    /// to rerun each of them from, i.e. the SUB_WORKFLOW task of the previous one for the parents.
+    fn find_rerun_path(
+        workflow_id: &InlineStr,
+        task_id: &InlineStr,
+    ) -> TegResult<Vec<(InlineStr, InlineStr)>> {
+        let mut rerun_workflow_id = if task_id.is_empty() {
+            workflow_id.clone()
+        } else {
+            match ExecutionDaoFacade::get_task_model(task_id)? {
+                Some(task) => task.workflow_instance_id,
+                None => {
+                    return fmt_err!(
+                        NotFound,
+                        "Task: {} not found in workflow: {}",
+                        task_id,
+                        workflow_id
+                    );
+                }
+            }
+        };
+        let mut rerun_from_task_id = task_id.clone();
+
+        let mut rerun_path = Vec::new();
+        loop {
+            let workflow = ExecutionDaoFacade::get_workflow_model(&rerun_workflow_id, false)?;
+            Self::check_rerunnable(&workflow)?;
+            rerun_path.push((rerun_workflow_id, rerun_from_task_id));
+            if workflow.workflow_id.eq(workflow_id) {
+                return Ok(rerun_path);
+            }
+            if workflow.parent_workflow_id.is_empty() {
+                return fmt_err!(
+                    NotFound,
+                    "Task: {} not found in workflow: {}",
+                    task_id,
+                    workflow_id
+                );
+            }
+            rerun_workflow_id = workflow.parent_workflow_id;
+            rerun_from_task_id = workflow.parent_workflow_task_id;
+        }
+    }
+
+    fn check_rerunnable(workflow: &WorkflowModel) -> TegResult<()> {
+        if !workflow.status.is_terminal() {
+            return fmt_err!(
+                Conflict,
+                "Workflow: {} is still {}, it can not be rerun",
+                workflow.workflow_id,
+                workflow.status.as_ref()
+            );
+        }
+        Ok(())
+    }
+
+    fn rerun_workflow(
+        workflow_id: &InlineStr,
+        task_id: &InlineStr,
+        task_input: Option<&HashMap<InlineStr, Object>>,
+        sub_workflow_rerun: bool,
+        workflow_input: Option<HashMap<InlineStr, Object>>,
+        correlation_id: &InlineStr,
+    ) -> TegResult<()> {
+        if !ExecutionLockService::acquire_lock(workflow_id)? {
+            return fmt_err!(
+                TransientException,
+                "Error acquiring lock when rerunning workflow: {}",
+                workflow_id
+            );
+        }
+        let result =
+            ExecutionDaoFacade::get_workflow_model(workflow_id, true).and_then(|mut workflow| {
+                Self::rerun_workflow_from_task(
+                    &mut workflow,
+                    task_id,
+                    task_input,
+                    sub_workflow_rerun,
+                    workflow_input,
+                    correlation_id,
+                )
+            });
+        ExecutionLockService::release_lock(workflow_id)?;
+
+        result?;
+        Self::decide_workflow_id(workflow_id)?;
+        Ok(())
+    }
+
+    /// Resets the terminal workflow and its tasks for the rerun, the caller holds the lock of the
+    /// workflow. When `sub_workflow_rerun` is set, the task to rerun from is a SUB_WORKFLOW task
+    /// whose sub workflow has been rerun.
+    fn rerun_workflow_from_task(
+        workflow: &mut WorkflowModel,
+        task_id: &InlineStr,
+        task_input: Option<&HashMap<InlineStr, Object>>,
+        sub_workflow_rerun: bool,
+        workflow_input: Option<HashMap<InlineStr, Object>>,
+        correlation_id: &InlineStr,
+    ) -> TegResult<()> {
+        Self::check_rerunnable(workflow)?;
+
+        // rerun the entire workflow
+        if task_id.is_empty() {
+            for task in &workflow.tasks {
+                ExecutionDaoFacade::remove_task(&task.task_id)?;
+            }
+            workflow.tasks.clear();
+            Self::reset_workflow_for_rerun(workflow, workflow_input, correlation_id);
+            ExecutionDaoFacade::update_workflow(workflow)?;
+            WorkflowSweeper::requeue(workflow)?;
+            return Ok(());
+        }
+
+        let rerun_from_seq = match workflow.tasks.iter().find(|x| x.task_id.eq(task_id)) {
+            Some(task) => task.seq,
+            None => {
+                return fmt_err!(
+                    NotFound,
+                    "Task: {} not found in workflow: {}",
+                    task_id,
+                    workflow.workflow_id
+                );
+            }
+        };
+
+        Self::reset_workflow_for_rerun(workflow, workflow_input, correlation_id);
+        ExecutionDaoFacade::update_workflow(workflow)?;
+        WorkflowSweeper::requeue(workflow)?;
+
+        // remove the tasks scheduled after the task to rerun from
+        for task in workflow.tasks.iter().filter(|x| x.seq > rerun_from_seq) {
+            ExecutionDaoFacade::remove_task(&task.task_id)?;
+        }
+        workflow.tasks = std::mem::take(&mut workflow.tasks)
+            .into_iter()
+            .filter(|x| x.seq <= rerun_from_seq)
+            .collect();
+
+        let workflow_ptr = addr_of_mut!(workflow);
+        let rerun_from_task = workflow
+            .tasks
+            .iter_mut()
+            .find(|x| x.seq == rerun_from_seq)
+            .expect("not none");
+        let now = Utc::now().timestamp_millis();
+        rerun_from_task.scheduled_time = now;
+        rerun_from_task.start_time = 0;
+        rerun_from_task.update_time = 0;
+        rerun_from_task.end_time = 0;
+        rerun_from_task.output_data.clear();
+        rerun_from_task.external_output_payload_storage_path = InlineStr::new();
+        rerun_from_task.reason_for_incompletion = InlineStr::new();
+        rerun_from_task.retried = false;
+        rerun_from_task.executed = false;
+
+        if sub_workflow_rerun {
+            // the sub workflow has been rerun, the task waits for it again
+            rerun_from_task.status = TaskStatus::InProgress;
+            rerun_from_task.start_time = now;
+            rerun_from_task.output_data.insert(
+                "subWorkflowId".into(),
+                rerun_from_task.sub_workflow_id.clone().into(),
+            );
+        } else {
+            if let Some(task_input) = task_input {
+                rerun_from_task.input_data = task_input.clone();
+            }
+            // a SUB_WORKFLOW task rerun by itself starts a new sub workflow
+            rerun_from_task.sub_workflow_id = InlineStr::new();
+            let workflow_system_task =
+                if SystemTaskRegistry::is_system_task(&rerun_from_task.task_type)? {
+                    Some(SystemTaskRegistry::get(&rerun_from_task.task_type)?)
+                } else {
+                    None
+                };
+            match workflow_system_task {
+                Some(workflow_system_task) if !workflow_system_task.is_async() => {
+                    // start the synchronous system task directly
+                    rerun_from_task.status = TaskStatus::InProgress;
+                    rerun_from_task.start_time = now;
+                    ExecutionDaoFacade::populate_task_data(rerun_from_task);
+                    workflow_system_task.start(from_addr_mut!(workflow_ptr), rerun_from_task)?;
+                }
+                _ => {
+                    rerun_from_task.status = TaskStatus::Scheduled;
+                    Self::add_task_to_queue(rerun_from_task)?;
+                }
+            }
+        }
+        ExecutionDaoFacade::update_task(rerun_from_task)?;
+        Ok(())
+    }
+
+    fn reset_workflow_for_rerun(
+        workflow: &mut WorkflowModel,
+        workflow_input: Option<HashMap<InlineStr, Object>>,
+        correlation_id: &InlineStr,
+    ) {
+        workflow.set_status(WorkflowStatus::Running);
+        workflow.reason_for_incompletion = InlineStr::new();
+        workflow.failed_task_id = InlineStr::new();
+        workflow.failed_task_names.clear();
+        workflow.failed_reference_task_names.clear();
+        workflow.end_time = 0;
+        if !correlation_id.is_empty() {
+            workflow.correlation_id = correlation_id.clone();
+        }
+        if let Some(workflow_input) = workflow_input {
+            workflow.input = workflow_input;
+        }
+    }
+
+    /// Restarts the terminal workflow from the beginning with the same id, optionally with the
+    /// latest version of its definition.
+    pub fn restart_workflow(
+        workflow_id: &InlineStr,
+        use_latest_definitions: bool,
+    ) -> TegResult<()> {
+        if !ExecutionLockService::acquire_lock(workflow_id)? {
+            return fmt_err!(
+                TransientException,
+                "Error acquiring lock when restarting workflow: {}",
+                workflow_id
+            );
+        }
+        let result = ExecutionDaoFacade::get_workflow_model(workflow_id, true)
+            .and_then(|mut workflow| Self::restart(&mut workflow, use_latest_definitions));
+        ExecutionLockService::release_lock(workflow_id)?;
+        result?;
+
+        Self::decide_workflow_id(workflow_id)
+    }
+
+    /// Re-creates the terminal workflow without its tasks, the caller holds the lock of the
+    /// workflow.
+    fn restart(workflow: &mut WorkflowModel, use_latest_definitions: bool) -> TegResult<()> {
+        if !workflow.status.is_terminal() {
+            return fmt_err!(
+                Conflict,
+                "Workflow: {} is still {}, it can not be restarted",
+                workflow.workflow_id,
+                workflow.status.as_ref()
+            );
+        }
+
+        if use_latest_definitions {
+            let name = &workflow.workflow_definition.name;
+            workflow.workflow_definition = Daos::get()?
+                .metadata_dao
+                .get_latest_workflow_def(name)?
+                .ok_or_else(|| {
+                    ErrorCode::NotFound(format!(
+                        "Unable to find latest definition for workflow: {}",
+                        name
+                    ))
+                })?;
+        }
+        if !workflow.workflow_definition.restartable && workflow.status == WorkflowStatus::Completed
+        {
+            return fmt_err!(
+                Conflict,
+                "Workflow: {} is completed and its definition is not restartable",
+                workflow.workflow_id
+            );
+        }
+
+        // remove the workflow and its tasks from the data store, then re-create it
+        ExecutionDaoFacade::remove_workflow(&workflow.workflow_id, false)?;
+        workflow.tasks.clear();
+        Self::reset_workflow_for_rerun(workflow, None, &InlineStr::new());
+        workflow.create_time = Utc::now().timestamp_millis();
+        workflow.last_retried_time = 0;
+        workflow.output.clear();
+        workflow.external_output_payload_storage_path = InlineStr::new();
+        ExecutionDaoFacade::create_workflow(workflow)?;
+
+        Self::update_and_push_parents(workflow)
+    }
+
+    /// Retries the last failed or timed out tasks of the terminal workflow and resumes it from
+    /// there.
+    ///
+    /// With `resume_sub_workflow_tasks`, a failed SUB_WORKFLOW task is resumed by retrying the
+    /// failed tasks of its sub workflow, instead of starting a new sub workflow.
+    pub fn retry_workflow(
+        workflow_id: &InlineStr,
+        resume_sub_workflow_tasks: bool,
+    ) -> TegResult<()> {
+        if !ExecutionLockService::acquire_lock(workflow_id)? {
+            return fmt_err!(
+                TransientException,
+                "Error acquiring lock when retrying workflow: {}",
+                workflow_id
+            );
+        }
+        let result =
+            ExecutionDaoFacade::get_workflow_model(workflow_id, true).and_then(|mut workflow| {
+                if !workflow.status.is_terminal() {
+                    return fmt_err!(
+                        Conflict,
+                        "Workflow: {} is still {}, it can not be retried",
+                        workflow_id,
+                        workflow.status.as_ref()
+                    );
+                }
+                if workflow.tasks.is_empty() {
+                    return fmt_err!(
+                        Conflict,
+                        "Workflow: {} has not started any task, use restart instead",
+                        workflow_id
+                    );
+                }
+
+                if resume_sub_workflow_tasks {
+                    workflow = Self::find_last_failed_sub_workflow(workflow)?;
+                }
+                Self::retry(&mut workflow)?;
+                Self::update_and_push_parents(&workflow)?;
+                Ok(workflow.workflow_id)
+            });
+        ExecutionLockService::release_lock(workflow_id)?;
+
+        // the innermost failed sub workflow when its tasks are resumed
+        let retried_workflow_id = result?;
+        Self::decide_workflow_id(&retried_workflow_id)
+    }
+
+    /// Reschedules the unsuccessful tasks of the workflow, the last attempt of each task
+    /// reference name decides whether it is retried.
+    fn retry(workflow: &mut WorkflowModel) -> TegResult<()> {
+        let mut tasks = workflow.tasks.iter_mut().collect::<Vec<_>>();
+        tasks.sort_by_key(|x| x.seq);
+        let mut retriable_tasks: HashMap<InlineStr, *mut TaskModel> = HashMap::default();
+        for task in tasks {
+            match task.status {
+                TaskStatus::Failed | TaskStatus::FailedWithTerminalError | TaskStatus::TimedOut => {
+                    retriable_tasks.insert(task.reference_task_name.clone(), addr_of_mut!(task));
+                }
+                TaskStatus::Canceled => {
+                    if task.task_type.eq(TaskType::Join.as_ref())
+                        || task.task_type.eq(TaskType::DoWhile.as_ref())
+                    {
+                        // evaluated again along with the retried tasks
+                        task.status = TaskStatus::InProgress;
+                        task.end_time = 0;
+                    } else {
+                        retriable_tasks
+                            .insert(task.reference_task_name.clone(), addr_of_mut!(task));
+                    }
+                }
+                _ => {
+                    retriable_tasks.remove(&task.reference_task_name);
+                }
+            }
+        }
+
+        // a workflow timed out by its definition may have no unsuccessful task
+        if retriable_tasks.is_empty() && workflow.status != WorkflowStatus::TimedOut {
+            return fmt_err!(
+                Conflict,
+                "There are no retryable tasks of workflow: {}, use restart instead",
+                workflow.workflow_id
+            );
+        }
+
+        Self::reset_workflow_for_rerun(workflow, None, &InlineStr::new());
+        workflow.last_retried_time = Utc::now().timestamp_millis();
+        ExecutionDaoFacade::update_workflow(workflow)?;
+        WorkflowSweeper::requeue(workflow)?;
+
+        let mut retriable_tasks = retriable_tasks
+            .into_values()
+            .map(|x| from_addr_mut!(x))
+            .collect::<Vec<_>>();
+        retriable_tasks.sort_by_key(|x| x.seq);
+        let mut rescheduled_tasks = Vec::with_capacity(retriable_tasks.len());
+        for task in retriable_tasks {
+            rescheduled_tasks.push(Self::task_to_be_rescheduled(workflow, task)?);
+        }
+        let (tasks_to_be_scheduled, _) = Self::dedup_and_add_tasks(workflow, rescheduled_tasks);
+        // the retried tasks are updated before the new attempts are scheduled
+        let all_tasks = workflow
+            .tasks
+            .iter_mut()
+            .map(|x| x as *mut TaskModel)
+            .collect::<Vec<_>>();
+        ExecutionDaoFacade::update_tasks(&all_tasks);
+        Self::schedule_task(workflow, &tasks_to_be_scheduled)?;
+        Ok(())
+    }
+
+    /// Creates the next attempt of the task, and marks the task as retried.
+    fn task_to_be_rescheduled(
+        workflow: &WorkflowModel,
+        task: &mut TaskModel,
+    ) -> TegResult<TaskModel> {
+        let mut rescheduled = task.clone();
+        rescheduled.task_id = IdGenerator::generate();
+        rescheduled.retried_task_id = task.task_id.clone();
+        rescheduled.status = TaskStatus::Scheduled;
+        rescheduled.retry_count = task.retry_count + 1;
+        rescheduled.retried = false;
+        rescheduled.executed = false;
+        rescheduled.poll_count = 0;
+        rescheduled.callback_after_seconds = 0;
+        rescheduled.sub_workflow_id = InlineStr::new();
+        rescheduled.seq = 0;
+        rescheduled.scheduled_time = 0;
+        rescheduled.start_time = 0;
+        rescheduled.end_time = 0;
+        rescheduled.worker_id = InlineStr::new();
+        rescheduled.reason_for_incompletion = InlineStr::new();
+        rescheduled.output_data.clear();
+
+        if let Some(workflow_task) = &task.workflow_task {
+            let task_input = ParametersUtils::get_task_input(
+                &workflow_task.input_parameters,
+                workflow,
+                workflow_task.task_definition.as_ref(),
+                Some(&rescheduled.task_id),
+            )?;
+            rescheduled.input_data.extend(task_input);
+        }
+
+        // the lifecycle of the task completes once its next attempt is computed
+        task.retried = true;
+        task.executed = true;
+        Ok(rescheduled)
+    }
+
+    /// Follows the last unsuccessful SUB_WORKFLOW task down to the innermost failed sub
+    /// workflow, returns the workflow itself if there is none.
+    fn find_last_failed_sub_workflow(workflow: WorkflowModel) -> TegResult<WorkflowModel> {
+        let sub_workflow_id = workflow
+            .tasks
+            .iter()
+            .filter(|x| !x.retried && x.status.is_terminal() && !x.status.is_successful())
+            .max_by_key(|x| x.seq)
+            .filter(|x| {
+                x.task_type.eq(TaskType::SubWorkflow.as_ref()) && !x.sub_workflow_id.is_empty()
+            })
+            .map(|x| x.sub_workflow_id.clone());
+
+        if let Some(sub_workflow_id) = sub_workflow_id {
+            let sub_workflow = ExecutionDaoFacade::get_workflow_model(&sub_workflow_id, true)?;
+            if sub_workflow.status.is_terminal() && !sub_workflow.status.is_successful() {
+                return Self::find_last_failed_sub_workflow(sub_workflow);
+            }
+        }
+        Ok(workflow)
+    }
+
+    /// Moves the ancestors of the restarted or retried workflow back to running, their
+    /// SUB_WORKFLOW tasks wait for it again.
+    fn update_and_push_parents(workflow: &WorkflowModel) -> TegResult<()> {
+        let mut parent_workflow_id = workflow.parent_workflow_id.clone();
+        let mut parent_workflow_task_id = workflow.parent_workflow_task_id.clone();
+        while !parent_workflow_id.is_empty() {
+            let mut sub_workflow_task =
+                ExecutionDaoFacade::get_task_model(&parent_workflow_task_id)?.ok_or_else(|| {
+                    ErrorCode::NotFound(format!(
+                        "No such task found by id: {}",
+                        parent_workflow_task_id
+                    ))
+                })?;
+            sub_workflow_task.status = TaskStatus::InProgress;
+            sub_workflow_task.sub_workflow_changed = true;
+            sub_workflow_task.end_time = 0;
+            sub_workflow_task.reason_for_incompletion = InlineStr::new();
+            sub_workflow_task.executed = false;
+            ExecutionDaoFacade::update_task(&mut sub_workflow_task)?;
+
+            let mut parent_workflow =
+                ExecutionDaoFacade::get_workflow_model(&parent_workflow_id, true)?;
+            Self::reset_workflow_for_rerun(&mut parent_workflow, None, &InlineStr::new());
+            parent_workflow.last_retried_time = Utc::now().timestamp_millis();
+            ExecutionDaoFacade::update_workflow(&mut parent_workflow)?;
+            WorkflowSweeper::requeue(&parent_workflow)?;
+            Self::expedite_lazy_workflow_evaluation(&parent_workflow_id);
+            info!(
+                "Workflow: {} is running again along with its sub workflow task: {}",
+                parent_workflow_id, parent_workflow_task_id
+            );
+
+            parent_workflow_id = parent_workflow.parent_workflow_id;
+            parent_workflow_task_id = parent_workflow.parent_workflow_task_id;
+        }
+        Ok(())
+    }
+
+    /// Pauses the running workflow, no task is scheduled until it is resumed, while the tasks
+    /// in progress may still complete.
+    pub fn pause_workflow(workflow_id: &InlineStr) -> TegResult<()> {
+        if !ExecutionLockService::acquire_lock(workflow_id)? {
+            return fmt_err!(
+                TransientException,
+                "Error acquiring lock when pausing workflow: {}",
+                workflow_id
+            );
+        }
+        let result =
+            ExecutionDaoFacade::get_workflow_model(workflow_id, false).and_then(|mut workflow| {
+                match workflow.status {
+                    WorkflowStatus::Paused => {
+                        info!("Workflow: {} has already been paused", workflow_id);
+                        Ok(())
+                    }
+                    WorkflowStatus::Running => {
+                        workflow.set_status(WorkflowStatus::Paused);
+                        ExecutionDaoFacade::update_workflow(&mut workflow)
+                    }
+                    status => fmt_err!(
+                        Conflict,
+                        "Workflow: {} has finished execution with status: {}, it can not be paused",
+                        workflow_id,
+                        status.as_ref()
+                    ),
+                }
+            });
+        ExecutionLockService::release_lock(workflow_id)?;
+        result
+    }
+
+    /// Resumes the paused workflow, and sends an evaluation event of it to schedule the next
+    /// tasks.
+    pub fn resume_workflow(workflow_id: &InlineStr) -> TegResult<()> {
+        if !ExecutionLockService::acquire_lock(workflow_id)? {
+            return fmt_err!(
+                TransientException,
+                "Error acquiring lock when resuming workflow: {}",
+                workflow_id
+            );
+        }
+        let result =
+            ExecutionDaoFacade::get_workflow_model(workflow_id, false).and_then(|mut workflow| {
+                if workflow.status != WorkflowStatus::Paused {
+                    return fmt_err!(
+                        Conflict,
+                        "Workflow: {} is not paused, status: {}",
+                        workflow_id,
+                        workflow.status.as_ref()
+                    );
+                }
+                workflow.set_status(WorkflowStatus::Running);
+                ExecutionDaoFacade::update_workflow(&mut workflow)
+            });
+        ExecutionLockService::release_lock(workflow_id)?;
+        result?;
+
+        let workflow = ExecutionDaoFacade::get_workflow_model(workflow_id, true)?;
+        WorkflowSweeper::requeue(&workflow)?;
+        Channel::send_evaluation_event(WorkflowEvaluationEvent::new(workflow))
+    }
+
+    /// Whether the task waits to be completed from outside of the engine.
+    fn is_signalable(task: &TaskModel) -> TegResult<bool> {
+        if task.task_type.eq(TaskType::Wait.as_ref()) || task.task_type.eq(TaskType::Human.as_ref())
+        {
+            return Ok(true);
+        }
+        Ok(SystemTaskRegistry::is_system_task(&task.task_type)?
+            && SystemTaskRegistry::get(&task.task_type)?.is_async_complete(task))
+    }
+
+    /// Completes the pending WAIT, HUMAN or async complete task of the workflow by its reference
+    /// name, the latest iteration of the task is signaled when it is within a DO_WHILE loop. The
+    /// output is merged into the output of the task, and the workflow is decided.
+    ///
+    /// return the id of the signaled task.
+    pub fn signal(
+        workflow_id: &InlineStr,
+        task_reference_name: &str,
+        status: TaskResultStatus,
+        output: HashMap<InlineStr, Object>,
+    ) -> TegResult<InlineStr> {
+        if !matches!(
+            status,
+            TaskResultStatus::Completed
+                | TaskResultStatus::Failed
+                | TaskResultStatus::FailedWithTerminalError
+        ) {
+            return fmt_err!(
+                IllegalArgument,
+                "Invalid status: {} to signal a task, it has to be terminal",
+                status.as_ref()
+            );
+        }
+
+        let workflow = ExecutionDaoFacade::get_workflow_model(workflow_id, true)?;
+        if workflow.status.is_terminal() {
+            return fmt_err!(
+                Conflict,
+                "Workflow: {} has already finished execution with status: {}",
+                workflow_id,
+                workflow.status.as_ref()
+            );
+        }
+
+        let task = workflow
+            .tasks
+            .iter()
+            .rev()
+            .find(|x| {
+                !x.status.is_terminal()
+                    && (x.reference_task_name.eq(task_reference_name)
+                        || TaskUtils::remove_iteration_from_task_ref_name(&x.reference_task_name)
+                            .eq(task_reference_name))
+            })
+            .ok_or_else(|| {
+                ErrorCode::NotFound(format!(
+                    "No pending task: {} found in workflow: {}",
+                    task_reference_name, workflow_id
+                ))
+            })?;
+        if !Self::is_signalable(task)? {
+            return fmt_err!(
+                Conflict,
+                "Task: {} of type: {} in workflow: {} can not be signaled",
+                task.reference_task_name,
+                task.task_type,
+                workflow_id
+            );
+        }
+
+        let mut output_data = task.output_data.clone();
+        output_data.extend(output);
+        let task_id = task.task_id.clone();
+        Self::update_task(TaskResult {
+            workflow_instance_id: workflow_id.clone(),
+            task_id: task_id.clone(),
+            reason_for_incompletion: InlineStr::new(),
+            callback_after_seconds: 0,
+            worker_id: InlineStr::new(),
+            status,
+            output_data,
+            output_message: Object::Null,
+            logs: Vec::default(),
+            external_output_payload_storage_path: InlineStr::new(),
+            sub_workflow_id: InlineStr::new(),
+            extend_lease: false,
+        })?;
+        Ok(task_id)
+    }
+
+    /// Gets the pending HUMAN tasks the user, a member of the groups, may claim or has claimed.
+    pub fn get_pending_human_tasks(user: &str, groups: &[&str]) -> TegResult<Vec<TaskModel>> {
+        Ok(
+            ExecutionDaoFacade::get_pending_tasks_for_task_type(TaskType::Human.as_ref())?
+                .into_iter()
+                .filter(|x| {
+                    x.status == TaskStatus::InProgress && Human::is_candidate(x, user, groups)
+                })
+                .collect(),
+        )
+    }
+
+    fn get_pending_human_task(task_id: &InlineStr) -> TegResult<TaskModel> {
+        let task = ExecutionDaoFacade::get_task_model(task_id)?
+            .ok_or_else(|| ErrorCode::NotFound(format!("No such task found by id: {}", task_id)))?;
+        if !task.task_type.eq(TaskType::Human.as_ref()) {
+            return fmt_err!(IllegalArgument, "Task: {} is not a HUMAN task", task_id);
+        }
+        if task.status != TaskStatus::InProgress {
+            return fmt_err!(
+                Conflict,
+                "HUMAN task: {} is no longer pending, status: {}",
+                task_id,
+                task.status.as_ref()
+            );
+        }
+        Ok(task)
+    }
+
+    /// Claims the HUMAN task for the user, a member of the groups, the other candidates can no
+    /// longer claim or complete it until it is released.
+    pub fn claim_human_task(task_id: &InlineStr, user: &str, groups: &[&str]) -> TegResult<()> {
+        let task = Self::get_pending_human_task(task_id)?;
+        let workflow_id = task.workflow_instance_id.clone();
+        if !ExecutionLockService::acquire_lock(&workflow_id)? {
+            return fmt_err!(
+                TransientException,
+                "Error acquiring lock on workflow: {} to claim task: {}",
+                workflow_id,
+                task_id
+            );
+        }
+        // reload the task under the lock, it may have been claimed or completed meanwhile
+        let result = Self::get_pending_human_task(task_id).and_then(|mut task| {
+            if let Some(claimed_by) = Human::get_claimed_by(&task) {
+                if claimed_by.eq(user) {
+                    return Ok(());
+                }
+                return fmt_err!(
+                    Conflict,
+                    "HUMAN task: {} is already claimed by: {}",
+                    task_id,
+                    claimed_by
+                );
+            }
+            if !Human::is_candidate(&task, user, groups) {
+                return fmt_err!(
+                    Conflict,
+                    "User: {} is not a candidate of HUMAN task: {}",
+                    user,
+                    task_id
+                );
+            }
+            task.output_data
+                .insert(Human::CLAIMED_BY.into(), Object::String(user.into()));
+            ExecutionDaoFacade::update_task(&mut task)
+        });
+        ExecutionLockService::release_lock(&workflow_id)?;
+        result
+    }
+
+    /// Releases the HUMAN task claimed by the user, so that the other candidates can claim it.
+    pub fn release_human_task(task_id: &InlineStr, user: &str) -> TegResult<()> {
+        let task = Self::get_pending_human_task(task_id)?;
+        let workflow_id = task.workflow_instance_id.clone();
+        if !ExecutionLockService::acquire_lock(&workflow_id)? {
+            return fmt_err!(
+                TransientException,
+                "Error acquiring lock on workflow: {} to release task: {}",
+                workflow_id,
+                task_id
+            );
+        }
+        let result = Self::get_pending_human_task(task_id).and_then(|mut task| {
+            if !Human::get_claimed_by(&task).map_or(false, |x| x.eq(user)) {
+                return fmt_err!(
+                    Conflict,
+                    "HUMAN task: {} is not claimed by: {}",
+                    task_id,
+                    user
+                );
+            }
+            task.output_data.remove(Human::CLAIMED_BY);
+            ExecutionDaoFacade::update_task(&mut task)
+        });
+        ExecutionLockService::release_lock(&workflow_id)?;
+        result
+    }
+
+    /// Completes the HUMAN task claimed by the user, the output is the submitted form along with
+    /// the user who completed the task.
+    pub fn complete_human_task(
+        task_id: &InlineStr,
+        user: &str,
+        output: HashMap<InlineStr, Object>,
+    ) -> TegResult<()> {
+        let task = Self::get_pending_human_task(task_id)?;
+        if !Human::get_claimed_by(&task).map_or(false, |x| x.eq(user)) {
+            return fmt_err!(
+                Conflict,
+                "HUMAN task: {} is not claimed by: {}",
+                task_id,
+                user
+            );
+        }
+
+        let mut output_data = output;
+        output_data.insert(Human::CLAIMED_BY.into(), Object::String(user.into()));
+        Self::update_task(TaskResult {
+            workflow_instance_id: task.workflow_instance_id,
+            task_id: task.task_id,
+            reason_for_incompletion: InlineStr::new(),
+            callback_after_seconds: 0,
+            worker_id: user.into(),
+            status: TaskResultStatus::Completed,
+            output_data,
+            output_message: Object::Null,
+            logs: Vec::default(),
+            external_output_payload_storage_path: InlineStr::new(),
+            sub_workflow_id: InlineStr::new(),
+            extend_lease: false,
+        })
+    }
+
     /// Determines if a workflow can be lazily evaluated, if it meets any of these criteria
     /// - The task is NOT a loop task within DO_WHILE
     /// - The task is one of the intermediate tasks in a branch within a FORK_JOIN
@@ -577,7 +1520,7 @@ impl WorkflowExecutor {
 
     pub fn decide_workflow_id(workflow_id: &InlineStr) -> TegResult<()> {
         let start = Instant::now();
-        if !ExecutionLockService::acquire_lock(workflow_id) {
+        if !ExecutionLockService::acquire_lock(workflow_id)? {
             return Ok(());
         }
         let decide_result = match ExecutionDaoFacade::get_workflow_model(workflow_id, true) {
@@ -585,7 +1528,7 @@ impl WorkflowExecutor {
             Err(e) => Err(e),
         };
 
-        ExecutionLockService::release_lock(workflow_id);
+        ExecutionLockService::release_lock(workflow_id)?;
         Monitors::record_workflow_decision_time(start.elapsed().as_millis() as i64);
         decide_result
     }
@@ -616,7 +1559,7 @@ impl WorkflowExecutor {
                     return Ok(());
                 }
 
-                Self::set_task_domains(&workflow, &mut outcome.tasks_to_be_scheduled);
+                Self::set_task_domains(&workflow, &mut outcome.tasks_to_be_scheduled)?;
 
                 let (tasks_to_be_scheduled, tasks_to_be_scheduled_in_outcome) =
                     Self::dedup_and_add_tasks(&mut workflow, outcome.tasks_to_be_scheduled);
@@ -628,12 +1571,12 @@ impl WorkflowExecutor {
                 for task in tasks_to_be_scheduled_in_outcome {
                     let task = from_addr_mut!(task);
                     ExecutionDaoFacade::populate_task_data(task);
-                    if SystemTaskRegistry::is_system_task(&task.task_type)
+                    if SystemTaskRegistry::is_system_task(&task.task_type)?
                         && !task.status.is_terminal()
                     {
                         let workflow_system_task = SystemTaskRegistry::get(&task.task_type)?;
                         debug!("find SystemTask: {}", workflow_system_task.get_task_type());
-                        if !workflow_system_task.value().as_ref().is_async()
+                        if !workflow_system_task.is_async()
                             && workflow_system_task.execute(&mut workflow, task)
                         {
                             outcome.tasks_to_be_updated.push(task);
@@ -655,7 +1598,7 @@ impl WorkflowExecutor {
                 }
 
                 if !outcome.tasks_to_be_updated.is_empty() || !tasks_to_be_scheduled.is_empty() {
-                    ExecutionDaoFacade::update_workflow(&mut workflow);
+                    ExecutionDaoFacade::update_workflow(&mut workflow)?;
                 }
 
                 Ok(())
@@ -696,7 +1639,7 @@ impl WorkflowExecutor {
             if !task.status.is_terminal() {
                 // Cancel the ones which are not completed yet....
                 task.status = TaskStatus::Canceled;
-                if SystemTaskRegistry::is_system_task(&task.task_type) {
+                if SystemTaskRegistry::is_system_task(&task.task_type)? {
                     let workflow_system_task = SystemTaskRegistry::get(&task.task_type)?;
                     if let Err(e) = workflow_system_task.cancel(from_addr_mut!(workflow_ptr), task)
                     {
@@ -715,7 +1658,10 @@ impl WorkflowExecutor {
         }
         if errored_tasks.is_empty() {
             // workflowStatusListener.onWorkflowFinalizedIfEnabled(workflow);
-            if let Err(e) = QueueDao::remove(QueueDao::DECIDER_QUEUE, &workflow.workflow_id) {
+            if let Err(e) = Daos::get()?
+                .queue_dao
+                .remove(DECIDER_QUEUE, &workflow.workflow_id)
+            {
                 error!(
                     "Error removing workflow: {} from decider queue, error: {}",
                     workflow.workflow_id, e
@@ -753,14 +1699,19 @@ impl WorkflowExecutor {
         // put in queue
         let task_queue_name = QueueUtils::get_queue_name_by_task_model(task);
         if task.callback_after_seconds > 0 {
-            QueueDao::push(
+            Daos::get()?.queue_dao.push(
                 &task_queue_name,
                 &task.task_id,
                 task.workflow_priority,
                 task.callback_after_seconds,
-            );
+            )?;
         } else {
-            QueueDao::push(&task_queue_name, &task.task_id, task.workflow_priority, 0);
+            Daos::get()?.queue_dao.push(
+                &task_queue_name,
+                &task.task_id,
+                task.workflow_priority,
+                0,
+            )?;
         }
         debug!(
             "Added task {:?} with priority {} to queue {} with call back seconds {}",
@@ -769,7 +1720,40 @@ impl WorkflowExecutor {
         Ok(())
     }
 
-    fn set_task_domains(workflow: &WorkflowModel, tasks: &mut [TaskModel]) {
+    /// Schedules the first loop over task of the next iteration of the loop task, the rest are
+    /// scheduled by the decider as the tasks of the iteration complete.
+    pub fn schedule_next_iteration(
+        loop_task: &TaskModel,
+        workflow: &mut WorkflowModel,
+    ) -> TegResult<()> {
+        let loop_over_task = loop_task
+            .workflow_task
+            .as_ref()
+            .and_then(|x| x.loop_over.first())
+            .ok_or_else(|| {
+                ErrorCode::IllegalArgument(format!(
+                    "The loop task {} has no loop over tasks",
+                    loop_task.reference_task_name
+                ))
+            })?;
+        let mut scheduled_loop_over_tasks = DeciderService::get_tasks_to_be_scheduled(
+            workflow,
+            loop_over_task,
+            loop_task.retry_count,
+        )?;
+        Self::set_task_domains(workflow, &mut scheduled_loop_over_tasks)?;
+        scheduled_loop_over_tasks.iter_mut().for_each(|x| {
+            TaskUtils::append_iteration(&mut x.reference_task_name, loop_task.iteration);
+            x.iteration = loop_task.iteration;
+        });
+
+        let (tasks_to_be_scheduled, _) =
+            Self::dedup_and_add_tasks(workflow, scheduled_loop_over_tasks);
+        Self::schedule_task(workflow, tasks_to_be_scheduled.as_slice())?;
+        Ok(())
+    }
+
+    fn set_task_domains(workflow: &WorkflowModel, tasks: &mut [TaskModel]) -> TegResult<()> {
         let task_to_domain = &workflow.task_to_domain;
         if !task_to_domain.is_empty() {
             // Step 1: Apply * mapping to all tasks, if present.
@@ -779,17 +1763,17 @@ impl WorkflowExecutor {
                     .and_then(|x| if x.trim().is_empty() { None } else { Some(x) })
             {
                 let domains = domain_str.split(",").collect::<Vec<_>>();
-                tasks.iter_mut().for_each(|x| {
+                for x in tasks.iter_mut() {
                     // Filter out SystemTask
-                    if !SystemTaskRegistry::is_system_task(&x.task_type) {
+                    if !SystemTaskRegistry::is_system_task(&x.task_type)? {
                         // Check which domain worker is polling
                         // Set the task domain
                         x.domain = Self::get_active_domain(&x.task_type, &domains);
                     }
-                });
+                }
                 // Step 2: Override additional mappings.
-                tasks.iter_mut().for_each(|x| {
-                    if !SystemTaskRegistry::is_system_task(&x.task_type) {
+                for x in tasks.iter_mut() {
+                    if !SystemTaskRegistry::is_system_task(&x.task_type)? {
                         if let Some(task_domain_str) = task_to_domain.get(&x.task_type) {
                             x.domain = Self::get_active_domain(
                                 &x.task_type,
@@ -797,9 +1781,10 @@ impl WorkflowExecutor {
                             );
                         }
                     }
-                });
+                }
             }
         }
+        Ok(())
     }
 
     /// Gets the active domain from the list of domains where the task is to be queued. The domain
@@ -858,7 +1843,7 @@ impl WorkflowExecutor {
             let mut system_task = Vec::default();
 
             for task_no_cat in tasks {
-                if SystemTaskRegistry::is_system_task(&task_no_cat.task_type) {
+                if SystemTaskRegistry::is_system_task(&task_no_cat.task_type)? {
                     system_task.push(task_no_cat);
                 } else {
                     tasks_to_be_queued.push(task_no_cat);
@@ -940,7 +1925,7 @@ impl WorkflowExecutor {
         if task.retried_task_id.is_empty() {
             Ok(s)
         } else {
-            if let Some(task) = ExecutionDaoFacade::get_task_model(&task.retried_task_id) {
+            if let Some(task) = ExecutionDaoFacade::get_task_model(&task.retried_task_id)? {
                 Ok(s + Self::get_task_duration(s, &task)?)
             } else {
                 error!(
@@ -959,7 +1944,7 @@ impl WorkflowExecutor {
         reason: InlineStr,
     ) -> TegResult<()> {
         if !workflow.status.is_terminal() {
-            workflow.status = status;
+            workflow.set_status(status);
         }
 
         if let Some(task) = &task {
@@ -986,12 +1971,47 @@ impl WorkflowExecutor {
         Self::terminate_workflow_with_failure_workflow(workflow, reason, failure_workflow)
     }
 
-    fn update_parent_workflow_task(_sub_workflow: &WorkflowModel) {
-        unimplemented!()
+    /// Syncs the status and the output of the sub workflow to the SUB_WORKFLOW task of its
+    /// parent.
+    fn update_parent_workflow_task(sub_workflow: &mut WorkflowModel) -> TegResult<()> {
+        let mut sub_workflow_task = ExecutionDaoFacade::get_task_model(
+            &sub_workflow.parent_workflow_task_id,
+        )?
+        .ok_or_else(|| {
+            ErrorCode::NotFound(format!(
+                "No such task found by id: {}",
+                sub_workflow.parent_workflow_task_id
+            ))
+        })?;
+        if sub_workflow_task.status.is_terminal() {
+            return Ok(());
+        }
+
+        let sub_workflow_system_task = SystemTaskRegistry::get(TaskType::SubWorkflow.as_ref())?;
+        sub_workflow_system_task.execute(sub_workflow, &mut sub_workflow_task);
+        ExecutionDaoFacade::update_task(&mut sub_workflow_task)
     }
 
-    /// Pushes workflow id into the decider queue with a higher priority to expedite evaluation.
-    fn expedite_lazy_workflow_evaluation(_workflow_id: &InlineStr) {
-        unimplemented!()
+    /// Sends an evaluation event of the workflow to expedite its evaluation.
+    fn expedite_lazy_workflow_evaluation(workflow_id: &InlineStr) {
+        match ExecutionDaoFacade::get_workflow_model(workflow_id, true) {
+            Ok(workflow) => {
+                if workflow.status.is_terminal() {
+                    return;
+                }
+                if let Err(e) =
+                    Channel::send_evaluation_event(WorkflowEvaluationEvent::new(workflow))
+                {
+                    error!(
+                        "Error expediting evaluation of workflow: {}, error: {}",
+                        workflow_id, e
+                    );
+                }
+            }
+            Err(e) => error!(
+                "Error loading workflow: {} to expedite its evaluation, error: {}",
+                workflow_id, e
+            ),
+        }
     }
 }
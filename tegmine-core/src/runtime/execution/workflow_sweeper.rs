@@ -0,0 +1,90 @@
+use chrono::Utc;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskType;
+
+use super::WorkflowExecutor;
+use crate::config::Properties;
+use crate::dao::{Daos, DECIDER_QUEUE};
+use crate::model::{TaskStatus, WorkflowModel, WorkflowStatus};
+use crate::runtime::ExecutionDaoFacade;
+
+/// The time (in milliseconds) a poll of the decider queue blocks before checking whether the
+/// engine is shut down.
+const POLL_TIMEOUT_MS: i32 = 100;
+const POLL_COUNT: i32 = 10;
+
+/// Decides the running workflows again when they are due in the decider queue, i.e. after
+/// `Properties::workflow_offset_timeout_sec`, or as soon as the wait of one of their WAIT or HUMAN
+/// tasks elapses.
+pub struct WorkflowSweeper;
+
+impl WorkflowSweeper {
+    /// Pops the workflows which are due in the decider queue and sweeps them.
+    pub fn poll_and_sweep() -> TegResult<()> {
+        match Daos::get()?
+            .queue_dao
+            .pop(DECIDER_QUEUE, POLL_COUNT, POLL_TIMEOUT_MS)
+        {
+            Ok(workflow_ids) => workflow_ids.iter().for_each(Self::sweep),
+            Err(e) => error!("Error polling the decider queue, error: {}", e),
+        }
+        Ok(())
+    }
+
+    pub fn sweep(workflow_id: &InlineStr) {
+        debug!("Running sweeper for workflow {}", workflow_id);
+        if let Err(e) = WorkflowExecutor::decide_workflow_id(workflow_id) {
+            error!(
+                "Error running sweeper for workflow {}, error: {}",
+                workflow_id, e
+            );
+        }
+
+        let result = match ExecutionDaoFacade::get_workflow_model(workflow_id, true) {
+            // a paused workflow is queued again when it is resumed
+            Ok(workflow) if workflow.status == WorkflowStatus::Running => Self::requeue(&workflow),
+            _ => Daos::get().and_then(|daos| daos.queue_dao.remove(DECIDER_QUEUE, workflow_id)),
+        };
+        if let Err(e) = result {
+            error!(
+                "Error updating workflow {} in the decider queue, error: {}",
+                workflow_id, e
+            );
+        }
+    }
+
+    /// Makes the workflow due in the decider queue after
+    /// `WorkflowSweeper::get_workflow_offset_timeout_ms`.
+    pub fn requeue(workflow: &WorkflowModel) -> TegResult<()> {
+        let offset_timeout_ms = Self::get_workflow_offset_timeout_ms(workflow)?;
+        let queue_dao = &Daos::get()?.queue_dao;
+        if !queue_dao.set_unack_timeout(DECIDER_QUEUE, &workflow.workflow_id, offset_timeout_ms)? {
+            queue_dao.push(
+                DECIDER_QUEUE,
+                &workflow.workflow_id,
+                workflow.priority,
+                offset_timeout_ms / 1000,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Gets the time (in milliseconds) after which the workflow has to be decided again, it is
+    /// earlier than the workflow offset timeout when the wait of one of its WAIT or HUMAN tasks
+    /// elapses before.
+    fn get_workflow_offset_timeout_ms(workflow: &WorkflowModel) -> TegResult<i64> {
+        let workflow_offset_timeout_ms = Properties::get()?.workflow_offset_timeout_sec * 1000;
+        let now = Utc::now().timestamp_millis();
+        Ok(workflow
+            .tasks
+            .iter()
+            .filter(|x| {
+                x.status == TaskStatus::InProgress
+                    && x.wait_timeout > 0
+                    && (x.task_type.eq(TaskType::Wait.as_ref())
+                        || x.task_type.eq(TaskType::Human.as_ref()))
+            })
+            .map(|x| (x.wait_timeout - now).max(0))
+            .fold(workflow_offset_timeout_ms, i64::min))
+    }
+}
@@ -1,8 +1,7 @@
-use dashmap::mapref::one::Ref;
 use tegmine_common::prelude::*;
 use tegmine_common::{TaskDef, TaskType, WorkflowDef, WorkflowTask};
 
-use crate::dao::MetadataDao;
+use crate::dao::Daos;
 use crate::metrics::Monitors;
 use crate::MetadataService;
 
@@ -18,8 +17,8 @@ impl MetadataMapperService {
     pub fn lookup_for_workflow_definition(
         name: &InlineStr,
         version: Option<i32>,
-    ) -> TegResult<(Ref<InlineStr, HashMap<i32, WorkflowDef>>, &WorkflowDef)> {
-        if !MetadataService::check_workflow_def_enabled(name) {
+    ) -> TegResult<WorkflowDef> {
+        if !MetadataService::check_workflow_def_enabled(name)? {
             error!(
                 "There is no enabled workflow defined with name {} and version {:?}",
                 name, version
@@ -31,9 +30,9 @@ impl MetadataMapperService {
         }
 
         let potential_def = if let Some(version) = version {
-            Self::lookup_workflow_definition(name, version)
+            Self::lookup_workflow_definition(name, version)?
         } else {
-            Self::lookup_latest_workflow_definition(name)
+            Self::lookup_latest_workflow_definition(name)?
         };
 
         // Check if the workflow definition is valid
@@ -55,28 +54,33 @@ impl MetadataMapperService {
     fn lookup_workflow_definition(
         workflow_name: &InlineStr,
         workflow_version: i32,
-    ) -> Option<(Ref<InlineStr, HashMap<i32, WorkflowDef>>, &WorkflowDef)> {
-        MetadataDao::get_workflow_def(workflow_name, workflow_version)
+    ) -> TegResult<Option<WorkflowDef>> {
+        Daos::get()?
+            .metadata_dao
+            .get_workflow_def(workflow_name, workflow_version)
     }
 
     fn lookup_latest_workflow_definition(
         workflow_name: &InlineStr,
-    ) -> Option<(Ref<InlineStr, HashMap<i32, WorkflowDef>>, &WorkflowDef)> {
-        MetadataDao::get_latest_workflow_def(workflow_name)
+    ) -> TegResult<Option<WorkflowDef>> {
+        Daos::get()?
+            .metadata_dao
+            .get_latest_workflow_def(workflow_name)
     }
 
     // populate_workflow_with_definitions
 
     pub fn populate_task_definitions(workflow_definition: &mut WorkflowDef) -> TegResult<()> {
-        workflow_definition.populate_tasks(Self::populate_workflow_task_with_definition);
+        workflow_definition.populate_tasks(Self::populate_workflow_task_with_definition)?;
 
         Self::check_not_empty_definitions(workflow_definition)
     }
 
-    fn populate_workflow_task_with_definition(workflow_task: &mut WorkflowTask) {
+    fn populate_workflow_task_with_definition(workflow_task: &mut WorkflowTask) -> TegResult<()> {
         if Self::should_populate_task_definition(workflow_task) {
-            workflow_task.task_definition =
-                MetadataDao::get_task_def(&workflow_task.name).map(|x| x.clone());
+            workflow_task.task_definition = Daos::get()?
+                .metadata_dao
+                .get_task_def(&workflow_task.name)?;
             if workflow_task.task_definition.is_none()
                 && workflow_task.type_.eq(TaskType::Simple.as_ref())
             {
@@ -90,12 +94,39 @@ impl MetadataMapperService {
             .as_str()
             .eq(TaskType::SubWorkflow.as_ref())
         {
-            Self::populate_version_for_sub_workflow(workflow_task)
+            Self::populate_version_for_sub_workflow(workflow_task)?;
         }
+        Ok(())
     }
 
-    fn populate_version_for_sub_workflow(_workflow_task: &WorkflowTask) {
-        unimplemented!()
+    /// Pins the sub workflow to the latest version of its definition, unless a version or an
+    /// inline definition is set.
+    fn populate_version_for_sub_workflow(workflow_task: &mut WorkflowTask) -> TegResult<()> {
+        let sub_workflow_params = workflow_task.sub_workflow_param.as_mut().ok_or_else(|| {
+            ErrorCode::IllegalArgument(format!(
+                "Task {} is defined as sub-workflow and is missing subWorkflowParams",
+                workflow_task.name
+            ))
+        })?;
+        if sub_workflow_params.version.is_none()
+            && sub_workflow_params.workflow_definition.is_none()
+        {
+            let sub_workflow_name = &sub_workflow_params.name;
+            let sub_workflow_version = Self::lookup_latest_workflow_definition(sub_workflow_name)?
+                .map(|x| x.version)
+                .ok_or_else(|| {
+                    error!(
+                        "The Task {} defined as a sub-workflow has no workflow definition available",
+                        sub_workflow_name
+                    );
+                    ErrorCode::NotFound(format!(
+                        "The Task {} defined as a sub-workflow has no workflow definition available",
+                        sub_workflow_name
+                    ))
+                })?;
+            sub_workflow_params.version = Some(sub_workflow_version);
+        }
+        Ok(())
     }
 
     fn check_not_empty_definitions(workflow_definition: &WorkflowDef) -> TegResult<()> {
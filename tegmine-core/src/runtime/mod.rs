@@ -7,8 +7,9 @@ mod sync;
 
 pub use dal::ExecutionDaoFacade;
 pub use execution::{
-    Channel, StartWorkflowInput, SystemTaskRegistry, SystemTaskWorkerCoordinator, TaskMapper,
-    TaskMapperContext, TaskMapperRegistry, WorkflowExecutor, WorkflowSystemTask,
+    Channel, EvaluatorRegistry, StartWorkflowInput, SystemTaskRegistry, SystemTaskWorker,
+    SystemTaskWorkerCoordinator, TaskMapper, TaskMapperContext, TaskMapperRegistry,
+    WorkflowExecutor, WorkflowSweeper, WorkflowSystemTask,
 };
 pub use operation::StartWorkflowOperation;
-pub use sync::Lock;
+pub use sync::{FileLock, LocalOnlyLock, Lock};
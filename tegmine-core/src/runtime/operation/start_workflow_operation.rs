@@ -6,7 +6,7 @@ use crate::metrics::Monitors;
 use crate::model::WorkflowModel;
 use crate::runtime::dal::ExecutionDaoFacade;
 use crate::runtime::event::{WorkflowCreationEvent, WorkflowEvaluationEvent};
-use crate::runtime::execution::{StartWorkflowInput, EVAL_EVENT_CHANNEL};
+use crate::runtime::execution::{Channel, StartWorkflowInput};
 use crate::runtime::metadata::MetadataMapperService;
 use crate::utils::{IdGenerator, ParametersUtils};
 
@@ -29,8 +29,6 @@ impl StartWorkflowOperation {
             workflow_def
         } else {
             MetadataMapperService::lookup_for_workflow_definition(&input.name, input.version)?
-                .1
-                .clone()
         };
 
         MetadataMapperService::populate_task_definitions(&mut workflow_definition)?;
@@ -105,7 +103,7 @@ impl StartWorkflowOperation {
     fn create_and_evaluate(mut workflow: WorkflowModel) -> TegResult<()> {
         // executionLockService.acquireLock(workflow.getWorkflowId()))
 
-        ExecutionDaoFacade::create_workflow(&mut workflow);
+        ExecutionDaoFacade::create_workflow(&mut workflow)?;
         debug!(
             "A new instance of workflow: {} created with id: {}",
             &workflow.workflow_definition.name, workflow.workflow_id
@@ -113,9 +111,7 @@ impl StartWorkflowOperation {
 
         ExecutionDaoFacade::populate_workflow_and_task_payload_data(&mut workflow);
 
-        EVAL_EVENT_CHANNEL
-            .0
-            .send(WorkflowEvaluationEvent::new(workflow))?;
+        Channel::send_evaluation_event(WorkflowEvaluationEvent::new(workflow))?;
 
         // executionLockService.releaseLock(workflow.getWorkflowId());
         Ok(())
@@ -0,0 +1,255 @@
+use std::fs::{File, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use fs2::FileExt;
+use tegmine_common::prelude::*;
+
+use super::Lock;
+
+const GUARD_FILE_NAME: &str = ".guard";
+const RETRY_INTERVAL_MS: u64 = 10;
+/// The minimum time between two purges of the expired lease records of the lock directory.
+const PURGE_INTERVAL_MS: i64 = 60000;
+
+/// Implementation of `Lock` based on advisory file locks, so that several engine processes on one
+/// host can safely decide the same workflow.
+///
+/// Each lock is a lease record `<owner>\n<expire at in ms>\n<hold count>` stored in a file of
+/// `lock_dir`, the records are only read and written while holding an exclusive advisory lock on
+/// the guard file of the directory. The expired records, e.g. left by a crashed process, are
+/// purged when a lock is acquired.
+pub struct FileLock {
+    lock_dir: PathBuf,
+    /// advisory locks are held per file handle, the mutex serializes the threads of this process
+    guard: Mutex<File>,
+    last_purge_ms: AtomicI64,
+}
+
+struct LeaseRecord {
+    owner: String,
+    /// 0 if the lease never expires
+    expire_at_ms: i64,
+    hold_count: u32,
+}
+
+impl LeaseRecord {
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines();
+        Some(Self {
+            owner: lines.next()?.to_string(),
+            expire_at_ms: lines.next()?.parse().ok()?,
+            hold_count: lines.next()?.parse().ok()?,
+        })
+    }
+
+    fn is_expired(&self, now_ms: i64) -> bool {
+        self.expire_at_ms > 0 && self.expire_at_ms <= now_ms
+    }
+}
+
+impl FileLock {
+    pub fn new(lock_dir: impl AsRef<Path>) -> TegResult<Self> {
+        let lock_dir = lock_dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&lock_dir)?;
+        let guard = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(lock_dir.join(GUARD_FILE_NAME))?;
+        Ok(Self {
+            lock_dir,
+            guard: Mutex::new(guard),
+            last_purge_ms: AtomicI64::new(0),
+        })
+    }
+
+    /// Identifies the current thread among all the processes sharing the lock directory.
+    fn owner() -> String {
+        format!("{}-{:?}", std::process::id(), std::thread::current().id())
+    }
+
+    /// The record file of a lock, the characters other than `[A-Za-z0-9_-]` of the lock id are
+    /// percent-encoded so that different ids never share a file.
+    fn record_path(&self, lock_id: &str) -> PathBuf {
+        let mut file_name = String::with_capacity(lock_id.len());
+        for x in lock_id.bytes() {
+            if x.is_ascii_alphanumeric() || x == b'-' || x == b'_' {
+                file_name.push(x as char);
+            } else {
+                file_name.push_str(&format!("%{:02X}", x));
+            }
+        }
+        self.lock_dir.join(format!("{}.lock", file_name))
+    }
+
+    fn read_record(path: &Path) -> TegResult<Option<LeaseRecord>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(LeaseRecord::parse(&content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write_record(path: &Path, record: &LeaseRecord) -> TegResult<()> {
+        std::fs::write(
+            path,
+            format!(
+                "{}\n{}\n{}\n",
+                record.owner, record.expire_at_ms, record.hold_count
+            ),
+        )?;
+        Ok(())
+    }
+
+    /// Runs `f` while holding the exclusive lock on the guard file. The advisory lock is released
+    /// with the file handle anyway, so a failure to unlock is only logged.
+    fn guarded<T>(&self, f: impl FnOnce() -> TegResult<T>) -> TegResult<T> {
+        let guard = self.guard.lock().expect("lock poisoned");
+        guard.lock_exclusive()?;
+        let result = f();
+        if let Err(e) = guard.unlock() {
+            error!(
+                "Failed to unlock the guard file of: {}, {}",
+                self.lock_dir.display(),
+                e
+            );
+        }
+        result
+    }
+
+    /// Removes the expired lease records of the lock directory, at most once per
+    /// `PURGE_INTERVAL_MS`. It must be called while holding the guard file.
+    fn purge_expired_records(&self, now_ms: i64) -> TegResult<()> {
+        if now_ms - self.last_purge_ms.load(Ordering::Relaxed) < PURGE_INTERVAL_MS {
+            return Ok(());
+        }
+        self.last_purge_ms.store(now_ms, Ordering::Relaxed);
+
+        for entry in std::fs::read_dir(&self.lock_dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(true, |x| x != "lock") {
+                continue;
+            }
+            if let Some(record) = Self::read_record(&path)? {
+                if record.is_expired(now_ms) {
+                    Self::remove_record(&path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn try_acquire(&self, lock_id: &str, lease_time_ms: i64) -> TegResult<bool> {
+        let path = self.record_path(lock_id);
+        let owner = Self::owner();
+        self.guarded(|| {
+            let now_ms = Utc::now().timestamp_millis();
+            let expire_at_ms = if lease_time_ms > 0 {
+                now_ms + lease_time_ms
+            } else {
+                0
+            };
+
+            let record = match Self::read_record(&path)? {
+                Some(record) if !record.is_expired(now_ms) => {
+                    if record.owner != owner {
+                        return Ok(false);
+                    }
+                    LeaseRecord {
+                        expire_at_ms,
+                        hold_count: record.hold_count + 1,
+                        ..record
+                    }
+                }
+                // free, or the lease of the previous owner expired
+                _ => LeaseRecord {
+                    owner,
+                    expire_at_ms,
+                    hold_count: 1,
+                },
+            };
+            Self::write_record(&path, &record)?;
+            if let Err(e) = self.purge_expired_records(now_ms) {
+                warn!(
+                    "Failed to purge the expired locks of: {}, {}",
+                    self.lock_dir.display(),
+                    e
+                );
+            }
+            Ok(true)
+        })
+    }
+
+    fn try_release(&self, lock_id: &str) -> TegResult<()> {
+        let path = self.record_path(lock_id);
+        let owner = Self::owner();
+        self.guarded(|| match Self::read_record(&path)? {
+            // the lease may have expired and the lock been taken by another owner
+            Some(record) if record.owner == owner => {
+                if record.hold_count > 1 {
+                    Self::write_record(
+                        &path,
+                        &LeaseRecord {
+                            hold_count: record.hold_count - 1,
+                            ..record
+                        },
+                    )
+                } else {
+                    Self::remove_record(&path)
+                }
+            }
+            _ => Ok(()),
+        })
+    }
+
+    fn remove_record(path: &Path) -> TegResult<()> {
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Lock for FileLock {
+    fn acquire_lock_try_and_lease_time(
+        &self,
+        lock_id: &InlineStr,
+        time_to_try_ms: i64,
+        lease_time_ms: i64,
+    ) -> bool {
+        let deadline = Instant::now() + Duration::from_millis(time_to_try_ms.max(0) as u64);
+        loop {
+            match self.try_acquire(lock_id, lease_time_ms) {
+                Ok(true) => return true,
+                Ok(false) => {}
+                Err(e) => {
+                    error!("Failed to acquire file lock: {}, {}", lock_id, e);
+                    return false;
+                }
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            std::thread::sleep((deadline - now).min(Duration::from_millis(RETRY_INTERVAL_MS)));
+        }
+    }
+
+    fn release_lock(&self, lock_id: &InlineStr) {
+        if let Err(e) = self.try_release(lock_id) {
+            error!("Failed to release file lock: {}, {}", lock_id, e);
+        }
+    }
+
+    fn delete_lock(&self, lock_id: &InlineStr) {
+        let path = self.record_path(lock_id);
+        if let Err(e) = self.guarded(|| Self::remove_record(&path)) {
+            error!("Failed to delete file lock: {}, {}", lock_id, e);
+        }
+    }
+}
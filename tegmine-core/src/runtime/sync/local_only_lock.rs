@@ -0,0 +1,112 @@
+use std::sync::{Condvar, Mutex};
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
+
+use tegmine_common::prelude::*;
+
+use super::Lock;
+
+/// In-process implementation of `Lock`, it only protects the workflows decided by one engine.
+#[derive(Default)]
+pub struct LocalOnlyLock {
+    locks: Mutex<HashMap<InlineStr, LockState>>,
+    released: Condvar,
+}
+
+struct LockState {
+    owner: ThreadId,
+    hold_count: u32,
+    /// None if the lease never expires
+    expire_at: Option<Instant>,
+}
+
+impl LockState {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expire_at.map(|x| x <= now).unwrap_or(false)
+    }
+}
+
+impl LocalOnlyLock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn expire_at(now: Instant, lease_time_ms: i64) -> Option<Instant> {
+        if lease_time_ms > 0 {
+            Some(now + Duration::from_millis(lease_time_ms as u64))
+        } else {
+            None
+        }
+    }
+}
+
+impl Lock for LocalOnlyLock {
+    fn acquire_lock_try_and_lease_time(
+        &self,
+        lock_id: &InlineStr,
+        time_to_try_ms: i64,
+        lease_time_ms: i64,
+    ) -> bool {
+        let current = std::thread::current().id();
+        let deadline = Instant::now() + Duration::from_millis(time_to_try_ms.max(0) as u64);
+
+        let mut locks = self.locks.lock().expect("lock poisoned");
+        loop {
+            let now = Instant::now();
+            let wait_until = match locks.get_mut(lock_id) {
+                Some(state) if state.owner == current && !state.is_expired(now) => {
+                    state.hold_count += 1;
+                    state.expire_at = Self::expire_at(now, lease_time_ms);
+                    return true;
+                }
+                Some(state) if !state.is_expired(now) => match state.expire_at {
+                    Some(expire_at) => deadline.min(expire_at),
+                    None => deadline,
+                },
+                _ => {
+                    // free, or the lease of the previous owner expired
+                    locks.insert(
+                        lock_id.clone(),
+                        LockState {
+                            owner: current,
+                            hold_count: 1,
+                            expire_at: Self::expire_at(now, lease_time_ms),
+                        },
+                    );
+                    return true;
+                }
+            };
+
+            if now >= deadline {
+                return false;
+            }
+            locks = self
+                .released
+                .wait_timeout(locks, wait_until - now)
+                .expect("lock poisoned")
+                .0;
+        }
+    }
+
+    fn release_lock(&self, lock_id: &InlineStr) {
+        let mut locks = self.locks.lock().expect("lock poisoned");
+        if let Some(state) = locks.get_mut(lock_id) {
+            // the lease may have expired and the lock been taken by another thread
+            if state.owner != std::thread::current().id() {
+                return;
+            }
+            state.hold_count -= 1;
+            if state.hold_count == 0 {
+                locks.remove(lock_id);
+                self.released.notify_all();
+            }
+        }
+    }
+
+    fn delete_lock(&self, lock_id: &InlineStr) {
+        let mut locks = self.locks.lock().expect("lock poisoned");
+        if locks.remove(lock_id).is_some() {
+            self.released.notify_all();
+        }
+    }
+}
@@ -1,22 +1,25 @@
 use tegmine_common::prelude::*;
 
 /// Interface implemented by a distributed lock client.
-pub struct Lock;
-
-impl Lock {
-    pub fn acquire_lock_try_and_lease_time(
+///
+/// A lock is owned by the thread which acquired it and is reentrant for that thread, every
+/// successful acquire must be paired with a release.
+pub trait Lock: Send + Sync {
+    /// Acquires a re-entrant lock on `lock_id`, blocks for `time_to_try_ms` before giving up.
+    /// The lock is released automatically after `lease_time_ms`, a non positive lease never
+    /// expires.
+    ///
+    /// return true, if successfully acquired.
+    fn acquire_lock_try_and_lease_time(
+        &self,
         lock_id: &InlineStr,
         time_to_try_ms: i64,
         lease_time_ms: i64,
-    ) -> bool {
-        unimplemented!()
-    }
+    ) -> bool;
 
-    pub fn release_lock(lock_id: &InlineStr) {
-        unimplemented!()
-    }
+    /// Release a previously acquired lock.
+    fn release_lock(&self, lock_id: &InlineStr);
 
-    pub fn delete_lock(lock_id: &InlineStr) {
-        unimplemented!()
-    }
+    /// Explicitly cleanup lock resources, if releasing it wouldn't do so.
+    fn delete_lock(&self, lock_id: &InlineStr);
 }
@@ -1,3 +1,7 @@
+mod file_lock;
+mod local_only_lock;
 mod lock;
 
+pub use file_lock::FileLock;
+pub use local_only_lock::LocalOnlyLock;
 pub use lock::Lock;
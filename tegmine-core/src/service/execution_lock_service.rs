@@ -1,8 +1,8 @@
 use tegmine_common::prelude::*;
 
 use crate::config::Properties;
+use crate::engine::EngineContext;
 use crate::metrics::Monitors;
-use crate::runtime::Lock;
 
 pub struct ExecutionLockService;
 
@@ -11,19 +11,20 @@ impl ExecutionLockService {
     /// cannot be acquired. Considering that the workflow decide can be triggered through multiple
     /// entry points, and periodically through the sweeper service, do not block on acquiring the
     /// lock, as the order of execution of decides on a workflow doesn't matter.
-    pub fn acquire_lock(lock_id: &InlineStr) -> bool {
+    pub fn acquire_lock(lock_id: &InlineStr) -> TegResult<bool> {
+        let properties = Properties::get()?;
         Self::acquire_lock_try_and_lease_time(
             lock_id,
-            Properties::default().lock_time_to_try_ms,
-            Properties::default().lock_lease_time_ms,
+            properties.lock_time_to_try_ms,
+            properties.lock_lease_time_ms,
         )
     }
 
-    pub fn acquire_lock_try_time(lock_id: &InlineStr, time_to_try_ms: i64) -> bool {
+    pub fn acquire_lock_try_time(lock_id: &InlineStr, time_to_try_ms: i64) -> TegResult<bool> {
         Self::acquire_lock_try_and_lease_time(
             lock_id,
             time_to_try_ms,
-            Properties::default().lock_lease_time_ms,
+            Properties::get()?.lock_lease_time_ms,
         )
     }
 
@@ -31,16 +32,20 @@ impl ExecutionLockService {
         lock_id: &InlineStr,
         time_to_try_ms: i64,
         lease_time_ms: i64,
-    ) -> bool {
-        if Properties::default().workflow_execution_lock_enabled {
-            if !Lock::acquire_lock_try_and_lease_time(lock_id, time_to_try_ms, lease_time_ms) {
+    ) -> TegResult<bool> {
+        let context = EngineContext::current()?;
+        if context.properties.workflow_execution_lock_enabled {
+            if !context
+                .lock
+                .acquire_lock_try_and_lease_time(lock_id, time_to_try_ms, lease_time_ms)
+            {
                 debug!(
                     "Thread {:?} failed to acquire lock to lockId {}.",
                     std::thread::current().id(),
                     lock_id
                 );
                 Monitors::record_acquire_lock_unsuccessful();
-                return false;
+                return Ok(false);
             }
             debug!(
                 "Thread {:?} acquired lock to lockId {}.",
@@ -48,28 +53,32 @@ impl ExecutionLockService {
                 lock_id
             );
         }
-        true
+        Ok(true)
     }
 
-    pub fn release_lock(lock_id: &InlineStr) {
-        if Properties::default().workflow_execution_lock_enabled {
-            Lock::release_lock(lock_id);
+    pub fn release_lock(lock_id: &InlineStr) -> TegResult<()> {
+        let context = EngineContext::current()?;
+        if context.properties.workflow_execution_lock_enabled {
+            context.lock.release_lock(lock_id);
             debug!(
                 "Thread {:?} released lock to lockId {}.",
                 std::thread::current().id(),
                 lock_id
             );
         }
+        Ok(())
     }
 
-    pub fn delete_lock(lock_id: &InlineStr) {
-        if Properties::default().workflow_execution_lock_enabled {
-            Lock::delete_lock(lock_id);
+    pub fn delete_lock(lock_id: &InlineStr) -> TegResult<()> {
+        let context = EngineContext::current()?;
+        if context.properties.workflow_execution_lock_enabled {
+            context.lock.delete_lock(lock_id);
             debug!(
                 "Thread {:?} deleted lockId {}.",
                 std::thread::current().id(),
                 lock_id
             );
         }
+        Ok(())
     }
 }
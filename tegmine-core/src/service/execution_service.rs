@@ -4,7 +4,7 @@ use tegmine_common::prelude::*;
 use tegmine_common::TaskResult;
 
 use crate::config::Properties;
-use crate::dao::QueueDao;
+use crate::dao::Daos;
 use crate::metrics::Monitors;
 use crate::model::{Task, TaskStatus, Workflow};
 use crate::runtime::{ExecutionDaoFacade, WorkflowExecutor};
@@ -38,23 +38,33 @@ impl ExecutionService {
         );
 
         let mut tasks = Vec::default();
-        for task_id in QueueDao::pop(&queue_name, count, timeout_millis).unwrap_or_else(|e| {
-            error!(
+        let polled_task_ids = Daos::get()?
+            .queue_dao
+            .pop(&queue_name, count, timeout_millis)
+            .unwrap_or_else(|e| {
+                error!(
                 "Error polling for task: {} from worker: {} in domain: {}, count: {}, error: {:?}",
                 task_type, worker_id, domain, count, e
             );
-            Monitors::error(Self::CLASS_NAME, "taskPoll");
-            Monitors::record_task_poll_error(
-                task_type,
-                domain,
-                e.code().numtoa_str(10, &mut [0; 16]),
-            );
-            Vec::default()
-        }) {
-            let task_model = ExecutionDaoFacade::get_task_model(&task_id);
+                Monitors::error(Self::CLASS_NAME, "taskPoll");
+                Monitors::record_task_poll_error(
+                    task_type,
+                    domain,
+                    e.code().numtoa_str(10, &mut [0; 16]),
+                );
+                Vec::default()
+            });
+        for task_id in polled_task_ids {
+            let task_model = match ExecutionDaoFacade::get_task_model(&task_id) {
+                Ok(task_model) => task_model,
+                Err(e) => {
+                    catch(e, &queue_name, task_type, domain, &task_id);
+                    continue;
+                }
+            };
             if task_model.is_none() || task_model.as_ref().expect("not none").status.is_terminal() {
                 // Remove taskId(s) without a valid Task/terminal state task from the queue
-                if let Err(e) = QueueDao::remove(&queue_name, &task_id) {
+                if let Err(e) = Daos::get()?.queue_dao.remove(&queue_name, &task_id) {
                     catch(e, &queue_name, task_type, domain, &task_id);
                 } else {
                     debug!("Removed task: {} from the queue: {}", task_id, queue_name);
@@ -63,13 +73,13 @@ impl ExecutionService {
             }
             let mut task_model = task_model.expect("not none");
 
-            if ExecutionDaoFacade::exceeds_in_progress_limit(&task_model) {
+            if ExecutionDaoFacade::exceeds_in_progress_limit(&task_model)? {
                 // Postpone this message, so that it would be available for poll again.
-                if let Err(e) = QueueDao::postpone(
+                if let Err(e) = Daos::get()?.queue_dao.postpone(
                     &queue_name,
                     &task_id,
                     task_model.workflow_priority,
-                    Properties::default().task_execution_postpone_duration_sec,
+                    Properties::get()?.task_execution_postpone_duration_sec,
                 ) {
                     catch(e, &queue_name, task_type, domain, &task_id);
                 } else {
@@ -77,28 +87,25 @@ impl ExecutionService {
                         "Postponed task: {} in queue: {} by {} seconds",
                         task_id,
                         queue_name,
-                        Properties::default().task_execution_postpone_duration_sec
+                        Properties::get()?.task_execution_postpone_duration_sec
                     );
                 }
                 continue;
             }
 
-            let task_def = task_model.get_task_definition();
-            if task_model.rate_limit_per_frequency > 0
-                && ExecutionDaoFacade::exceeds_rate_limit_per_frequency(&task_model, task_def)
-            {
+            if ExecutionDaoFacade::exceeds_rate_limit_per_frequency(&task_model)? {
                 // Postpone this message, so that it would be available for poll again.
-                if let Err(e) = QueueDao::postpone(
+                if let Err(e) = Daos::get()?.queue_dao.postpone(
                     &queue_name,
                     &task_id,
                     task_model.workflow_priority,
-                    Properties::default().task_execution_postpone_duration_sec,
+                    Properties::get()?.task_execution_postpone_duration_sec,
                 ) {
                     catch(e, &queue_name, task_type, domain, &task_id);
                 } else {
                     debug!(
-                        "RateLimit Execution limited for {}:{}, limit:{}",
-                        task_id, task_model.task_def_name, task_model.rate_limit_per_frequency
+                        "RateLimit Execution limited for {}:{}",
+                        task_id, task_model.task_def_name
                     );
                 }
                 continue;
@@ -138,12 +145,14 @@ impl ExecutionService {
                     domain,
                     e.code().numtoa_str(10, &mut [0; 16]),
                 );
-                let _ = QueueDao::postpone(
-                    queue_name,
-                    &task_id,
-                    0,
-                    Properties::default().task_execution_postpone_duration_sec,
-                );
+                let _ = Daos::get().and_then(|daos| {
+                    daos.queue_dao.postpone(
+                        queue_name,
+                        &task_id,
+                        0,
+                        Properties::get()?.task_execution_postpone_duration_sec,
+                    )
+                });
             }
         }
 
@@ -159,27 +168,57 @@ impl ExecutionService {
         WorkflowExecutor::update_task(task_result)
     }
 
-    pub fn get_task(task_id: &InlineStr) -> Option<Task> {
+    pub fn get_pending_human_tasks(user: &str, groups: &[&str]) -> TegResult<Vec<Task>> {
+        Ok(WorkflowExecutor::get_pending_human_tasks(user, groups)?
+            .into_iter()
+            .map(|x| x.to_task())
+            .collect())
+    }
+
+    pub fn claim_human_task(task_id: &InlineStr, user: &str, groups: &[&str]) -> TegResult<()> {
+        WorkflowExecutor::claim_human_task(task_id, user, groups)
+    }
+
+    pub fn release_human_task(task_id: &InlineStr, user: &str) -> TegResult<()> {
+        WorkflowExecutor::release_human_task(task_id, user)
+    }
+
+    pub fn complete_human_task(
+        task_id: &InlineStr,
+        user: &str,
+        output: HashMap<InlineStr, Object>,
+    ) -> TegResult<()> {
+        WorkflowExecutor::complete_human_task(task_id, user, output)
+    }
+
+    pub fn get_task(task_id: &InlineStr) -> TegResult<Option<Task>> {
         ExecutionDaoFacade::get_task(task_id)
     }
 
-    pub fn ack_task_received_by_task_id(task_id: &InlineStr) -> bool {
-        let task = Self::get_task(task_id);
-        task.map_or(false, |v| Self::ack_task_received(&v))
+    pub fn ack_task_received_by_task_id(task_id: &InlineStr) -> TegResult<bool> {
+        if let Some(task) = Self::get_task(task_id)? {
+            Self::ack_task_received(&task)
+        } else {
+            Ok(false)
+        }
     }
 
-    pub fn ack_task_received(task: &Task) -> bool {
-        QueueDao::ack(
+    pub fn ack_task_received(task: &Task) -> TegResult<bool> {
+        Daos::get()?.queue_dao.ack(
             &QueueUtils::get_queue_name_by_task_model(&task.inner),
             &task.inner.task_id,
         )
     }
 
+    pub fn remove_workflow(workflow_id: &str, archive_workflow: bool) -> TegResult<()> {
+        ExecutionDaoFacade::remove_workflow(&workflow_id.into(), archive_workflow)
+    }
+
     pub fn get_execution_status(
         workflow_id: &str,
         include_tasks: bool,
     ) -> TegResult<(WorkflowStatus, Option<Workflow>)> {
-        if let Some(status) = ExecutionDaoFacade::get_workflow_status(&workflow_id.into()) {
+        if let Some(status) = ExecutionDaoFacade::get_workflow_status(&workflow_id.into())? {
             if status.is_terminal() {
                 Ok((
                     status,
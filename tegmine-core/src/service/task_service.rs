@@ -48,4 +48,45 @@ impl TaskService {
         );
         Ok(task_result.task_id.to_string())
     }
+
+    /// Gets the pending HUMAN tasks of a user, the tasks assigned to the user or offered to one
+    /// of the groups the user is a member of.
+    pub fn get_pending_human_tasks(user: &str, groups: &[&str]) -> TegResult<Vec<Task>> {
+        let tasks = ExecutionService::get_pending_human_tasks(user, groups)?;
+        debug!(
+            "The HUMAN tasks {:?} are pending for user: {} of groups: {:?}",
+            tasks
+                .iter()
+                .map(|x| x.inner.task_id.clone())
+                .collect::<Vec<_>>(),
+            user,
+            groups
+        );
+        Ok(tasks)
+    }
+
+    /// Claims a HUMAN task for a user, a member of the groups.
+    pub fn claim_human_task(task_id: &str, user: &str, groups: &[&str]) -> TegResult<()> {
+        ExecutionService::claim_human_task(&task_id.into(), user, groups)?;
+        debug!("HUMAN task: {} claimed by user: {}", task_id, user);
+        Ok(())
+    }
+
+    /// Releases a HUMAN task claimed by a user.
+    pub fn release_human_task(task_id: &str, user: &str) -> TegResult<()> {
+        ExecutionService::release_human_task(&task_id.into(), user)?;
+        debug!("HUMAN task: {} released by user: {}", task_id, user);
+        Ok(())
+    }
+
+    /// Completes a HUMAN task claimed by a user with the submitted form as output.
+    pub fn complete_human_task(
+        task_id: &str,
+        user: &str,
+        output: HashMap<InlineStr, Object>,
+    ) -> TegResult<()> {
+        ExecutionService::complete_human_task(&task_id.into(), user, output)?;
+        debug!("HUMAN task: {} completed by user: {}", task_id, user);
+        Ok(())
+    }
 }
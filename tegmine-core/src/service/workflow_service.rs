@@ -1,9 +1,11 @@
 use tegmine_common::prelude::*;
-use tegmine_common::{RerunWorkflowRequest, SkipTaskRequest, StartWorkflowRequest};
+use tegmine_common::{
+    RerunWorkflowRequest, SkipTaskRequest, StartWorkflowRequest, TaskResultStatus,
+};
 
 use super::ExecutionService;
 use crate::model::Workflow;
-use crate::runtime::StartWorkflowOperation;
+use crate::runtime::{StartWorkflowOperation, WorkflowExecutor};
 use crate::WorkflowStatus;
 
 pub struct WorkflowService;
@@ -17,6 +19,27 @@ impl WorkflowService {
         StartWorkflowOperation::execute(start_workflow_request.into())
     }
 
+    /// Signals the pending WAIT, HUMAN or async complete task of a workflow by its reference
+    /// name, the task is updated with the status and output, and the workflow is decided.
+    ///
+    /// return the id of the signaled task.
+    pub fn signal(
+        workflow_id: &str,
+        task_reference_name: &str,
+        status: TaskResultStatus,
+        output: HashMap<InlineStr, Object>,
+    ) -> TegResult<InlineStr> {
+        let task_id =
+            WorkflowExecutor::signal(&workflow_id.into(), task_reference_name, status, output)?;
+        debug!(
+            "Task: {} of workflow: {} signaled with status: {}",
+            task_reference_name,
+            workflow_id,
+            status.as_ref()
+        );
+        Ok(task_id)
+    }
+
     /// Lists workflows for the given correlation id.
 
     #[allow(unused)]
@@ -39,11 +62,12 @@ impl WorkflowService {
         ExecutionService::get_execution_status(workflow_id, include_tasks)
     }
 
-    /// Removes the workflow from the system.
-
-    #[allow(unused)]
-    fn delete_workflow(&self, workflow_id: &str, archive_workflow: bool) {
-        unimplemented!()
+    /// Removes the workflow from the system, the execution record of the terminal workflow is
+    /// written to the archive directory first if `archive_workflow` is set.
+    pub fn delete_workflow(workflow_id: &str, archive_workflow: bool) -> TegResult<()> {
+        ExecutionService::remove_workflow(workflow_id, archive_workflow)?;
+        debug!("Workflow: {} deleted", workflow_id);
+        Ok(())
     }
 
     /// Retrieves all the running workflows.
@@ -68,50 +92,62 @@ impl WorkflowService {
     }
 
     /// Pauses the workflow given a workflow_id.
-
-    #[allow(unused)]
-    fn pause_workflow(&self, workflow_id: &str) {
-        unimplemented!()
+    pub fn pause_workflow(workflow_id: &str) -> TegResult<()> {
+        WorkflowExecutor::pause_workflow(&workflow_id.into())?;
+        debug!("Workflow: {} paused", workflow_id);
+        Ok(())
     }
 
     /// Resumes the workflow.
-
-    #[allow(unused)]
-    fn resume_workflow(&self, workflow_id: &str) {
-        unimplemented!()
+    pub fn resume_workflow(workflow_id: &str) -> TegResult<()> {
+        WorkflowExecutor::resume_workflow(&workflow_id.into())?;
+        debug!("Workflow: {} resumed", workflow_id);
+        Ok(())
     }
 
     /// Skips a given task from a current running workflow.
-
-    #[allow(unused)]
-    fn skip_task_from_workflow(
-        &self,
+    pub fn skip_task_from_workflow(
         workflow_id: &str,
         task_reference_name: &str,
         skip_task_request: SkipTaskRequest,
-    ) {
-        unimplemented!()
+    ) -> TegResult<()> {
+        WorkflowExecutor::skip_task_from_workflow(
+            &workflow_id.into(),
+            task_reference_name,
+            skip_task_request,
+        )?;
+        debug!(
+            "Task: {} of workflow: {} skipped",
+            task_reference_name, workflow_id
+        );
+        Ok(())
     }
 
     /// Reruns the workflow from a specific task.
-
-    #[allow(unused)]
-    fn rerun_workflow(&self, workflow_id: &str, request: RerunWorkflowRequest) {
-        unimplemented!()
+    ///
+    /// return the id of the rerun workflow.
+    pub fn rerun_workflow(
+        workflow_id: &str,
+        mut request: RerunWorkflowRequest,
+    ) -> TegResult<InlineStr> {
+        request.re_run_from_workflow_id = workflow_id.into();
+        let workflow_id = WorkflowExecutor::rerun(request)?;
+        debug!("Workflow: {} rerun", workflow_id);
+        Ok(workflow_id)
     }
 
     /// Restarts a completed workflow.
-
-    #[allow(unused)]
-    fn restart_workflow(&self, workflow_id: &str, use_latest_definitions: bool) {
-        unimplemented!()
+    pub fn restart_workflow(workflow_id: &str, use_latest_definitions: bool) -> TegResult<()> {
+        WorkflowExecutor::restart_workflow(&workflow_id.into(), use_latest_definitions)?;
+        debug!("Workflow: {} restarted", workflow_id);
+        Ok(())
     }
 
     /// Retries the last failed task.
-
-    #[allow(unused)]
-    fn retry_workflow(&self, workflow_id: &str, resume_sub_workflow_tasks: bool) {
-        unimplemented!()
+    pub fn retry_workflow(workflow_id: &str, resume_sub_workflow_tasks: bool) -> TegResult<()> {
+        WorkflowExecutor::retry_workflow(&workflow_id.into(), resume_sub_workflow_tasks)?;
+        debug!("Workflow: {} retried", workflow_id);
+        Ok(())
     }
     /// Resets callback times of all non-terminal SIMPLE tasks to 0.
 
@@ -121,10 +157,10 @@ impl WorkflowService {
     }
 
     /// Terminate workflow execution.
-
-    #[allow(unused)]
-    fn terminate_workflow(&self, workflow_id: &str, reason: &str) {
-        unimplemented!()
+    pub fn terminate_workflow(workflow_id: &str, reason: &str) -> TegResult<()> {
+        WorkflowExecutor::terminate_workflow(&workflow_id.into(), reason.into())?;
+        debug!("Workflow: {} terminated", workflow_id);
+        Ok(())
     }
 
     // fn search_workflows
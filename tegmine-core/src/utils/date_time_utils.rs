@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tegmine_common::prelude::*;
+
+static DURATION_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^\s*(?:(\d+)\s*(?:days?|d))?\s*(?:(\d+)\s*(?:hours?|hrs?|h))?\s*(?:(\d+)\s*(?:minutes?|mins?|m))?\s*(?:(\d+)\s*(?:seconds?|secs?|s))?\s*$",
+    )
+    .expect("invalid duration pattern")
+});
+
+pub struct DateTimeUtils;
+
+impl DateTimeUtils {
+    const DATE_TIME_FORMATS: [&'static str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+    const DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+    /// Parses a duration like `30s`, `2h` or `1d 2hours 30 mins`.
+    ///
+    /// return the duration in seconds
+    pub fn parse_duration(text: &str) -> TegResult<i64> {
+        let captures = match DURATION_PATTERN.captures(text) {
+            Some(captures) if !text.trim().is_empty() => captures,
+            _ => return fmt_err!(IllegalArgument, "Not valid duration: {}", text),
+        };
+
+        let mut seconds = 0;
+        for (i, unit_seconds) in [(1, 24 * 60 * 60), (2, 60 * 60), (3, 60), (4, 1)] {
+            if let Some(value) = captures.get(i) {
+                let value = value.as_str().parse::<i64>().map_err(|_| {
+                    ErrorCode::IllegalArgument(format!("Not valid duration: {}", text))
+                })?;
+                seconds += value * unit_seconds;
+            }
+        }
+        Ok(seconds)
+    }
+
+    /// Parses a point in time like `2022-12-25 09:00 America/Los_Angeles`, the time zone is UTC
+    /// when omitted. RFC 3339 timestamps are accepted as well.
+    ///
+    /// return the epoch time in milliseconds
+    pub fn parse_date(text: &str) -> TegResult<i64> {
+        let text = text.trim();
+        if let Ok(date_time) = DateTime::parse_from_rfc3339(text) {
+            return Ok(date_time.timestamp_millis());
+        }
+
+        // the time zone, if any, is the last part of the text
+        let (local, tz) = match text.rsplit_once(' ') {
+            Some((local, tz)) if !tz.contains(':') && !tz.contains('-') => {
+                let tz = Tz::from_str(tz).map_err(|_| {
+                    ErrorCode::IllegalArgument(format!("Not valid time zone: {}", tz))
+                })?;
+                (local.trim(), tz)
+            }
+            _ => (text, Tz::UTC),
+        };
+
+        let local = Self::DATE_TIME_FORMATS
+            .iter()
+            .find_map(|x| NaiveDateTime::parse_from_str(local, x).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(local, Self::DATE_FORMAT)
+                    .ok()
+                    .and_then(|x| x.and_hms_opt(0, 0, 0))
+            })
+            .ok_or_else(|| ErrorCode::IllegalArgument(format!("Not valid date time: {}", text)))?;
+        tz.from_local_datetime(&local)
+            .earliest()
+            .map(|x| x.with_timezone(&Utc).timestamp_millis())
+            .ok_or_else(|| {
+                ErrorCode::IllegalArgument(format!("Not valid date time in time zone: {}", text))
+            })
+    }
+}
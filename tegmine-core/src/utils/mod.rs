@@ -1,6 +1,8 @@
+mod date_time_utils;
 mod id_generator;
 mod parameters_utils;
 mod queue_utils;
+mod runtime_utils;
 // mod v8_runtime;
 // mod v8_utils;
 mod deno_utils;
@@ -8,8 +10,10 @@ mod semaphore_util;
 
 // pub use v8_runtime::RuntimeEngine;
 // pub use v8_utils::V8Utils;
+pub use date_time_utils::DateTimeUtils;
 pub use deno_utils::DenoUtils;
 pub use id_generator::IdGenerator;
 pub use parameters_utils::ParametersUtils;
 pub use queue_utils::QueueUtils;
+pub use runtime_utils::RuntimeUtils;
 pub use semaphore_util::SemaphoreUtil;
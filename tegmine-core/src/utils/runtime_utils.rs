@@ -0,0 +1,29 @@
+use std::future::Future;
+
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+
+pub struct RuntimeUtils;
+
+impl RuntimeUtils {
+    /// Blocks on a future of a dedicated runtime. The engine may be called from an async context,
+    /// e.g. `TegmineEngine::async_execute_workflow`, where blocking on a runtime directly panics.
+    pub fn block_on<F>(runtime: &Runtime, future: F) -> F::Output
+    where
+        F: Future + Send,
+        F::Output: Send,
+    {
+        match Handle::try_current() {
+            Err(_) => runtime.block_on(future),
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| runtime.block_on(future))
+            }
+            // the worker of a current thread runtime can not be blocked in place, the future is
+            // blocked on from another thread instead
+            Ok(_) => std::thread::scope(|s| {
+                s.spawn(|| runtime.block_on(future))
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            }),
+        }
+    }
+}
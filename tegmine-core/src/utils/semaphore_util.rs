@@ -1,36 +1,51 @@
-use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
 
 pub struct SemaphoreUtil {
     num_slots: i32,
-    available_slots: AtomicI32,
+    available_slots: Mutex<i32>,
+    /// notified when slots are released
+    released: Condvar,
 }
 
 impl SemaphoreUtil {
     pub fn new(num_slots: i32) -> Self {
         Self {
             num_slots,
-            available_slots: AtomicI32::new(num_slots),
+            available_slots: Mutex::new(num_slots),
+            released: Condvar::new(),
         }
     }
 
     pub fn complete_processing(&self, num_slots: i32) {
-        self.available_slots.fetch_add(num_slots, Ordering::SeqCst);
+        *self.available_slots.lock().expect("lock poisoned") += num_slots;
+        self.released.notify_all();
     }
 
     pub fn available_slots(&self) -> i32 {
-        self.available_slots.load(Ordering::SeqCst)
+        *self.available_slots.lock().expect("lock poisoned")
+    }
+
+    /// Blocks until at least one slot is available, or `timeout_ms` elapses.
+    ///
+    /// return the number of available slots
+    pub fn wait_available_slots(&self, timeout_ms: u64) -> i32 {
+        let available_slots = self.available_slots.lock().expect("lock poisoned");
+        *self
+            .released
+            .wait_timeout_while(available_slots, Duration::from_millis(timeout_ms), |x| {
+                *x <= 0
+            })
+            .expect("lock poisoned")
+            .0
     }
 
     pub fn acquire_slots(&self, num_slots: i32) -> bool {
-        let current = self.available_slots.load(Ordering::SeqCst);
-        if current < num_slots {
+        let mut available_slots = self.available_slots.lock().expect("lock poisoned");
+        if *available_slots < num_slots {
             return false;
         }
-        return current
-            == self.available_slots.compare_and_swap(
-                current,
-                current - num_slots,
-                Ordering::SeqCst,
-            );
+        *available_slots -= num_slots;
+        true
     }
 }
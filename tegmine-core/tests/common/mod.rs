@@ -0,0 +1,57 @@
+//! The fixtures shared by the integration tests.
+
+use std::time::{Duration, Instant};
+
+use tegmine_common::prelude::*;
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{TegmineEngine, WorkflowModel, WorkflowService};
+
+/// Starts the workflow of the request, and evaluates it until there is nothing left to decide.
+pub fn start_workflow(
+    engine: &TegmineEngine,
+    start_workflow_request: serde_json::Value,
+) -> InlineStr {
+    let start_workflow_request: StartWorkflowRequest = start_workflow_request
+        .try_into()
+        .expect("parse StartWorkflowRequest failed");
+
+    let _guard = engine.enter();
+    let workflow_id =
+        WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
+    evaluate(engine);
+    workflow_id
+}
+
+/// Evaluates the workflows of the engine until there is nothing left to decide.
+pub fn evaluate(engine: &TegmineEngine) {
+    while engine.evaluate_once().is_ok() {}
+}
+
+/// Evaluates the workflow until it is terminal, or gives up after 10 seconds. The async system
+/// tasks are executed by the system task workers in the background.
+#[allow(dead_code)]
+pub fn wait_for_terminal(engine: &TegmineEngine, workflow_id: &InlineStr) -> WorkflowModel {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        evaluate(engine);
+        let workflow = get_workflow(engine, workflow_id);
+        if workflow.status.is_terminal() || Instant::now() > deadline {
+            return workflow;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Gets the workflow with its tasks, `None` if it has been removed.
+pub fn find_workflow(engine: &TegmineEngine, workflow_id: &InlineStr) -> Option<WorkflowModel> {
+    engine
+        .daos()
+        .execution_dao
+        .get_workflow(workflow_id)
+        .expect("get_workflow failed")
+}
+
+/// Gets the workflow with its tasks.
+pub fn get_workflow(engine: &TegmineEngine, workflow_id: &InlineStr) -> WorkflowModel {
+    find_workflow(engine, workflow_id).expect("not none")
+}
@@ -0,0 +1,72 @@
+use serde_json::json;
+use tegmine_common::{TaskDef, WorkflowTask};
+use tegmine_core::{
+    ConcurrentExecutionLimitDao, ExecutionDao, InMemoryExecutionDao, SqliteDataSource,
+    SqliteExecutionDao, TaskModel, TaskStatus,
+};
+
+fn limited_task(task_id: &str) -> TaskModel {
+    let mut task_def = TaskDef::new("limited_task");
+    task_def.concurrent_exec_limit = Some(1);
+    let mut workflow_task = WorkflowTask::try_from(&json!({
+        "name": "limited_task",
+        "taskReferenceName": task_id,
+        "type": "SIMPLE",
+        "inputParameters": {}
+    }))
+    .expect("parse workflow task failed");
+    workflow_task.task_definition = Some(task_def);
+
+    let mut task = TaskModel::new(TaskStatus::Scheduled);
+    task.task_id = task_id.into();
+    task.task_type = "SIMPLE".into();
+    task.task_def_name = "limited_task".into();
+    task.reference_task_name = task_id.into();
+    task.workflow_instance_id = "workflow_1".into();
+    task.workflow_task = Some(workflow_task);
+    task
+}
+
+fn check_exceeds_limit<T: ExecutionDao + ConcurrentExecutionLimitDao>(dao: &T) {
+    let mut task_1 = limited_task("task_1");
+    let mut task_2 = limited_task("task_2");
+    dao.create_tasks(&mut [&mut task_1, &mut task_2])
+        .expect("create tasks failed");
+
+    // the tasks get the slot in order of creation, whichever is checked first
+    assert!(dao.exceeds_limit(&task_2));
+    assert!(!dao.exceeds_limit(&task_1));
+
+    task_1.status = TaskStatus::InProgress;
+    dao.update_task(&mut task_1).expect("update task failed");
+    assert!(dao.exceeds_limit(&task_2));
+
+    // a task keeps its place until it reaches a terminal status
+    task_1.status = TaskStatus::Scheduled;
+    dao.update_task(&mut task_1).expect("update task failed");
+    assert!(dao.exceeds_limit(&task_2));
+    assert!(!dao.exceeds_limit(&task_1));
+
+    // the slot is released once the task reaches a terminal status
+    task_1.status = TaskStatus::Completed;
+    dao.update_task(&mut task_1).expect("update task failed");
+    assert!(!dao.exceeds_limit(&task_2));
+
+    // tasks without a concurrency limit are never limited
+    let mut unlimited_task = limited_task("task_3");
+    unlimited_task.workflow_task = None;
+    assert!(!dao.exceeds_limit(&unlimited_task));
+}
+
+#[test]
+fn in_memory_concurrent_execution_limit() {
+    check_exceeds_limit(&InMemoryExecutionDao::new());
+}
+
+#[test]
+fn sqlite_concurrent_execution_limit() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let url = format!("sqlite://{}", dir.path().join("tegmine.db").display());
+    let data_source = SqliteDataSource::connect(&url).expect("connect sqlite failed");
+    check_exceeds_limit(&SqliteExecutionDao::new(data_source));
+}
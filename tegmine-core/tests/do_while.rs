@@ -0,0 +1,120 @@
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowService, WorkflowStatus};
+
+fn do_while_request(loop_input_parameters: serde_json::Value) -> StartWorkflowRequest {
+    let start_workflow_request = serde_json::json!({
+        "name": "do_while_workflow",
+        "workflowDef": {
+            "name": "do_while_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "loop",
+                    "taskReferenceName": "loop",
+                    "type": "DO_WHILE",
+                    "inputParameters": loop_input_parameters,
+                    "loopCondition": "$.loop['iteration'] < 3",
+                    "loopOver": [
+                        {
+                            "name": "set_counter",
+                            "taskReferenceName": "set_counter",
+                            "type": "SET_VARIABLE",
+                            "inputParameters": {
+                                "counter": "${loop.output.iteration}"
+                            }
+                        }
+                    ]
+                }
+            ],
+            "outputParameters": {
+                "counter": "${workflow.variables.counter}"
+            }
+        },
+        "input": {}
+    });
+    start_workflow_request
+        .try_into()
+        .expect("parse StartWorkflowRequest failed")
+}
+
+#[test]
+fn do_while_runs_until_condition_is_false() {
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
+    let workflow_id = WorkflowService::start_workflow(do_while_request(serde_json::json!({})))
+        .expect("start_workflow failed");
+    while engine.evaluate_once().is_ok() {}
+
+    let workflow = engine
+        .daos()
+        .execution_dao
+        .get_workflow(&workflow_id)
+        .expect("get_workflow failed")
+        .expect("not none");
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let loop_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.reference_task_name.eq("loop"))
+        .expect("no DO_WHILE task");
+    assert_eq!(loop_task.status, TaskStatus::Completed);
+    assert_eq!(loop_task.iteration, 3);
+    for iteration in ["1", "2", "3"] {
+        assert!(
+            loop_task.output_data.contains_key(iteration),
+            "no output of iteration {}",
+            iteration
+        );
+    }
+
+    let iterations = workflow
+        .tasks
+        .iter()
+        .filter(|x| x.reference_task_name.starts_with("set_counter__"))
+        .map(|x| x.reference_task_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        iterations,
+        vec!["set_counter__1", "set_counter__2", "set_counter__3"]
+    );
+    assert_eq!(
+        workflow.output.get("counter").expect("no output").to_json(),
+        serde_json::json!(3)
+    );
+}
+
+#[test]
+fn do_while_keeps_last_n_iterations() {
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
+    let workflow_id = WorkflowService::start_workflow(do_while_request(serde_json::json!({
+        "keepLastN": 2
+    })))
+    .expect("start_workflow failed");
+    while engine.evaluate_once().is_ok() {}
+
+    let workflow = engine
+        .daos()
+        .execution_dao
+        .get_workflow(&workflow_id)
+        .expect("get_workflow failed")
+        .expect("not none");
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let loop_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.reference_task_name.eq("loop"))
+        .expect("no DO_WHILE task");
+    assert!(!loop_task.output_data.contains_key("1"));
+    assert!(loop_task.output_data.contains_key("2"));
+    assert!(loop_task.output_data.contains_key("3"));
+
+    // the tasks of the removed iteration are removed as well
+    assert_eq!(workflow.tasks.len(), 3);
+    assert!(workflow
+        .tasks
+        .iter()
+        .all(|x| !x.reference_task_name.eq("set_counter__1")));
+}
@@ -0,0 +1,175 @@
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowService, WorkflowStatus};
+
+fn fork_join_dynamic_request(
+    dynamic_tasks: serde_json::Value,
+    dynamic_tasks_input: serde_json::Value,
+) -> StartWorkflowRequest {
+    let start_workflow_request = serde_json::json!({
+        "name": "fork_join_dynamic_workflow",
+        "workflowDef": {
+            "name": "fork_join_dynamic_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "prepare",
+                    "taskReferenceName": "prepare",
+                    "type": "SET_VARIABLE",
+                    "inputParameters": {
+                        "dynamicTasks": "${workflow.input.dynamicTasks}",
+                        "dynamicTasksInput": "${workflow.input.dynamicTasksInput}"
+                    }
+                },
+                {
+                    "name": "fan_out",
+                    "taskReferenceName": "fan_out",
+                    "type": "FORK_JOIN_DYNAMIC",
+                    "dynamicForkTasksParam": "dynamicTasks",
+                    "dynamicForkTasksInputParamName": "dynamicTasksInput",
+                    "inputParameters": {
+                        "dynamicTasks": "${workflow.variables.dynamicTasks}",
+                        "dynamicTasksInput": "${workflow.variables.dynamicTasksInput}"
+                    }
+                },
+                {
+                    "name": "fan_in",
+                    "taskReferenceName": "fan_in",
+                    "type": "JOIN",
+                    "inputParameters": {}
+                }
+            ]
+        },
+        "input": {
+            "dynamicTasks": dynamic_tasks,
+            "dynamicTasksInput": dynamic_tasks_input
+        }
+    });
+    start_workflow_request
+        .try_into()
+        .expect("parse StartWorkflowRequest failed")
+}
+
+#[test]
+fn fork_join_dynamic_schedules_runtime_branches() {
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
+    let start_workflow_request = fork_join_dynamic_request(
+        serde_json::json!([
+            {
+                "name": "branch_a",
+                "taskReferenceName": "branch_a",
+                "type": "SET_VARIABLE",
+                "inputParameters": {
+                    "a": "unset"
+                }
+            },
+            {
+                "name": "branch_b",
+                "taskReferenceName": "branch_b",
+                "type": "SET_VARIABLE",
+                "inputParameters": {
+                    "b": "unset"
+                }
+            }
+        ]),
+        serde_json::json!({
+            "branch_a": { "a": "alpha" },
+            "branch_b": { "b": "beta" }
+        }),
+    );
+    let workflow_id =
+        WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
+    while engine.evaluate_once().is_ok() {}
+
+    let workflow = engine
+        .daos()
+        .execution_dao
+        .get_workflow(&workflow_id)
+        .expect("get_workflow failed")
+        .expect("not none");
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let fork_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.task_type.eq("FORK"))
+        .expect("no FORK task");
+    assert_eq!(fork_task.status, TaskStatus::Completed);
+    assert_eq!(fork_task.reference_task_name.as_str(), "fan_out");
+
+    for (task_ref_name, variable, value) in [("branch_a", "a", "alpha"), ("branch_b", "b", "beta")]
+    {
+        let task = workflow
+            .tasks
+            .iter()
+            .find(|x| x.reference_task_name.eq(task_ref_name))
+            .expect("no dynamic task");
+        assert_eq!(task.status, TaskStatus::Completed);
+        // the input of the branch comes from the dynamic tasks input
+        assert_eq!(
+            workflow
+                .variables
+                .get(variable)
+                .expect("no variable")
+                .as_string()
+                .expect("no valid variable")
+                .as_str(),
+            value
+        );
+    }
+
+    let join_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.reference_task_name.eq("fan_in"))
+        .expect("no JOIN task");
+    assert_eq!(join_task.status, TaskStatus::Completed);
+    // the JOIN waits on the dynamic branches
+    assert_eq!(
+        join_task
+            .input_data
+            .get("join_on")
+            .expect("no join_on")
+            .to_json(),
+        serde_json::json!(["branch_a", "branch_b"])
+    );
+    assert!(join_task.output_data.contains_key("branch_a"));
+    assert!(join_task.output_data.contains_key("branch_b"));
+}
+
+#[test]
+fn fork_join_dynamic_fails_when_input_is_not_a_map() {
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
+    let start_workflow_request = fork_join_dynamic_request(
+        serde_json::json!([
+            {
+                "name": "branch_a",
+                "taskReferenceName": "branch_a",
+                "type": "SET_VARIABLE",
+                "inputParameters": {
+                    "a": "unset"
+                }
+            }
+        ]),
+        serde_json::json!("not a map"),
+    );
+    let workflow_id =
+        WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
+    while engine.evaluate_once().is_ok() {}
+
+    let workflow = engine
+        .daos()
+        .execution_dao
+        .get_workflow(&workflow_id)
+        .expect("get_workflow failed")
+        .expect("not none");
+    assert!(workflow.status.is_terminal());
+    assert!(
+        workflow
+            .reason_for_incompletion
+            .contains("Input to the dynamically forked tasks is not a map"),
+        "{}",
+        workflow.reason_for_incompletion
+    );
+}
@@ -0,0 +1,217 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowModel, WorkflowStatus};
+
+mod common;
+
+/// A mock HTTP server answering every request according to its path, returns its port.
+fn start_mock_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server failed");
+    let port = listener.local_addr().expect("no local address").port();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            handle_request(stream);
+        }
+    });
+    port
+}
+
+fn handle_request(mut stream: TcpStream) {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|x| x == b"\r\n\r\n") {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+    let request = String::from_utf8_lossy(&request);
+    let path = request.split_whitespace().nth(1).unwrap_or_default();
+    let (status, content_type, body) = match path {
+        "/greeting" => ("200 OK", "application/json", r#"{"greeting":"hello"}"#),
+        "/text" => ("200 OK", "text/plain", "plain text"),
+        "/missing" => ("404 Not Found", "text/plain", "not found"),
+        _ => ("500 Internal Server Error", "text/plain", "server error"),
+    };
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+}
+
+fn http_workflow_request(http_request: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "name": "http_workflow",
+        "workflowDef": {
+            "name": "http_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "call",
+                    "taskReferenceName": "call",
+                    "type": "HTTP",
+                    "inputParameters": {
+                        "http_request": http_request
+                    },
+                    "retryCount": 1,
+                    "taskDefinition": {
+                        "name": "call",
+                        "retryLogic": "FIXED",
+                        "retryDelaySeconds": 0
+                    }
+                }
+            ]
+        },
+        "input": {}
+    })
+}
+
+/// Runs the workflow until it is finished, the HTTP task is executed by the system task workers
+/// in the background.
+fn run_workflow(http_request: serde_json::Value) -> WorkflowModel {
+    let engine = TegmineEngine::default();
+    engine
+        .init_system_task_executor()
+        .expect("init_system_task_executor failed");
+    let workflow_id = common::start_workflow(&engine, http_workflow_request(http_request));
+    common::wait_for_terminal(&engine, &workflow_id)
+}
+
+#[test]
+fn http_task_completes_with_json_response() {
+    let port = start_mock_server();
+    let workflow = run_workflow(serde_json::json!({
+        "uri": format!("http://127.0.0.1:{}/greeting", port),
+        "method": "GET",
+        "headers": {
+            "X-Request-Id": "tegmine"
+        }
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let task = workflow.tasks.front().expect("no HTTP task");
+    assert_eq!(task.status, TaskStatus::Completed);
+    let response = task
+        .output_data
+        .get("response")
+        .expect("no response")
+        .to_json();
+    assert_eq!(response["statusCode"], serde_json::json!(200));
+    assert_eq!(response["body"], serde_json::json!({ "greeting": "hello" }));
+}
+
+#[test]
+fn http_task_keeps_text_response() {
+    let port = start_mock_server();
+    let workflow = run_workflow(serde_json::json!({
+        "uri": format!("http://127.0.0.1:{}/text", port),
+        "method": "GET"
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let task = workflow.tasks.front().expect("no HTTP task");
+    let response = task
+        .output_data
+        .get("response")
+        .expect("no response")
+        .to_json();
+    assert_eq!(response["body"], serde_json::json!("plain text"));
+}
+
+#[test]
+fn http_task_accepts_configured_status_codes() {
+    let port = start_mock_server();
+    let workflow = run_workflow(serde_json::json!({
+        "uri": format!("http://127.0.0.1:{}/missing", port),
+        "method": "GET",
+        "acceptedStatusCodes": [200, 404]
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+}
+
+#[test]
+fn http_task_fails_terminally_on_client_error() {
+    let port = start_mock_server();
+    let workflow = run_workflow(serde_json::json!({
+        "uri": format!("http://127.0.0.1:{}/missing", port),
+        "method": "GET"
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+
+    // a 4xx response is not retried
+    assert_eq!(workflow.tasks.len(), 1);
+    let task = workflow.tasks.front().expect("no HTTP task");
+    assert_eq!(task.status, TaskStatus::FailedWithTerminalError);
+    assert_eq!(task.reason_for_incompletion.as_str(), "not found");
+}
+
+#[test]
+fn http_task_retries_on_server_error() {
+    let port = start_mock_server();
+    let workflow = run_workflow(serde_json::json!({
+        "uri": format!("http://127.0.0.1:{}/error", port),
+        "method": "POST",
+        "body": {
+            "greeting": "hello"
+        }
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+
+    assert_eq!(workflow.tasks.len(), 2);
+    assert!(workflow
+        .tasks
+        .iter()
+        .all(|x| x.status == TaskStatus::Failed));
+}
+
+#[test]
+fn http_task_retries_on_transport_error() {
+    // the port is free again once the listener is dropped
+    let port = TcpListener::bind("127.0.0.1:0")
+        .expect("bind failed")
+        .local_addr()
+        .expect("no local address")
+        .port();
+    let workflow = run_workflow(serde_json::json!({
+        "uri": format!("http://127.0.0.1:{}/greeting", port),
+        "method": "GET"
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+
+    assert_eq!(workflow.tasks.len(), 2);
+    let task = workflow.tasks.back().expect("no HTTP task");
+    assert_eq!(task.status, TaskStatus::Failed);
+    assert!(
+        task.reason_for_incompletion
+            .contains("Failed to invoke HTTP task"),
+        "{}",
+        task.reason_for_incompletion
+    );
+}
+
+#[test]
+fn http_task_requires_http_request_input() {
+    let start_workflow_request: Result<StartWorkflowRequest, _> = serde_json::json!({
+        "name": "http_workflow",
+        "workflowDef": {
+            "name": "http_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "call",
+                    "taskReferenceName": "call",
+                    "type": "HTTP",
+                    "inputParameters": {}
+                }
+            ]
+        }
+    })
+    .try_into();
+    assert!(start_workflow_request.is_err());
+}
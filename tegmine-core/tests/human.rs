@@ -0,0 +1,216 @@
+use tegmine_common::prelude::*;
+use tegmine_core::{TaskService, TaskStatus, TegmineEngine, WorkflowModel, WorkflowStatus};
+
+mod common;
+
+/// A HUMAN task followed by a SWITCH on whether it has expired.
+fn start_human_workflow(engine: &TegmineEngine, input_parameters: serde_json::Value) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "human_workflow",
+        "workflowDef": {
+            "name": "human_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "approve",
+                    "taskReferenceName": "approve",
+                    "type": "HUMAN",
+                    "inputParameters": input_parameters
+                },
+                {
+                    "name": "expired",
+                    "taskReferenceName": "expired",
+                    "type": "SWITCH",
+                    "evaluatorType": "javascript",
+                    "expression": "$.timedOut ? 'expired' : 'done'",
+                    "inputParameters": {
+                        "timedOut": "${approve.output.timedOut}"
+                    },
+                    "decisionCases": {
+                        "expired": [
+                            {
+                                "name": "escalate",
+                                "taskReferenceName": "escalate",
+                                "type": "SET_VARIABLE",
+                                "inputParameters": {
+                                    "escalated": true
+                                }
+                            }
+                        ]
+                    },
+                    "defaultCase": [
+                        {
+                            "name": "proceed",
+                            "taskReferenceName": "proceed",
+                            "type": "SET_VARIABLE",
+                            "inputParameters": {
+                                "escalated": false
+                            }
+                        }
+                    ]
+                }
+            ]
+        },
+        "input": {}
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+fn is_escalated(workflow: &WorkflowModel) -> bool {
+    workflow
+        .variables
+        .get("escalated")
+        .and_then(|x| x.as_bool().ok())
+        .expect("no escalated variable")
+}
+
+fn get_pending_task_ids(engine: &TegmineEngine, user: &str, groups: &[&str]) -> Vec<InlineStr> {
+    let _guard = engine.enter();
+    TaskService::get_pending_human_tasks(user, groups)
+        .expect("get_pending_human_tasks failed")
+        .into_iter()
+        .map(|x| x.inner.task_id)
+        .collect()
+}
+
+#[test]
+fn human_task_is_listed_for_assignee_and_candidate_groups() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_human_workflow(&engine, serde_json::json!({ "assignee": "alice" }));
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Running);
+    let task = workflow.tasks.front().expect("no HUMAN task");
+    assert_eq!(task.status, TaskStatus::InProgress);
+
+    assert_eq!(
+        get_pending_task_ids(&engine, "alice", &[]),
+        vec![task.task_id.clone()]
+    );
+    assert!(get_pending_task_ids(&engine, "bob", &["reviewers"]).is_empty());
+
+    let workflow_id = start_human_workflow(
+        &engine,
+        serde_json::json!({ "candidateGroups": ["reviewers", "managers"] }),
+    );
+    let task_id = common::get_workflow(&engine, &workflow_id)
+        .tasks
+        .front()
+        .expect("no HUMAN task")
+        .task_id
+        .clone();
+    assert_eq!(
+        get_pending_task_ids(&engine, "bob", &["managers"]),
+        vec![task_id]
+    );
+    assert!(get_pending_task_ids(&engine, "carol", &["interns"]).is_empty());
+}
+
+#[test]
+fn human_task_requires_assignee_or_candidate_groups() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_human_workflow(&engine, serde_json::json!({ "candidateGroups": [] }));
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+    let task = workflow.tasks.front().expect("no HUMAN task");
+    assert_eq!(task.status, TaskStatus::FailedWithTerminalError);
+    assert!(task.reason_for_incompletion.contains("assignee"));
+    assert!(get_pending_task_ids(&engine, "bob", &[]).is_empty());
+}
+
+#[test]
+fn human_task_claimed_by_one_candidate() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_human_workflow(
+        &engine,
+        serde_json::json!({ "candidateGroups": ["reviewers"] }),
+    );
+    let task_id = common::get_workflow(&engine, &workflow_id)
+        .tasks
+        .front()
+        .expect("no HUMAN task")
+        .task_id
+        .clone();
+
+    let _guard = engine.enter();
+    TaskService::claim_human_task(&task_id, "bob", &["reviewers"]).expect("claim failed");
+    assert!(TaskService::claim_human_task(&task_id, "carol", &["reviewers"]).is_err());
+    assert!(TaskService::release_human_task(&task_id, "carol").is_err());
+    drop(_guard);
+    assert!(get_pending_task_ids(&engine, "carol", &["reviewers"]).is_empty());
+
+    // once released, the other candidates can claim it
+    let _guard = engine.enter();
+    TaskService::release_human_task(&task_id, "bob").expect("release failed");
+    TaskService::claim_human_task(&task_id, "carol", &["reviewers"]).expect("claim failed");
+}
+
+#[test]
+fn human_task_completed_with_form_output() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_human_workflow(&engine, serde_json::json!({ "assignee": "alice" }));
+    let task_id = common::get_workflow(&engine, &workflow_id)
+        .tasks
+        .front()
+        .expect("no HUMAN task")
+        .task_id
+        .clone();
+
+    {
+        let _guard = engine.enter();
+        let mut output = HashMap::default();
+        output.insert("approved".into(), Object::Boolean(true));
+        // the task has to be claimed before it is completed
+        assert!(TaskService::complete_human_task(&task_id, "alice", output.clone()).is_err());
+        TaskService::claim_human_task(&task_id, "alice", &[]).expect("claim failed");
+        TaskService::complete_human_task(&task_id, "alice", output).expect("complete failed");
+        common::evaluate(&engine);
+    }
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let task = workflow.tasks.front().expect("no HUMAN task");
+    assert_eq!(task.status, TaskStatus::Completed);
+    assert!(task
+        .output_data
+        .get("approved")
+        .and_then(|x| x.as_bool().ok())
+        .expect("no approved output"));
+    assert_eq!(
+        task.output_data
+            .get("claimedBy")
+            .and_then(|x| x.as_string().ok())
+            .expect("no claimedBy output")
+            .as_str(),
+        "alice"
+    );
+    assert!(!is_escalated(&workflow));
+}
+
+#[test]
+fn human_task_expires_down_timeout_path() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_human_workflow(
+        &engine,
+        serde_json::json!({ "assignee": "alice", "expiry": "1s" }),
+    );
+    assert!(
+        common::get_workflow(&engine, &workflow_id)
+            .tasks
+            .front()
+            .expect("no HUMAN task")
+            .wait_timeout
+            > 0
+    );
+
+    // the workflow is decided again by the sweeper once the task expires
+    engine.spawn_sweeper().expect("spawn_sweeper failed");
+    let workflow = common::wait_for_terminal(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let task = workflow.tasks.front().expect("no HUMAN task");
+    assert!(task
+        .output_data
+        .get("timedOut")
+        .and_then(|x| x.as_bool().ok())
+        .expect("no timedOut output"));
+    assert!(is_escalated(&workflow));
+}
@@ -0,0 +1,114 @@
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowModel, WorkflowService, WorkflowStatus};
+
+fn run_inline_workflow(input_parameters: serde_json::Value) -> WorkflowModel {
+    let start_workflow_request: StartWorkflowRequest = serde_json::json!({
+        "name": "inline_workflow",
+        "workflowDef": {
+            "name": "inline_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "inline",
+                    "taskReferenceName": "inline",
+                    "type": "INLINE",
+                    "inputParameters": input_parameters
+                }
+            ],
+            "outputParameters": {
+                "result": "${inline.output.result}"
+            }
+        },
+        "input": {
+            "a": 1,
+            "b": 2
+        }
+    })
+    .try_into()
+    .expect("parse StartWorkflowRequest failed");
+
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
+    let workflow_id =
+        WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
+    while engine.evaluate_once().is_ok() {}
+
+    engine
+        .daos()
+        .execution_dao
+        .get_workflow(&workflow_id)
+        .expect("get_workflow failed")
+        .expect("not none")
+}
+
+#[test]
+fn inline_evaluates_javascript() {
+    let workflow = run_inline_workflow(serde_json::json!({
+        "evaluatorType": "javascript",
+        "expression": "$.a + $.b",
+        "a": "${workflow.input.a}",
+        "b": "${workflow.input.b}"
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    // javascript numbers may come back as doubles
+    assert_eq!(
+        workflow
+            .output
+            .get("result")
+            .expect("no result")
+            .to_json()
+            .as_f64(),
+        Some(3.0)
+    );
+}
+
+#[test]
+fn inline_evaluates_value_param() {
+    let workflow = run_inline_workflow(serde_json::json!({
+        "evaluatorType": "value-param",
+        "expression": "value",
+        "value": "${workflow.input.b}"
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert_eq!(
+        workflow.output.get("result").expect("no result").to_json(),
+        serde_json::json!(2)
+    );
+}
+
+#[test]
+fn inline_fails_task_on_script_error() {
+    let workflow = run_inline_workflow(serde_json::json!({
+        "evaluatorType": "javascript",
+        "expression": "$.missing.value"
+    }));
+    // the workflow fails because of the failed task, it is not terminated by the script
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+
+    let task = workflow.tasks.front().expect("no INLINE task");
+    assert_eq!(task.status, TaskStatus::Failed);
+    assert!(!task.reason_for_incompletion.is_empty());
+}
+
+#[test]
+fn inline_requires_evaluator_type_and_expression() {
+    let start_workflow_request: Result<StartWorkflowRequest, _> = serde_json::json!({
+        "name": "inline_workflow",
+        "workflowDef": {
+            "name": "inline_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "inline",
+                    "taskReferenceName": "inline",
+                    "type": "INLINE",
+                    "inputParameters": {
+                        "expression": "$.a"
+                    }
+                }
+            ]
+        }
+    })
+    .try_into();
+    assert!(start_workflow_request.is_err());
+}
@@ -0,0 +1,281 @@
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowModel, WorkflowService, WorkflowStatus};
+
+fn run_workflow(start_workflow_request: serde_json::Value) -> WorkflowModel {
+    let start_workflow_request: StartWorkflowRequest = start_workflow_request
+        .try_into()
+        .expect("parse StartWorkflowRequest failed");
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
+    let workflow_id =
+        WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
+    while engine.evaluate_once().is_ok() {}
+
+    engine
+        .daos()
+        .execution_dao
+        .get_workflow(&workflow_id)
+        .expect("get_workflow failed")
+        .expect("not none")
+}
+
+fn fork_join_workflow(join_on: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "name": "fork_join_workflow",
+        "workflowDef": {
+            "name": "fork_join_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "fan_out",
+                    "taskReferenceName": "fan_out",
+                    "type": "FORK_JOIN",
+                    "inputParameters": {},
+                    "forkTasks": [
+                        [
+                            {
+                                "name": "branch_a",
+                                "taskReferenceName": "branch_a",
+                                "type": "SET_VARIABLE",
+                                "inputParameters": {
+                                    "a": "alpha"
+                                }
+                            }
+                        ],
+                        [
+                            {
+                                "name": "branch_b",
+                                "taskReferenceName": "branch_b",
+                                "type": "SET_VARIABLE",
+                                "inputParameters": {
+                                    "b": "beta"
+                                }
+                            }
+                        ]
+                    ]
+                },
+                {
+                    "name": "fan_in",
+                    "taskReferenceName": "fan_in",
+                    "type": "JOIN",
+                    "inputParameters": {},
+                    "joinOn": join_on
+                }
+            ]
+        },
+        "input": {}
+    })
+}
+
+#[test]
+fn join_completes_when_forked_tasks_complete() {
+    let workflow = run_workflow(fork_join_workflow(serde_json::json!([
+        "branch_a", "branch_b"
+    ])));
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let join_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.reference_task_name.eq("fan_in"))
+        .expect("no JOIN task");
+    assert_eq!(join_task.status, TaskStatus::Completed);
+    // the outputs of the forked tasks are keyed by their reference names
+    assert!(join_task.output_data.contains_key("branch_a"));
+    assert!(join_task.output_data.contains_key("branch_b"));
+}
+
+#[test]
+fn join_on_has_to_reference_forked_tasks() {
+    let start_workflow_request: Result<StartWorkflowRequest, _> =
+        fork_join_workflow(serde_json::json!(["branch_a", "branch_c"])).try_into();
+    assert!(start_workflow_request.is_err());
+}
+
+#[test]
+fn join_on_can_not_be_empty() {
+    // only the JOIN of a FORK_JOIN_DYNAMIC task may omit joinOn
+    for join_on in [serde_json::json!([]), serde_json::Value::Null] {
+        let start_workflow_request: Result<StartWorkflowRequest, _> =
+            fork_join_workflow(join_on).try_into();
+        assert!(start_workflow_request.is_err());
+    }
+}
+
+#[test]
+fn exclusive_join_falls_back_to_default_task() {
+    let workflow = run_workflow(serde_json::json!({
+        "name": "exclusive_join_workflow",
+        "workflowDef": {
+            "name": "exclusive_join_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "switch",
+                    "taskReferenceName": "switch",
+                    "type": "SWITCH",
+                    "evaluatorType": "value-param",
+                    "expression": "switchCaseValue",
+                    "inputParameters": {
+                        "switchCaseValue": "${workflow.input.service}"
+                    },
+                    "decisionCases": {
+                        "fedex": [
+                            {
+                                "name": "set_fedex",
+                                "taskReferenceName": "set_fedex",
+                                "type": "SET_VARIABLE",
+                                "inputParameters": {
+                                    "service": "fedex"
+                                }
+                            }
+                        ],
+                        "ups": [
+                            {
+                                "name": "set_ups",
+                                "taskReferenceName": "set_ups",
+                                "type": "SET_VARIABLE",
+                                "inputParameters": {
+                                    "service": "ups"
+                                }
+                            }
+                        ]
+                    },
+                    "defaultCase": [
+                        {
+                            "name": "set_default",
+                            "taskReferenceName": "set_default",
+                            "type": "SET_VARIABLE",
+                            "inputParameters": {
+                                "service": "default"
+                            }
+                        }
+                    ]
+                },
+                {
+                    "name": "exclusive_join",
+                    "taskReferenceName": "exclusive_join",
+                    "type": "EXCLUSIVE_JOIN",
+                    "inputParameters": {},
+                    "exclusiveJoinOn": ["set_fedex", "set_ups"],
+                    "defaultExclusiveJoinTask": ["set_default"]
+                }
+            ]
+        },
+        "input": {
+            "service": "dhl"
+        }
+    }));
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let exclusive_join_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.reference_task_name.eq("exclusive_join"))
+        .expect("no EXCLUSIVE_JOIN task");
+    assert_eq!(exclusive_join_task.status, TaskStatus::Completed);
+    assert_eq!(
+        workflow
+            .variables
+            .get("service")
+            .expect("no variable")
+            .as_string()
+            .expect("no valid variable")
+            .as_str(),
+        "default"
+    );
+}
+
+/// A fork of two SET_VARIABLE tasks and a SIMPLE task which is never polled, joined by a JOIN of
+/// the given mode.
+fn join_mode_workflow(join_mode: serde_json::Value) -> serde_json::Value {
+    let mut workflow = fork_join_workflow(serde_json::json!(["branch_a", "branch_b", "branch_c"]));
+    workflow["workflowDef"]["tasks"][0]["forkTasks"]
+        .as_array_mut()
+        .expect("no forkTasks")
+        .push(serde_json::json!([
+            {
+                "name": "branch_c",
+                "taskReferenceName": "branch_c",
+                "type": "SIMPLE",
+                "inputParameters": {},
+                "taskDefinition": {
+                    "name": "branch_c",
+                    "retryLogic": "FIXED"
+                }
+            }
+        ]));
+    let join = workflow["workflowDef"]["tasks"][1]
+        .as_object_mut()
+        .expect("no JOIN task");
+    for (k, v) in join_mode.as_object().expect("join mode is not a map") {
+        join.insert(k.clone(), v.clone());
+    }
+    workflow
+}
+
+fn assert_join_satisfied_early(workflow: &WorkflowModel) {
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let join_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.reference_task_name.eq("fan_in"))
+        .expect("no JOIN task");
+    assert_eq!(join_task.status, TaskStatus::Completed);
+    // the branch which has not completed is canceled
+    let canceled_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.reference_task_name.eq("branch_c"))
+        .expect("no SIMPLE task");
+    assert_eq!(canceled_task.status, TaskStatus::Canceled);
+}
+
+#[test]
+fn join_waits_for_all_forked_tasks_by_default() {
+    let workflow = run_workflow(join_mode_workflow(serde_json::json!({})));
+    assert_eq!(workflow.status, WorkflowStatus::Running);
+    let join_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.reference_task_name.eq("fan_in"))
+        .expect("no JOIN task");
+    assert_eq!(join_task.status, TaskStatus::InProgress);
+}
+
+#[test]
+fn join_any_completes_with_first_forked_task() {
+    assert_join_satisfied_early(&run_workflow(join_mode_workflow(serde_json::json!({
+        "joinMode": "ANY"
+    }))));
+}
+
+#[test]
+fn join_first_n_completes_with_n_forked_tasks() {
+    assert_join_satisfied_early(&run_workflow(join_mode_workflow(serde_json::json!({
+        "joinMode": "FIRST_N",
+        "joinCount": 2
+    }))));
+}
+
+#[test]
+fn join_quorum_completes_with_percentage_of_forked_tasks() {
+    assert_join_satisfied_early(&run_workflow(join_mode_workflow(serde_json::json!({
+        "joinMode": "QUORUM",
+        "joinQuorum": 60
+    }))));
+}
+
+#[test]
+fn join_mode_requires_valid_count() {
+    for join_mode in [
+        serde_json::json!({ "joinMode": "FIRST_N" }),
+        serde_json::json!({ "joinMode": "FIRST_N", "joinCount": 4 }),
+        serde_json::json!({ "joinMode": "QUORUM", "joinQuorum": 101 }),
+        serde_json::json!({ "joinMode": "MOST" }),
+    ] {
+        let start_workflow_request: Result<StartWorkflowRequest, _> =
+            join_mode_workflow(join_mode).try_into();
+        assert!(start_workflow_request.is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowModel, WorkflowService, WorkflowStatus};
+
+fn run_jq_workflow(query_expression: &str) -> WorkflowModel {
+    let start_workflow_request: StartWorkflowRequest = serde_json::json!({
+        "name": "jq_workflow",
+        "workflowDef": {
+            "name": "jq_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "transform",
+                    "taskReferenceName": "transform",
+                    "type": "JSON_JQ_TRANSFORM",
+                    "inputParameters": {
+                        "queryExpression": query_expression,
+                        "orders": "${workflow.input.orders}"
+                    }
+                }
+            ]
+        },
+        "input": {
+            "orders": [
+                { "id": "a", "amount": 1 },
+                { "id": "b", "amount": 2 }
+            ]
+        }
+    })
+    .try_into()
+    .expect("parse StartWorkflowRequest failed");
+
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
+    let workflow_id =
+        WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
+    while engine.evaluate_once().is_ok() {}
+
+    engine
+        .daos()
+        .execution_dao
+        .get_workflow(&workflow_id)
+        .expect("get_workflow failed")
+        .expect("not none")
+}
+
+#[test]
+fn json_jq_transform_keeps_first_result() {
+    let workflow = run_jq_workflow("{ ids: [.orders[].id], total: ([.orders[].amount] | add) }");
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let task = workflow.tasks.front().expect("no JSON_JQ_TRANSFORM task");
+    assert_eq!(task.status, TaskStatus::Completed);
+    assert_eq!(
+        task.output_data.get("result").expect("no result").to_json(),
+        serde_json::json!({ "ids": ["a", "b"], "total": 3 })
+    );
+}
+
+#[test]
+fn json_jq_transform_keeps_all_results() {
+    let workflow = run_jq_workflow(".orders[].id");
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+    let task = workflow.tasks.front().expect("no JSON_JQ_TRANSFORM task");
+    assert_eq!(
+        task.output_data.get("result").expect("no result").to_json(),
+        serde_json::json!("a")
+    );
+    assert_eq!(
+        task.output_data
+            .get("resultList")
+            .expect("no resultList")
+            .to_json(),
+        serde_json::json!(["a", "b"])
+    );
+}
+
+#[test]
+fn json_jq_transform_fails_on_invalid_query() {
+    let workflow = run_jq_workflow(".orders[");
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+
+    let task = workflow.tasks.front().expect("no JSON_JQ_TRANSFORM task");
+    assert_eq!(task.status, TaskStatus::Failed);
+    assert!(task
+        .reason_for_incompletion
+        .contains("Invalid queryExpression"));
+    // the query is not run once it failed to compile
+    assert!(!task.output_data.contains_key("result"));
+}
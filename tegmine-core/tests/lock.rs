@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tegmine_core::{FileLock, LocalOnlyLock, Lock};
+
+fn acquire_in_other_thread(lock: &Arc<dyn Lock>, time_to_try_ms: i64) -> bool {
+    let lock = Arc::clone(lock);
+    thread::spawn(move || {
+        let acquired =
+            lock.acquire_lock_try_and_lease_time(&"workflow_1".into(), time_to_try_ms, 60000);
+        if acquired {
+            lock.release_lock(&"workflow_1".into());
+        }
+        acquired
+    })
+    .join()
+    .expect("thread panicked")
+}
+
+fn check_lock(lock: Arc<dyn Lock>) {
+    let lock_id = "workflow_1".into();
+
+    // reentrant for the owner thread
+    assert!(lock.acquire_lock_try_and_lease_time(&lock_id, 0, 60000));
+    assert!(lock.acquire_lock_try_and_lease_time(&lock_id, 0, 60000));
+    assert!(!acquire_in_other_thread(&lock, 50));
+
+    lock.release_lock(&lock_id);
+    assert!(!acquire_in_other_thread(&lock, 0));
+    lock.release_lock(&lock_id);
+    assert!(acquire_in_other_thread(&lock, 0));
+
+    // a waiter gets the lock once the lease expires
+    assert!(lock.acquire_lock_try_and_lease_time(&lock_id, 0, 100));
+    let start = Instant::now();
+    assert!(acquire_in_other_thread(&lock, 2000));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    // releasing an expired lease is a no-op
+    lock.release_lock(&lock_id);
+
+    assert!(lock.acquire_lock_try_and_lease_time(&lock_id, 0, 60000));
+    lock.delete_lock(&lock_id);
+    assert!(acquire_in_other_thread(&lock, 0));
+}
+
+#[test]
+fn local_only_lock() {
+    check_lock(Arc::new(LocalOnlyLock::new()));
+}
+
+#[test]
+fn file_lock() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    check_lock(Arc::new(
+        FileLock::new(dir.path()).expect("create file lock failed"),
+    ));
+
+    // locks on the same directory exclude each other
+    let lock = FileLock::new(dir.path()).expect("create file lock failed");
+    let other: Arc<dyn Lock> =
+        Arc::new(FileLock::new(dir.path()).expect("create file lock failed"));
+    assert!(lock.acquire_lock_try_and_lease_time(&"workflow_1".into(), 0, 60000));
+    assert!(!acquire_in_other_thread(&other, 50));
+    lock.release_lock(&"workflow_1".into());
+    assert!(acquire_in_other_thread(&other, 0));
+
+    // lock ids differing only in special characters are distinct locks
+    assert!(lock.acquire_lock_try_and_lease_time(&"workflow.1".into(), 0, 60000));
+    let acquired = thread::spawn(move || {
+        let acquired = other.acquire_lock_try_and_lease_time(&"workflow_1".into(), 0, 60000);
+        other.release_lock(&"workflow_1".into());
+        acquired
+    })
+    .join()
+    .expect("thread panicked");
+    assert!(acquired);
+    lock.release_lock(&"workflow.1".into());
+}
+
+#[test]
+fn file_lock_purges_expired_leases() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let lock = FileLock::new(dir.path()).expect("create file lock failed");
+    // the lease is never released, as if the process had crashed
+    assert!(lock.acquire_lock_try_and_lease_time(&"workflow_1".into(), 0, 1));
+    assert!(dir.path().join("workflow_1.lock").exists());
+    thread::sleep(Duration::from_millis(10));
+
+    let other = FileLock::new(dir.path()).expect("create file lock failed");
+    assert!(other.acquire_lock_try_and_lease_time(&"workflow_2".into(), 0, 60000));
+    assert!(!dir.path().join("workflow_1.lock").exists());
+    other.release_lock(&"workflow_2".into());
+}
@@ -1,5 +1,5 @@
 use tegmine_common::{TaskDef, WorkflowDef};
-use tegmine_core::MetadataService;
+use tegmine_core::{MetadataService, TegmineEngine};
 
 #[test]
 pub fn register_workflow() {
@@ -45,6 +45,8 @@ pub fn register_workflow() {
     let workflow_def: serde_json::Value =
         serde_json::from_str(workflow_def).expect("parse json failed");
     let workflow_def = WorkflowDef::try_from(&workflow_def).expect("parse WorkflowDef failed");
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
     MetadataService::register_workflow_def(workflow_def).expect("register_workflow_def failed");
 }
 
@@ -77,5 +79,7 @@ fn register_task() {
     }"#;
     let task_def: serde_json::Value = serde_json::from_str(task_def).expect("parse json failed");
     let task_def = TaskDef::try_from(&task_def).expect("parse TaskDef failed");
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
     MetadataService::register_task_def(vec![task_def], "test").expect("register_task_def failed");
 }
@@ -0,0 +1,107 @@
+use tegmine_common::prelude::*;
+use tegmine_common::TaskResultStatus;
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowService, WorkflowStatus};
+
+mod common;
+
+/// A WAIT task, which is completed by a signal, followed by a SET_VARIABLE task.
+fn start_workflow(engine: &TegmineEngine) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "pause_workflow",
+        "workflowDef": {
+            "name": "pause_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "wait",
+                    "taskReferenceName": "wait",
+                    "type": "WAIT",
+                    "inputParameters": {}
+                },
+                {
+                    "name": "set_done",
+                    "taskReferenceName": "set_done",
+                    "type": "SET_VARIABLE",
+                    "inputParameters": {
+                        "done": true
+                    }
+                }
+            ]
+        },
+        "input": {}
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+#[test]
+fn paused_workflow_schedules_no_task_until_resumed() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(&engine);
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::pause_workflow(&workflow_id).expect("pause_workflow failed");
+        // pausing again has no effect
+        WorkflowService::pause_workflow(&workflow_id).expect("pause_workflow failed");
+    }
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Paused);
+    assert_eq!(workflow.previous_status, Some(WorkflowStatus::Running));
+
+    // the task in progress completes, the next one is not scheduled
+    {
+        let _guard = engine.enter();
+        WorkflowService::signal(
+            &workflow_id,
+            "wait",
+            TaskResultStatus::Completed,
+            HashMap::default(),
+        )
+        .expect("signal failed");
+        common::evaluate(&engine);
+    }
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Paused);
+    assert_eq!(workflow.tasks.len(), 1);
+    assert_eq!(
+        workflow.tasks.front().expect("no WAIT task").status,
+        TaskStatus::Completed
+    );
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::resume_workflow(&workflow_id).expect("resume_workflow failed");
+        assert_eq!(
+            common::get_workflow(&engine, &workflow_id).previous_status,
+            Some(WorkflowStatus::Paused)
+        );
+        common::evaluate(&engine);
+    }
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert!(workflow
+        .variables
+        .get("done")
+        .and_then(|x| x.as_bool().ok())
+        .expect("no done variable"));
+}
+
+#[test]
+fn only_running_workflow_can_be_paused() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(&engine);
+
+    let _guard = engine.enter();
+    // a running workflow can not be resumed
+    assert!(WorkflowService::resume_workflow(&workflow_id).is_err());
+
+    WorkflowService::signal(
+        &workflow_id,
+        "wait",
+        TaskResultStatus::Completed,
+        HashMap::default(),
+    )
+    .expect("signal failed");
+    common::evaluate(&engine);
+    assert!(WorkflowService::pause_workflow(&workflow_id).is_err());
+}
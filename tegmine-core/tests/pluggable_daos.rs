@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{
+    Daos, ExecutionDao, ExecutionService, Properties, SqliteDataSource, SqliteExecutionDao,
+    SqliteMetadataDao, SqliteQueueDao, TegmineEngine, WorkflowStatus,
+};
+
+fn sqlite_daos(dir: &tempfile::TempDir) -> (Arc<SqliteExecutionDao>, Daos) {
+    let url = format!("sqlite://{}", dir.path().join("tegmine.db").display());
+    let data_source = SqliteDataSource::connect(&url).expect("connect sqlite failed");
+
+    let execution_dao = Arc::new(SqliteExecutionDao::new(data_source.clone()));
+    let daos = Daos {
+        execution_dao: execution_dao.clone(),
+        queue_dao: Arc::new(SqliteQueueDao::new(data_source.clone())),
+        metadata_dao: Arc::new(SqliteMetadataDao::new(data_source)),
+        concurrent_execution_limit_dao: execution_dao.clone(),
+        ..Default::default()
+    };
+    (execution_dao, daos)
+}
+
+fn start_workflow_request() -> StartWorkflowRequest {
+    let start_workflow_request = r#"
+    {
+        "name": "sqlite_workflow",
+        "workflowDef": {
+            "name": "sqlite_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "set_name",
+                    "taskReferenceName": "set_name",
+                    "type": "SET_VARIABLE",
+                    "inputParameters": {
+                        "name": "${workflow.input.name}"
+                    }
+                }
+            ],
+            "outputParameters": {
+                "output": "${workflow.variables.name}"
+            }
+        },
+        "input": {
+            "name": "Foo"
+        }
+    }"#;
+    let start_workflow_request: serde_json::Value =
+        serde_json::from_str(start_workflow_request).expect("parse json failed");
+    start_workflow_request
+        .try_into()
+        .expect("parse StartWorkflowRequest failed")
+}
+
+#[test]
+fn start_workflow_with_sqlite_daos() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let (execution_dao, daos) = sqlite_daos(&dir);
+    let engine = TegmineEngine::new(Properties::default(), daos);
+
+    let workflow_instance_id = engine
+        .start_workflow(start_workflow_request())
+        .expect("start_workflow failed");
+    engine.evaluate_once().expect("evaluation failed");
+
+    let (workflow_status, workflow) = engine
+        .get_execution_status(workflow_instance_id.as_str(), false)
+        .expect("get_execution_status failed");
+    assert_eq!(workflow_status, WorkflowStatus::Completed);
+    let output = workflow.expect("not none").workflow.output;
+    assert_eq!(
+        output
+            .get("output")
+            .and_then(|x| x.as_string().ok())
+            .map(|x| x.as_str()),
+        Some("Foo")
+    );
+
+    // the execution is stored by the dao of the engine
+    let stored = execution_dao
+        .get_workflow(&workflow_instance_id)
+        .expect("get_workflow failed")
+        .expect("workflow not found");
+    assert_eq!(stored.status, WorkflowStatus::Completed);
+    assert_eq!(stored.tasks.len(), 1);
+
+    // another engine in the same process has its own stores
+    let other = TegmineEngine::default();
+    let _guard = other.enter();
+    assert!(ExecutionService::get_execution_status(workflow_instance_id.as_str(), false).is_err());
+}
+
+/// The DAOs are called from an async context, as `TegmineEngine::async_execute_workflow` does.
+async fn execute_workflow_in_async_context() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let (_, daos) = sqlite_daos(&dir);
+    let engine = TegmineEngine::new(Properties::default(), daos);
+
+    let workflow_instance_id = engine
+        .start_workflow(start_workflow_request())
+        .expect("start_workflow failed");
+    engine.evaluate_once().expect("evaluation failed");
+    let (workflow_status, _) = engine
+        .get_execution_status(workflow_instance_id.as_str(), false)
+        .expect("get_execution_status failed");
+    assert_eq!(workflow_status, WorkflowStatus::Completed);
+}
+
+#[tokio::test]
+async fn sqlite_daos_in_current_thread_runtime() {
+    execute_workflow_in_async_context().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sqlite_daos_in_multi_thread_runtime() {
+    execute_workflow_in_async_context().await
+}
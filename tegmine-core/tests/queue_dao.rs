@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tegmine_core::{InMemoryQueueDao, QueueDao, SqliteDataSource, SqliteQueueDao};
+
+fn check_unack_timeout(queue_dao: &dyn QueueDao) {
+    let queue_name = "unack_queue";
+    queue_dao
+        .push(queue_name, &"message_1".into(), 0, 0)
+        .expect("push failed");
+    queue_dao
+        .push(queue_name, &"message_2".into(), 1, 0)
+        .expect("push failed");
+    assert_eq!(queue_dao.size(queue_name).expect("size failed"), 2);
+
+    // not popped yet
+    assert!(!queue_dao
+        .ack(queue_name, &"message_1".into())
+        .expect("ack failed"));
+
+    let popped = queue_dao.pop(queue_name, 2, 50).expect("pop failed");
+    assert_eq!(popped, vec!["message_1", "message_2"]);
+    // popped messages are invisible, but still in the queue
+    assert!(queue_dao
+        .pop(queue_name, 2, 0)
+        .expect("pop failed")
+        .is_empty());
+    assert_eq!(queue_dao.size(queue_name).expect("size failed"), 2);
+
+    // an ack'ed message is never delivered again
+    assert!(queue_dao
+        .ack(queue_name, &"message_1".into())
+        .expect("ack failed"));
+    assert!(!queue_dao
+        .exists(queue_name, &"message_1".into())
+        .expect("exists failed"));
+
+    // an unack'ed message is delivered again once its unack timeout elapses
+    assert!(queue_dao
+        .set_unack_timeout(queue_name, &"message_2".into(), 0)
+        .expect("set_unack_timeout failed"));
+    assert_eq!(
+        queue_dao.pop(queue_name, 2, 50).expect("pop failed"),
+        vec!["message_2"]
+    );
+    assert!(!queue_dao
+        .set_unack_timeout(queue_name, &"message_1".into(), 0)
+        .expect("set_unack_timeout failed"));
+
+    queue_dao.flush(queue_name).expect("flush failed");
+    assert_eq!(queue_dao.size(queue_name).expect("size failed"), 0);
+    assert!(!queue_dao
+        .exists(queue_name, &"message_2".into())
+        .expect("exists failed"));
+}
+
+fn check_push_wakes_poller(queue_dao: Arc<dyn QueueDao>) {
+    let queue_name = "wake_queue";
+    let pusher = {
+        let queue_dao = Arc::clone(&queue_dao);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            queue_dao
+                .push(queue_name, &"message_1".into(), 0, 0)
+                .expect("push failed");
+        })
+    };
+
+    // the poller is woken up by the push, long before the timeout
+    let start = Instant::now();
+    let popped = queue_dao.pop(queue_name, 1, 5000).expect("pop failed");
+    assert_eq!(popped, vec!["message_1"]);
+    assert!(start.elapsed() < Duration::from_millis(1000));
+    pusher.join().expect("thread panicked");
+
+    // an idle poller waits until the timeout elapses
+    let start = Instant::now();
+    assert!(queue_dao
+        .pop(queue_name, 1, 100)
+        .expect("pop failed")
+        .is_empty());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn in_memory_queue_dao_unack_timeout() {
+    check_unack_timeout(&InMemoryQueueDao::new());
+}
+
+#[test]
+fn in_memory_queue_dao_push_wakes_poller() {
+    check_push_wakes_poller(Arc::new(InMemoryQueueDao::new()));
+}
+
+#[test]
+fn sqlite_queue_dao_unack_timeout() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let url = format!("sqlite://{}", dir.path().join("tegmine.db").display());
+    let data_source = SqliteDataSource::connect(&url).expect("connect sqlite failed");
+    check_unack_timeout(&SqliteQueueDao::new(data_source));
+}
+
+#[test]
+fn sqlite_queue_dao_push_wakes_poller() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let url = format!("sqlite://{}", dir.path().join("tegmine.db").display());
+    let data_source = SqliteDataSource::connect(&url).expect("connect sqlite failed");
+    check_push_wakes_poller(Arc::new(SqliteQueueDao::new(data_source)));
+}
@@ -0,0 +1,45 @@
+use std::thread;
+use std::time::Duration;
+
+use tegmine_common::TaskDef;
+use tegmine_core::{InMemoryRateLimitingDao, RateLimitingDao, TaskModel, TaskStatus};
+
+fn task(task_def_name: &str, domain: &str) -> TaskModel {
+    let mut task = TaskModel::new(TaskStatus::Scheduled);
+    task.task_def_name = task_def_name.into();
+    task.domain = domain.into();
+    task.rate_limit_per_frequency = 2;
+    task.rate_limit_frequency_in_seconds = 1;
+    task
+}
+
+#[test]
+fn in_memory_rate_limiting_dao() {
+    let rate_limiting_dao = InMemoryRateLimitingDao::new();
+    let task_1 = task("task_1", "");
+
+    assert!(!rate_limiting_dao.exceeds_rate_limit_per_frequency(&task_1, None));
+    assert!(!rate_limiting_dao.exceeds_rate_limit_per_frequency(&task_1, None));
+    assert!(rate_limiting_dao.exceeds_rate_limit_per_frequency(&task_1, None));
+
+    // other task definitions and domains are limited separately
+    assert!(!rate_limiting_dao.exceeds_rate_limit_per_frequency(&task("task_2", ""), None));
+    assert!(!rate_limiting_dao.exceeds_rate_limit_per_frequency(&task("task_1", "domain"), None));
+
+    // the task definition takes precedence over the task
+    let mut task_def = TaskDef::new("task_1");
+    task_def.rate_limit_per_frequency = Some(3);
+    task_def.rate_limit_frequency_in_seconds = Some(1);
+    assert!(!rate_limiting_dao.exceeds_rate_limit_per_frequency(&task_1, Some(&task_def)));
+    assert!(rate_limiting_dao.exceeds_rate_limit_per_frequency(&task_1, Some(&task_def)));
+
+    // the window slides
+    thread::sleep(Duration::from_millis(1100));
+    assert!(!rate_limiting_dao.exceeds_rate_limit_per_frequency(&task_1, None));
+
+    // no limit
+    task_def.rate_limit_per_frequency = None;
+    for _ in 0..10 {
+        assert!(!rate_limiting_dao.exceeds_rate_limit_per_frequency(&task_1, Some(&task_def)));
+    }
+}
@@ -0,0 +1,316 @@
+use tegmine_common::prelude::*;
+use tegmine_common::RerunWorkflowRequest;
+use tegmine_core::{
+    TaskModel, TaskStatus, TegmineEngine, WorkflowModel, WorkflowService, WorkflowStatus,
+};
+
+mod common;
+
+/// A JSON_JQ_TRANSFORM task which fails unless the `value` input is a number.
+fn compute_task() -> serde_json::Value {
+    serde_json::json!({
+        "name": "compute",
+        "taskReferenceName": "compute",
+        "type": "JSON_JQ_TRANSFORM",
+        "inputParameters": {
+            "queryExpression": ".value + 1",
+            "value": "${workflow.input.value}"
+        }
+    })
+}
+
+fn start_workflow(
+    engine: &TegmineEngine,
+    tasks: serde_json::Value,
+    input: serde_json::Value,
+) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "rerun_workflow",
+        "workflowDef": {
+            "name": "rerun_workflow",
+            "version": 1,
+            "tasks": tasks,
+            "outputParameters": {
+                "result": "${compute.output.result}"
+            }
+        },
+        "input": input
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+fn rerun(engine: &TegmineEngine, workflow_id: &InlineStr, request: RerunWorkflowRequest) {
+    let _guard = engine.enter();
+    WorkflowService::rerun_workflow(workflow_id, request).expect("rerun_workflow failed");
+    common::evaluate(engine);
+}
+
+fn get_task<'a>(workflow: &'a WorkflowModel, task_ref_name: &str) -> &'a TaskModel {
+    workflow
+        .get_task_by_ref_name(task_ref_name)
+        .expect("get_task_by_ref_name failed")
+        .expect("no task")
+}
+
+fn get_result(workflow: &WorkflowModel) -> i64 {
+    workflow
+        .output
+        .get("result")
+        .expect("no result")
+        .to_json()
+        .as_i64()
+        .expect("no valid result")
+}
+
+#[test]
+fn rerun_from_failed_task_with_task_input() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "set_start",
+                "taskReferenceName": "set_start",
+                "type": "SET_VARIABLE",
+                "inputParameters": {
+                    "started": true
+                }
+            },
+            compute_task()
+        ]),
+        serde_json::json!({ "value": "one" }),
+    );
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+    let set_start_id = get_task(&workflow, "set_start").task_id.clone();
+    let compute = get_task(&workflow, "compute");
+    assert_eq!(compute.status, TaskStatus::Failed);
+
+    let mut request = RerunWorkflowRequest::new(InlineStr::new(), compute.task_id.clone());
+    let mut task_input = HashMap::default();
+    task_input.insert("queryExpression".into(), ".value + 1".into());
+    task_input.insert("value".into(), Object::Int(1));
+    request.task_input = Some(task_input);
+    rerun(&engine, &workflow_id, request);
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert_eq!(workflow.previous_status, Some(WorkflowStatus::Running));
+    assert!(workflow.reason_for_incompletion.is_empty());
+    assert_eq!(workflow.tasks.len(), 2);
+    // the tasks before the rerun task are kept
+    assert_eq!(get_task(&workflow, "set_start").task_id, set_start_id);
+    assert_eq!(get_result(&workflow), 2);
+}
+
+#[test]
+fn rerun_from_beginning_with_workflow_input() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([compute_task()]),
+        serde_json::json!({ "value": "one" }),
+    );
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+    let failed_task_id = get_task(&workflow, "compute").task_id.clone();
+
+    let mut request = RerunWorkflowRequest::new(InlineStr::new(), InlineStr::new());
+    let mut workflow_input = HashMap::default();
+    workflow_input.insert("value".into(), Object::Int(41));
+    request.workflow_input = Some(workflow_input);
+    rerun(&engine, &workflow_id, request);
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert_eq!(workflow.tasks.len(), 1);
+    // the tasks are scheduled again
+    assert_ne!(get_task(&workflow, "compute").task_id, failed_task_id);
+    assert_eq!(get_result(&workflow), 42);
+}
+
+#[test]
+fn rerun_removes_tasks_after_rerun_task() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            compute_task(),
+            {
+                "name": "set_result",
+                "taskReferenceName": "set_result",
+                "type": "SET_VARIABLE",
+                "inputParameters": {
+                    "result": "${compute.output.result}"
+                }
+            }
+        ]),
+        serde_json::json!({ "value": 1 }),
+    );
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let compute_id = get_task(&workflow, "compute").task_id.clone();
+    let set_result_id = get_task(&workflow, "set_result").task_id.clone();
+
+    let mut request = RerunWorkflowRequest::new(InlineStr::new(), compute_id.clone());
+    let mut task_input = HashMap::default();
+    task_input.insert("queryExpression".into(), ".value * 10".into());
+    task_input.insert("value".into(), Object::Int(5));
+    request.task_input = Some(task_input);
+    rerun(&engine, &workflow_id, request);
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert_eq!(workflow.tasks.len(), 2);
+    assert_eq!(get_task(&workflow, "compute").task_id, compute_id);
+    assert_ne!(get_task(&workflow, "set_result").task_id, set_result_id);
+    assert_eq!(get_result(&workflow), 50);
+    assert_eq!(
+        workflow
+            .variables
+            .get("result")
+            .expect("no result variable")
+            .to_json(),
+        serde_json::json!(50)
+    );
+}
+
+/// A SUB_WORKFLOW task of a workflow with the compute task, which fails with the input `value`
+/// "one".
+fn start_parent_workflow(engine: &TegmineEngine) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "parent_workflow",
+        "workflowDef": {
+            "name": "parent_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "sub",
+                    "taskReferenceName": "sub",
+                    "type": "SUB_WORKFLOW",
+                    "inputParameters": {
+                        "value": "${workflow.input.value}"
+                    },
+                    "subWorkflowParam": {
+                        "workflowDefinition": {
+                            "name": "child_workflow",
+                            "version": 1,
+                            "tasks": [compute_task()],
+                            "outputParameters": {
+                                "result": "${compute.output.result}"
+                            }
+                        }
+                    }
+                }
+            ],
+            "outputParameters": {
+                "result": "${sub.output.result}"
+            }
+        },
+        "input": {
+            "value": "one"
+        }
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+/// The task input of the compute task which succeeds with the result 2.
+fn compute_task_input() -> HashMap<InlineStr, Object> {
+    let mut task_input = HashMap::default();
+    task_input.insert("queryExpression".into(), ".value + 1".into());
+    task_input.insert("value".into(), Object::Int(1));
+    task_input
+}
+
+#[test]
+fn rerun_from_task_of_sub_workflow() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_parent_workflow(&engine);
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+    let sub_workflow_id = get_task(&workflow, "sub").sub_workflow_id.clone();
+    let sub_workflow = common::get_workflow(&engine, &sub_workflow_id);
+    assert_eq!(sub_workflow.status, WorkflowStatus::Failed);
+
+    let mut request = RerunWorkflowRequest::new(
+        InlineStr::new(),
+        get_task(&sub_workflow, "compute").task_id.clone(),
+    );
+    request.task_input = Some(compute_task_input());
+    rerun(&engine, &workflow_id, request);
+
+    assert_eq!(
+        common::get_workflow(&engine, &sub_workflow_id).status,
+        WorkflowStatus::Completed
+    );
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert_eq!(get_task(&workflow, "sub").sub_workflow_id, sub_workflow_id);
+    assert_eq!(get_result(&workflow), 2);
+}
+
+#[test]
+fn rerun_sub_workflow_task_starts_new_sub_workflow() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_parent_workflow(&engine);
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    let sub_task = get_task(&workflow, "sub");
+    let sub_workflow_id = sub_task.sub_workflow_id.clone();
+
+    let request = RerunWorkflowRequest::new(InlineStr::new(), sub_task.task_id.clone());
+    rerun(&engine, &workflow_id, request);
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    let new_sub_workflow_id = get_task(&workflow, "sub").sub_workflow_id.clone();
+    assert!(!new_sub_workflow_id.is_empty());
+    assert_ne!(new_sub_workflow_id, sub_workflow_id);
+    // the new sub workflow fails with the same input, the previous one is left as it was
+    assert_eq!(
+        common::get_workflow(&engine, &new_sub_workflow_id).status,
+        WorkflowStatus::Failed
+    );
+    assert_eq!(
+        common::get_workflow(&engine, &sub_workflow_id).status,
+        WorkflowStatus::Failed
+    );
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+}
+
+#[test]
+fn rerun_sub_workflow_resumes_parent() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_parent_workflow(&engine);
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    let sub_workflow_id = get_task(&workflow, "sub").sub_workflow_id.clone();
+    let sub_workflow = common::get_workflow(&engine, &sub_workflow_id);
+
+    let mut request = RerunWorkflowRequest::new(
+        InlineStr::new(),
+        get_task(&sub_workflow, "compute").task_id.clone(),
+    );
+    request.task_input = Some(compute_task_input());
+    rerun(&engine, &sub_workflow_id, request);
+
+    assert_eq!(
+        common::get_workflow(&engine, &sub_workflow_id).status,
+        WorkflowStatus::Completed
+    );
+    // the parent waits for the rerun sub workflow again, and completes along with it
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert_eq!(get_result(&workflow), 2);
+}
+
+#[test]
+fn rerun_requires_known_task() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([compute_task()]),
+        serde_json::json!({ "value": "one" }),
+    );
+
+    let _guard = engine.enter();
+    let request = RerunWorkflowRequest::new(InlineStr::new(), "missing".into());
+    assert!(WorkflowService::rerun_workflow(&workflow_id, request).is_err());
+}
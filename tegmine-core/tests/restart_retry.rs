@@ -0,0 +1,284 @@
+use tegmine_common::prelude::*;
+use tegmine_common::{TaskResultStatus, WorkflowDef};
+use tegmine_core::{MetadataService, TaskStatus, TegmineEngine, WorkflowService, WorkflowStatus};
+
+mod common;
+
+fn wait_task() -> serde_json::Value {
+    serde_json::json!({
+        "name": "wait",
+        "taskReferenceName": "wait",
+        "type": "WAIT",
+        "inputParameters": {}
+    })
+}
+
+fn start_workflow(engine: &TegmineEngine, workflow_def: serde_json::Value) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": workflow_def["name"],
+        "workflowDef": workflow_def,
+        "input": {}
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+fn signal(
+    engine: &TegmineEngine,
+    workflow_id: &InlineStr,
+    task_reference_name: &str,
+    status: TaskResultStatus,
+) {
+    let _guard = engine.enter();
+    WorkflowService::signal(workflow_id, task_reference_name, status, HashMap::default())
+        .expect("signal failed");
+    common::evaluate(engine);
+}
+
+#[test]
+fn restart_completed_workflow() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!({
+            "name": "restart_workflow",
+            "version": 1,
+            "tasks": [wait_task()],
+            "outputParameters": {
+                "done": true
+            }
+        }),
+    );
+    signal(&engine, &workflow_id, "wait", TaskResultStatus::Completed);
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let wait_task_id = workflow
+        .tasks
+        .front()
+        .expect("no WAIT task")
+        .task_id
+        .clone();
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::restart_workflow(&workflow_id, false).expect("restart_workflow failed");
+        common::evaluate(&engine);
+    }
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Running);
+    assert!(workflow.output.is_empty());
+    assert_eq!(workflow.tasks.len(), 1);
+    let task = workflow.tasks.front().expect("no WAIT task");
+    assert_ne!(task.task_id, wait_task_id);
+    assert_eq!(task.status, TaskStatus::InProgress);
+
+    signal(&engine, &workflow_id, "wait", TaskResultStatus::Completed);
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Completed
+    );
+}
+
+#[test]
+fn restart_requires_restartable_definition() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!({
+            "name": "restart_workflow",
+            "version": 1,
+            "restartable": false,
+            "tasks": [wait_task()]
+        }),
+    );
+
+    {
+        let _guard = engine.enter();
+        // a running workflow can not be restarted
+        assert!(WorkflowService::restart_workflow(&workflow_id, false).is_err());
+    }
+
+    signal(&engine, &workflow_id, "wait", TaskResultStatus::Completed);
+    let _guard = engine.enter();
+    assert!(WorkflowService::restart_workflow(&workflow_id, false).is_err());
+}
+
+#[test]
+fn restart_with_latest_definition() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!({
+            "name": "restart_workflow",
+            "version": 1,
+            "tasks": [wait_task()]
+        }),
+    );
+    signal(&engine, &workflow_id, "wait", TaskResultStatus::Failed);
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Failed
+    );
+
+    {
+        let _guard = engine.enter();
+        let workflow_def = serde_json::json!({
+            "name": "restart_workflow",
+            "version": 2,
+            "tasks": [
+                {
+                    "name": "set_restarted",
+                    "taskReferenceName": "set_restarted",
+                    "type": "SET_VARIABLE",
+                    "inputParameters": {
+                        "restarted": true
+                    }
+                }
+            ]
+        });
+        let workflow_def = WorkflowDef::try_from(&workflow_def).expect("parse WorkflowDef failed");
+        MetadataService::register_workflow_def(workflow_def).expect("register_workflow_def failed");
+        WorkflowService::restart_workflow(&workflow_id, true).expect("restart_workflow failed");
+        common::evaluate(&engine);
+    }
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert_eq!(workflow.workflow_definition.version, 2);
+    assert!(workflow
+        .variables
+        .get("restarted")
+        .and_then(|x| x.as_bool().ok())
+        .expect("no restarted variable"));
+}
+
+#[test]
+fn retry_failed_task() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!({
+            "name": "retry_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "set_start",
+                    "taskReferenceName": "set_start",
+                    "type": "SET_VARIABLE",
+                    "inputParameters": {
+                        "started": true
+                    }
+                },
+                wait_task()
+            ]
+        }),
+    );
+    signal(&engine, &workflow_id, "wait", TaskResultStatus::Failed);
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+    assert_eq!(workflow.last_retried_time, 0);
+    let failed_task_id = workflow.tasks.back().expect("no WAIT task").task_id.clone();
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::retry_workflow(&workflow_id, false).expect("retry_workflow failed");
+        common::evaluate(&engine);
+    }
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Running);
+    assert!(workflow.last_retried_time > 0);
+    // only the failed task is rescheduled
+    assert_eq!(workflow.tasks.len(), 3);
+    let failed_task = workflow
+        .tasks
+        .iter()
+        .find(|x| x.task_id == failed_task_id)
+        .expect("no failed task");
+    assert!(failed_task.retried);
+    let retried_task = workflow.tasks.back().expect("no retried task");
+    assert_eq!(retried_task.retried_task_id, failed_task_id);
+    assert_eq!(retried_task.retry_count, 1);
+    assert_eq!(retried_task.status, TaskStatus::InProgress);
+
+    signal(&engine, &workflow_id, "wait", TaskResultStatus::Completed);
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Completed
+    );
+
+    // nothing left to retry
+    let _guard = engine.enter();
+    assert!(WorkflowService::retry_workflow(&workflow_id, false).is_err());
+}
+
+#[test]
+fn retry_resumes_failed_sub_workflow() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!({
+            "name": "parent_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "sub",
+                    "taskReferenceName": "sub",
+                    "type": "SUB_WORKFLOW",
+                    "inputParameters": {},
+                    "subWorkflowParam": {
+                        "workflowDefinition": {
+                            "name": "child_workflow",
+                            "version": 1,
+                            "tasks": [wait_task()]
+                        }
+                    }
+                }
+            ]
+        }),
+    );
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    let sub_workflow_id = workflow
+        .tasks
+        .front()
+        .expect("no SUB_WORKFLOW task")
+        .sub_workflow_id
+        .clone();
+    signal(&engine, &sub_workflow_id, "wait", TaskResultStatus::Failed);
+    assert_eq!(
+        common::get_workflow(&engine, &sub_workflow_id).status,
+        WorkflowStatus::Failed
+    );
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Failed
+    );
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::retry_workflow(&workflow_id, true).expect("retry_workflow failed");
+        common::evaluate(&engine);
+    }
+    // the same sub workflow is resumed
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Running);
+    assert_eq!(workflow.tasks.len(), 1);
+    let task = workflow.tasks.front().expect("no SUB_WORKFLOW task");
+    assert_eq!(task.status, TaskStatus::InProgress);
+    assert_eq!(task.sub_workflow_id, sub_workflow_id);
+    let sub_workflow = common::get_workflow(&engine, &sub_workflow_id);
+    assert_eq!(sub_workflow.status, WorkflowStatus::Running);
+    assert_eq!(sub_workflow.tasks.len(), 2);
+
+    signal(
+        &engine,
+        &sub_workflow_id,
+        "wait",
+        TaskResultStatus::Completed,
+    );
+    assert_eq!(
+        common::get_workflow(&engine, &sub_workflow_id).status,
+        WorkflowStatus::Completed
+    );
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Completed
+    );
+}
@@ -0,0 +1,155 @@
+use tegmine_common::prelude::*;
+use tegmine_common::TaskResultStatus;
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowService, WorkflowStatus};
+
+mod common;
+
+fn start_workflow(engine: &TegmineEngine, tasks: serde_json::Value) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "signal_workflow",
+        "workflowDef": {
+            "name": "signal_workflow",
+            "version": 1,
+            "tasks": tasks
+        },
+        "input": {}
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+fn signal(
+    engine: &TegmineEngine,
+    workflow_id: &InlineStr,
+    task_reference_name: &str,
+    output: serde_json::Value,
+) -> TegResult<InlineStr> {
+    let _guard = engine.enter();
+    let output = match Object::from_json(&output) {
+        Object::Map(output) => output,
+        _ => HashMap::default(),
+    };
+    let task_id = WorkflowService::signal(
+        workflow_id,
+        task_reference_name,
+        TaskResultStatus::Completed,
+        output,
+    )?;
+    common::evaluate(engine);
+    Ok(task_id)
+}
+
+#[test]
+fn signal_completes_wait_task_with_output() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "wait",
+                "taskReferenceName": "wait",
+                "type": "WAIT",
+                "inputParameters": {}
+            },
+            {
+                "name": "set_approved",
+                "taskReferenceName": "set_approved",
+                "type": "SET_VARIABLE",
+                "inputParameters": {
+                    "approved": "${wait.output.approved}"
+                }
+            }
+        ]),
+    );
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Running
+    );
+
+    let task_id = signal(
+        &engine,
+        &workflow_id,
+        "wait",
+        serde_json::json!({ "approved": true }),
+    )
+    .expect("signal failed");
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let task = workflow.tasks.front().expect("no WAIT task");
+    assert_eq!(task.task_id, task_id);
+    assert_eq!(task.status, TaskStatus::Completed);
+    assert!(workflow
+        .variables
+        .get("approved")
+        .and_then(|x| x.as_bool().ok())
+        .expect("no approved variable"));
+
+    // the task is no longer pending
+    assert!(signal(&engine, &workflow_id, "wait", serde_json::json!({})).is_err());
+}
+
+#[test]
+fn signal_completes_latest_iteration_in_loop() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "loop",
+                "taskReferenceName": "loop",
+                "type": "DO_WHILE",
+                "inputParameters": {},
+                "loopCondition": "$.loop['iteration'] < 2",
+                "loopOver": [
+                    {
+                        "name": "wait",
+                        "taskReferenceName": "wait",
+                        "type": "WAIT",
+                        "inputParameters": {}
+                    }
+                ]
+            }
+        ]),
+    );
+
+    signal(&engine, &workflow_id, "wait", serde_json::json!({})).expect("signal failed");
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Running);
+    let task = workflow
+        .get_task_by_ref_name("wait__2")
+        .expect("get_task_by_ref_name failed")
+        .expect("no second iteration");
+    assert_eq!(task.status, TaskStatus::InProgress);
+
+    signal(&engine, &workflow_id, "wait", serde_json::json!({})).expect("signal failed");
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Completed
+    );
+}
+
+#[test]
+fn signal_rejects_worker_tasks() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "worker",
+                "taskReferenceName": "worker",
+                "type": "SIMPLE",
+                "inputParameters": {},
+                "taskDefinition": {
+                    "name": "worker",
+                    "retryLogic": "FIXED"
+                }
+            }
+        ]),
+    );
+
+    assert!(signal(&engine, &workflow_id, "worker", serde_json::json!({})).is_err());
+    assert!(signal(&engine, &workflow_id, "missing", serde_json::json!({})).is_err());
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Running
+    );
+}
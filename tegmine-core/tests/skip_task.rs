@@ -0,0 +1,152 @@
+use tegmine_common::prelude::*;
+use tegmine_common::{SkipTaskRequest, TaskResultStatus};
+use tegmine_core::{TaskStatus, TegmineEngine, WorkflowModel, WorkflowService, WorkflowStatus};
+
+mod common;
+
+/// A SIMPLE task, which is never polled, followed by a SET_VARIABLE task referring to its output.
+fn start_workflow(engine: &TegmineEngine, first_tasks: serde_json::Value) -> InlineStr {
+    let mut tasks = first_tasks.as_array().cloned().unwrap_or_default();
+    tasks.push(serde_json::json!({
+        "name": "worker",
+        "taskReferenceName": "worker",
+        "type": "SIMPLE",
+        "inputParameters": {},
+        "taskDefinition": {
+            "name": "worker",
+            "retryLogic": "FIXED"
+        }
+    }));
+    tasks.push(serde_json::json!({
+        "name": "set_value",
+        "taskReferenceName": "set_value",
+        "type": "SET_VARIABLE",
+        "inputParameters": {
+            "value": "${worker.output.value}"
+        }
+    }));
+    let start_workflow_request = serde_json::json!({
+        "name": "skip_workflow",
+        "workflowDef": {
+            "name": "skip_workflow",
+            "version": 1,
+            "tasks": tasks
+        },
+        "input": {}
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+fn skip_task_request() -> SkipTaskRequest {
+    serde_json::json!({
+        "taskInput": {
+            "reason": "manual"
+        },
+        "taskOutput": {
+            "value": 7
+        }
+    })
+    .try_into()
+    .expect("parse SkipTaskRequest failed")
+}
+
+fn assert_skipped(workflow: &WorkflowModel) {
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let task = workflow
+        .get_task_by_ref_name("worker")
+        .expect("get_task_by_ref_name failed")
+        .expect("no SIMPLE task");
+    assert_eq!(task.status, TaskStatus::Skipped);
+    assert!(task.input_data.contains_key("reason"));
+    // the output of the skipped task is bound to the following task
+    assert_eq!(
+        workflow
+            .variables
+            .get("value")
+            .expect("no value variable")
+            .to_json(),
+        serde_json::json!(7)
+    );
+}
+
+#[test]
+fn skip_scheduled_task() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(&engine, serde_json::json!([]));
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(
+        workflow.tasks.front().expect("no SIMPLE task").status,
+        TaskStatus::Scheduled
+    );
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::skip_task_from_workflow(&workflow_id, "worker", skip_task_request())
+            .expect("skip_task_from_workflow failed");
+        common::evaluate(&engine);
+        // the skipped task is no longer polled
+        assert!(engine
+            .daos()
+            .queue_dao
+            .pop("worker", 1, 10)
+            .expect("pop failed")
+            .is_empty());
+    }
+    assert_skipped(&common::get_workflow(&engine, &workflow_id));
+}
+
+#[test]
+fn skip_task_not_scheduled_yet() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "wait",
+                "taskReferenceName": "wait",
+                "type": "WAIT",
+                "inputParameters": {}
+            }
+        ]),
+    );
+
+    let _guard = engine.enter();
+    WorkflowService::skip_task_from_workflow(&workflow_id, "worker", skip_task_request())
+        .expect("skip_task_from_workflow failed");
+    // a task can only be skipped once
+    assert!(
+        WorkflowService::skip_task_from_workflow(&workflow_id, "worker", skip_task_request())
+            .is_err()
+    );
+    WorkflowService::signal(
+        &workflow_id,
+        "wait",
+        TaskResultStatus::Completed,
+        HashMap::default(),
+    )
+    .expect("signal failed");
+    common::evaluate(&engine);
+    drop(_guard);
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_skipped(&workflow);
+    assert_eq!(workflow.tasks.len(), 3);
+}
+
+#[test]
+fn skip_requires_known_task_of_running_workflow() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(&engine, serde_json::json!([]));
+
+    let _guard = engine.enter();
+    assert!(
+        WorkflowService::skip_task_from_workflow(&workflow_id, "missing", skip_task_request())
+            .is_err()
+    );
+
+    WorkflowService::pause_workflow(&workflow_id).expect("pause_workflow failed");
+    assert!(
+        WorkflowService::skip_task_from_workflow(&workflow_id, "worker", skip_task_request())
+            .is_err()
+    );
+}
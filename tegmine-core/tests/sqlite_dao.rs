@@ -0,0 +1,147 @@
+use tegmine_common::{TaskDef, WorkflowDef};
+use tegmine_core::{
+    ExecutionDao, MetadataDao, QueueDao, SqliteDataSource, SqliteExecutionDao, SqliteMetadataDao,
+    SqliteQueueDao, TaskModel, TaskStatus,
+};
+
+fn data_source(dir: &tempfile::TempDir) -> std::sync::Arc<SqliteDataSource> {
+    let url = format!("sqlite://{}", dir.path().join("tegmine.db").display());
+    SqliteDataSource::connect(&url).expect("connect sqlite failed")
+}
+
+#[test]
+fn sqlite_metadata_dao() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let metadata_dao = SqliteMetadataDao::new(data_source(&dir));
+
+    let task_def: serde_json::Value = serde_json::from_str(
+        r#"{ "name": "encode_task", "retryLogic": "FIXED", "concurrentExecLimit": 10 }"#,
+    )
+    .expect("parse json failed");
+    let task_def = TaskDef::try_from(&task_def).expect("parse TaskDef failed");
+    metadata_dao
+        .create_task_def(task_def)
+        .expect("create_task_def failed");
+    let task_def = metadata_dao
+        .get_task_def(&"encode_task".into())
+        .expect("get_task_def failed")
+        .expect("task def not found");
+    assert_eq!(task_def.concurrent_exec_limit, Some(10));
+
+    let workflow_def: serde_json::Value = serde_json::from_str(
+        r#"{
+            "name": "sqlite_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "encode_task",
+                    "taskReferenceName": "encode_task_ref",
+                    "inputParameters": { "id": "${workflow.input.id}" },
+                    "type": "SIMPLE"
+                }
+            ]
+        }"#,
+    )
+    .expect("parse json failed");
+    let workflow_def = WorkflowDef::try_from(&workflow_def).expect("parse WorkflowDef failed");
+    metadata_dao
+        .create_workflow_def(workflow_def.clone())
+        .expect("create_workflow_def failed");
+    assert!(metadata_dao.create_workflow_def(workflow_def).is_err());
+    assert!(metadata_dao
+        .check_workflow_def_endabled("sqlite_workflow")
+        .expect("check_workflow_def_endabled failed"));
+
+    metadata_dao
+        .toggle_workflow_def(&"sqlite_workflow".into(), false)
+        .expect("toggle_workflow_def failed");
+    assert!(!metadata_dao
+        .check_workflow_def_endabled("sqlite_workflow")
+        .expect("check_workflow_def_endabled failed"));
+
+    let latest = metadata_dao
+        .get_latest_workflow_def(&"sqlite_workflow".into())
+        .expect("get_latest_workflow_def failed")
+        .expect("workflow def not found");
+    assert_eq!(latest.tasks[0].task_reference_name, "encode_task_ref");
+}
+
+#[test]
+fn sqlite_execution_dao() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let execution_dao = SqliteExecutionDao::new(data_source(&dir));
+
+    let mut task = TaskModel::new(TaskStatus::Scheduled);
+    task.task_id = "task_1".into();
+    task.workflow_instance_id = "workflow_1".into();
+    task.reference_task_name = "encode_task_ref".into();
+    task.task_def_name = "encode_task".into();
+    task.input_data.insert("id".into(), 1.into());
+
+    let mut duplicate = task.clone();
+    duplicate.task_id = "task_2".into();
+
+    execution_dao
+        .create_tasks(&mut [&mut task, &mut duplicate])
+        .expect("create_tasks failed");
+    assert!(task.scheduled_time > 0);
+
+    // the second task has the same task key and is not scheduled again
+    let tasks = execution_dao
+        .get_tasks_for_workflow(&"workflow_1".into())
+        .expect("get_tasks_for_workflow failed");
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].task_id, "task_1");
+
+    task.status = TaskStatus::Completed;
+    task.output_data.insert("result".into(), "done".into());
+    execution_dao
+        .update_task(&mut task)
+        .expect("update_task failed");
+    let stored = execution_dao
+        .get_task(&"task_1".into())
+        .expect("get_task failed")
+        .expect("task not found");
+    assert_eq!(stored.status, TaskStatus::Completed);
+    assert_eq!(
+        stored
+            .output_data
+            .get("result")
+            .and_then(|x| x.as_string().ok())
+            .map(|x| x.as_str()),
+        Some("done")
+    );
+
+    assert!(execution_dao
+        .remove_task(&"task_1".into())
+        .expect("remove_task failed"));
+    assert!(execution_dao
+        .get_task(&"task_1".into())
+        .expect("get_task failed")
+        .is_none());
+}
+
+#[test]
+fn sqlite_queue_dao() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let queue_dao = SqliteQueueDao::new(data_source(&dir));
+
+    queue_dao
+        .push("sqlite_queue", &"message_1".into(), 0, 0)
+        .expect("push failed");
+    queue_dao
+        .push("sqlite_queue", &"message_2".into(), 0, 3600)
+        .expect("push failed");
+
+    let popped = queue_dao.pop("sqlite_queue", 2, 50).expect("pop failed");
+    assert_eq!(popped, vec!["message_1"]);
+
+    // popped messages are not delivered twice
+    assert!(queue_dao
+        .pop("sqlite_queue", 2, 0)
+        .expect("pop failed")
+        .is_empty());
+    assert!(queue_dao
+        .ack("sqlite_queue", &"message_1".into())
+        .expect("ack failed"));
+}
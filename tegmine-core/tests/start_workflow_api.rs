@@ -1,5 +1,7 @@
 use tegmine_common::StartWorkflowRequest;
-use tegmine_core::{ExecutionService, WorkflowService};
+use tegmine_core::{
+    ExecutionService, MetadataService, TaskService, TegmineEngine, WorkflowService,
+};
 
 #[test]
 fn start_workflow() {
@@ -75,11 +77,13 @@ fn start_workflow() {
         .try_into()
         .expect("parse StartWorkflowRequest failed");
 
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
     let workflow_instance_id =
         WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
     eprintln!("workflow_instance_id is: {}", workflow_instance_id);
 
-    tegmine_core::evaluate_once().expect("evaluation failed");
+    engine.evaluate_once().expect("evaluation failed");
 
     let (workflow_status, workflow) =
         ExecutionService::get_execution_status(workflow_instance_id.as_str(), false)
@@ -176,12 +180,14 @@ fn start_workflow_javascript() {
         .try_into()
         .expect("parse StartWorkflowRequest failed");
 
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
     let workflow_instance_id =
         WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
     eprintln!("workflow_instance_id is: {}", workflow_instance_id);
 
     tegmine_core::initialize();
-    tegmine_core::evaluate_once().expect("evaluation failed");
+    engine.evaluate_once().expect("evaluation failed");
 
     let (workflow_status, workflow) =
         ExecutionService::get_execution_status(workflow_instance_id.as_str(), false)
@@ -223,7 +229,41 @@ fn start_workflow_registered() {
     let start_workflow_request: StartWorkflowRequest = start_workflow_request
         .try_into()
         .expect("parse StartWorkflowRequest failed");
+    let engine = TegmineEngine::default();
+    let _guard = engine.enter();
     let workflow_instance_id =
         WorkflowService::start_workflow(start_workflow_request).expect("start_workflow failed");
     eprintln!("workflow_instance_id is: {}", workflow_instance_id);
 }
+
+#[test]
+fn services_without_entered_engine() {
+    let start_workflow_request: StartWorkflowRequest = serde_json::json!({
+        "name": "my_adhoc_unregistered_workflow",
+        "workflowDef": {
+            "name": "my_adhoc_unregistered_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "set_name",
+                    "taskReferenceName": "set_name",
+                    "type": "SET_VARIABLE",
+                    "inputParameters": {
+                        "name": "Foo"
+                    }
+                }
+            ]
+        },
+        "input": {}
+    })
+    .try_into()
+    .expect("parse StartWorkflowRequest failed");
+
+    // the services return an error instead of panicking when no engine is entered
+    let _engine = TegmineEngine::default();
+    assert!(WorkflowService::start_workflow(start_workflow_request).is_err());
+    assert!(WorkflowService::pause_workflow("workflow_id").is_err());
+    assert!(TaskService::batch_poll("task_type", "worker_id", "", 1, 100).is_err());
+    assert!(ExecutionService::get_execution_status("workflow_id", false).is_err());
+    assert!(MetadataService::unregister_workflow_def(&"my_workflow".into(), 1).is_err());
+}
@@ -0,0 +1,127 @@
+use tegmine_common::prelude::*;
+use tegmine_common::StartWorkflowRequest;
+use tegmine_core::{TegmineEngine, WorkflowStatus};
+
+mod common;
+
+fn start_workflow(engine: &TegmineEngine, start_workflow: serde_json::Value) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "starter_workflow",
+        "workflowDef": {
+            "name": "starter_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "start",
+                    "taskReferenceName": "start",
+                    "type": "START_WORKFLOW",
+                    "inputParameters": {
+                        "startWorkflow": start_workflow
+                    }
+                }
+            ],
+            "outputParameters": {
+                "workflowId": "${start.output.workflowId}"
+            }
+        },
+        "input": {
+            "who": "tegmine"
+        }
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+#[test]
+fn start_workflow_task_completes_with_workflow_id() {
+    let engine = TegmineEngine::default();
+    engine
+        .init_system_task_executor()
+        .expect("init_system_task_executor failed");
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!({
+            "name": "started_workflow",
+            "workflowDef": {
+                "name": "started_workflow",
+                "version": 1,
+                "tasks": [
+                    {
+                        "name": "set_greeting",
+                        "taskReferenceName": "set_greeting",
+                        "type": "SET_VARIABLE",
+                        "inputParameters": {
+                            "greeting": "${workflow.input.who}"
+                        }
+                    }
+                ]
+            },
+            "input": {
+                "who": "${workflow.input.who}"
+            },
+            "correlationId": "started_by_starter"
+        }),
+    );
+
+    let workflow = common::wait_for_terminal(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let started_workflow_id = workflow
+        .output
+        .get("workflowId")
+        .expect("no started workflow id")
+        .as_string()
+        .expect("no valid started workflow id")
+        .clone();
+    assert_ne!(started_workflow_id, workflow_id);
+
+    let started_workflow = common::wait_for_terminal(&engine, &started_workflow_id);
+    assert_eq!(started_workflow.status, WorkflowStatus::Completed);
+    assert_eq!(
+        started_workflow.correlation_id.as_str(),
+        "started_by_starter"
+    );
+    // START_WORKFLOW does not create a parent-child relationship
+    assert!(started_workflow.parent_workflow_id.is_empty());
+}
+
+#[test]
+fn start_workflow_task_fails_without_definition() {
+    let engine = TegmineEngine::default();
+    engine
+        .init_system_task_executor()
+        .expect("init_system_task_executor failed");
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!({
+            "name": "not_registered_workflow",
+            "input": {
+                "who": "${workflow.input.who}"
+            }
+        }),
+    );
+
+    let workflow = common::wait_for_terminal(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+    let reason = workflow.reason_for_incompletion;
+    assert!(reason.contains("not_registered_workflow"), "{}", reason);
+}
+
+#[test]
+fn start_workflow_task_requires_start_workflow_input() {
+    let start_workflow_request: Result<StartWorkflowRequest, _> = serde_json::json!({
+        "name": "starter_workflow",
+        "workflowDef": {
+            "name": "starter_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "start",
+                    "taskReferenceName": "start",
+                    "type": "START_WORKFLOW",
+                    "inputParameters": {}
+                }
+            ]
+        }
+    })
+    .try_into();
+    assert!(start_workflow_request.is_err());
+}
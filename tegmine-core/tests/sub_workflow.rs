@@ -0,0 +1,147 @@
+use tegmine_common::prelude::*;
+use tegmine_core::{TegmineEngine, WorkflowService, WorkflowStatus};
+
+mod common;
+
+fn start_workflow(engine: &TegmineEngine, sub_workflow_tasks: serde_json::Value) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "parent_workflow",
+        "workflowDef": {
+            "name": "parent_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "sub",
+                    "taskReferenceName": "sub",
+                    "type": "SUB_WORKFLOW",
+                    "inputParameters": {
+                        "who": "${workflow.input.who}"
+                    },
+                    "subWorkflowParam": {
+                        "workflowDefinition": {
+                            "name": "child_workflow",
+                            "version": 1,
+                            "tasks": sub_workflow_tasks,
+                            "outputParameters": {
+                                "greeting": "${workflow.variables.greeting}"
+                            }
+                        }
+                    }
+                }
+            ],
+            "outputParameters": {
+                "greeting": "${sub.output.greeting}",
+                "subWorkflowId": "${sub.output.subWorkflowId}"
+            }
+        },
+        "input": {
+            "who": "tegmine"
+        }
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+#[test]
+fn sub_workflow_output_propagates_to_parent() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "set_greeting",
+                "taskReferenceName": "set_greeting",
+                "type": "SET_VARIABLE",
+                "inputParameters": {
+                    "greeting": "${workflow.input.who}"
+                }
+            }
+        ]),
+    );
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let output = workflow.output;
+    assert_eq!(
+        output
+            .get("greeting")
+            .expect("no output")
+            .as_string()
+            .expect("no valid output"),
+        "tegmine"
+    );
+
+    let sub_workflow_id = output
+        .get("subWorkflowId")
+        .expect("no sub workflow id")
+        .as_string()
+        .expect("no valid sub workflow id");
+    let sub_workflow = common::get_workflow(&engine, sub_workflow_id);
+    assert_eq!(sub_workflow.status, WorkflowStatus::Completed);
+    assert_eq!(sub_workflow.parent_workflow_id, workflow_id);
+    assert!(!sub_workflow.parent_workflow_task_id.is_empty());
+}
+
+#[test]
+fn sub_workflow_failure_propagates_to_parent() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "fail",
+                "taskReferenceName": "fail",
+                "type": "TERMINATE",
+                "inputParameters": {
+                    "terminationStatus": "FAILED",
+                    "terminationReason": "child failed"
+                }
+            }
+        ]),
+    );
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+    let reason = workflow.reason_for_incompletion;
+    assert!(reason.contains("child failed"), "{}", reason);
+}
+
+#[test]
+fn terminate_parent_terminates_sub_workflow() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "wait",
+                "taskReferenceName": "wait",
+                "type": "WAIT",
+                "inputParameters": {}
+            }
+        ]),
+    );
+    let sub_workflow_id = common::get_workflow(&engine, &workflow_id)
+        .tasks
+        .front()
+        .expect("no SUB_WORKFLOW task")
+        .sub_workflow_id
+        .clone();
+    assert_eq!(
+        common::get_workflow(&engine, &sub_workflow_id).status,
+        WorkflowStatus::Running
+    );
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::terminate_workflow(&workflow_id, "parent terminated")
+            .expect("terminate_workflow failed");
+        common::evaluate(&engine);
+    }
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Terminated
+    );
+    assert_eq!(
+        common::get_workflow(&engine, &sub_workflow_id).status,
+        WorkflowStatus::Terminated
+    );
+}
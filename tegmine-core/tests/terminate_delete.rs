@@ -0,0 +1,177 @@
+use std::fs::File;
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use tegmine_common::prelude::*;
+use tegmine_common::TaskResultStatus;
+use tegmine_core::{Daos, Properties, TaskStatus, TegmineEngine, WorkflowService, WorkflowStatus};
+
+mod common;
+
+fn wait_task() -> serde_json::Value {
+    serde_json::json!({
+        "name": "wait",
+        "taskReferenceName": "wait",
+        "type": "WAIT",
+        "inputParameters": {}
+    })
+}
+
+fn start_workflow(engine: &TegmineEngine, tasks: serde_json::Value) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "terminate_workflow",
+        "workflowDef": {
+            "name": "terminate_workflow",
+            "version": 1,
+            "tasks": tasks
+        },
+        "input": {}
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+fn read_archive(path: &Path) -> serde_json::Value {
+    let file = File::open(path).expect("open archive failed");
+    serde_json::from_reader(GzDecoder::new(file)).expect("parse archive failed")
+}
+
+#[test]
+fn terminate_cascades_to_sub_workflow() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(
+        &engine,
+        serde_json::json!([
+            {
+                "name": "sub",
+                "taskReferenceName": "sub",
+                "type": "SUB_WORKFLOW",
+                "inputParameters": {},
+                "subWorkflowParam": {
+                    "workflowDefinition": {
+                        "name": "child_workflow",
+                        "version": 1,
+                        "tasks": [wait_task()]
+                    }
+                }
+            }
+        ]),
+    );
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    let sub_workflow_id = workflow
+        .tasks
+        .front()
+        .expect("no SUB_WORKFLOW task")
+        .sub_workflow_id
+        .clone();
+    assert_eq!(
+        common::get_workflow(&engine, &sub_workflow_id).status,
+        WorkflowStatus::Running
+    );
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::terminate_workflow(&workflow_id, "no longer needed")
+            .expect("terminate_workflow failed");
+        common::evaluate(&engine);
+    }
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Terminated);
+    assert_eq!(
+        workflow.reason_for_incompletion.as_str(),
+        "no longer needed"
+    );
+    assert_eq!(
+        workflow.tasks.front().expect("no SUB_WORKFLOW task").status,
+        TaskStatus::Canceled
+    );
+
+    // the in-flight tasks of the sub workflow are canceled as well
+    let sub_workflow = common::get_workflow(&engine, &sub_workflow_id);
+    assert_eq!(sub_workflow.status, WorkflowStatus::Terminated);
+    assert!(sub_workflow
+        .reason_for_incompletion
+        .contains("no longer needed"));
+    assert_eq!(
+        sub_workflow.tasks.front().expect("no WAIT task").status,
+        TaskStatus::Canceled
+    );
+
+    // a terminal workflow can not be terminated again
+    let _guard = engine.enter();
+    assert!(WorkflowService::terminate_workflow(&workflow_id, "again").is_err());
+}
+
+#[test]
+fn delete_workflow_with_archive() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let properties = Properties {
+        workflow_archive_dir: Some(dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let engine = TegmineEngine::new(properties, Daos::default());
+    let workflow_id = start_workflow(&engine, serde_json::json!([wait_task()]));
+    let archive_path = dir.path().join(format!("{}.json.gz", workflow_id));
+
+    {
+        let _guard = engine.enter();
+        // only the terminal workflows are archived
+        assert!(WorkflowService::delete_workflow(&workflow_id, true).is_err());
+        WorkflowService::signal(
+            &workflow_id,
+            "wait",
+            TaskResultStatus::Completed,
+            HashMap::default(),
+        )
+        .expect("signal failed");
+        common::evaluate(&engine);
+    }
+    assert!(common::find_workflow(&engine, &workflow_id).is_some());
+    assert!(!archive_path.exists());
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::delete_workflow(&workflow_id, true).expect("delete_workflow failed");
+    }
+    assert!(common::find_workflow(&engine, &workflow_id).is_none());
+
+    let record = read_archive(&archive_path);
+    assert_eq!(record["workflowId"], workflow_id.as_str());
+    assert_eq!(record["status"], WorkflowStatus::Completed.as_ref());
+    let tasks = record["tasks"].as_array().expect("no tasks");
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["referenceTaskName"], "wait");
+}
+
+#[test]
+fn delete_workflow_without_archive() {
+    let dir = tempfile::tempdir().expect("create temp dir failed");
+    let properties = Properties {
+        workflow_archive_dir: Some(dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let engine = TegmineEngine::new(properties, Daos::default());
+    let workflow_id = start_workflow(&engine, serde_json::json!([wait_task()]));
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::delete_workflow(&workflow_id, false).expect("delete_workflow failed");
+        assert!(WorkflowService::delete_workflow(&workflow_id, false).is_err());
+    }
+    assert!(common::find_workflow(&engine, &workflow_id).is_none());
+    assert!(!dir.path().join(format!("{}.json.gz", workflow_id)).exists());
+}
+
+#[test]
+fn delete_workflow_with_archive_requires_archive_dir() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_workflow(&engine, serde_json::json!([wait_task()]));
+
+    {
+        let _guard = engine.enter();
+        WorkflowService::terminate_workflow(&workflow_id, "no longer needed")
+            .expect("terminate_workflow failed");
+        // the workflow is kept as it can not be archived
+        assert!(WorkflowService::delete_workflow(&workflow_id, true).is_err());
+    }
+    assert!(common::find_workflow(&engine, &workflow_id).is_some());
+}
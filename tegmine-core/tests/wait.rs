@@ -0,0 +1,114 @@
+use std::time::{Duration, Instant};
+
+use tegmine_common::prelude::*;
+use tegmine_common::{TaskResult, TaskResultStatus};
+use tegmine_core::{ExecutionService, TaskStatus, TegmineEngine, WorkflowStatus};
+
+mod common;
+
+fn start_wait_workflow(engine: &TegmineEngine, input_parameters: serde_json::Value) -> InlineStr {
+    let start_workflow_request = serde_json::json!({
+        "name": "wait_workflow",
+        "workflowDef": {
+            "name": "wait_workflow",
+            "version": 1,
+            "tasks": [
+                {
+                    "name": "wait",
+                    "taskReferenceName": "wait",
+                    "type": "WAIT",
+                    "inputParameters": input_parameters
+                }
+            ]
+        },
+        "input": {}
+    });
+    common::start_workflow(engine, start_workflow_request)
+}
+
+#[test]
+fn wait_completes_after_duration() {
+    let engine = TegmineEngine::default();
+    let started = Instant::now();
+    let workflow_id = start_wait_workflow(&engine, serde_json::json!({ "duration": "1s" }));
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Running);
+    let task = workflow.tasks.front().expect("no WAIT task");
+    assert_eq!(task.status, TaskStatus::InProgress);
+    assert!(task.wait_timeout > 0);
+
+    // the workflow is decided again by the sweeper once the wait elapses
+    engine.spawn_sweeper().expect("spawn_sweeper failed");
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut workflow = common::get_workflow(&engine, &workflow_id);
+    while !workflow.status.is_terminal() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+        workflow = common::get_workflow(&engine, &workflow_id);
+    }
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
+
+#[test]
+fn wait_until_timestamp_in_time_zone() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_wait_workflow(
+        &engine,
+        serde_json::json!({ "until": "2020-01-01 09:00 America/Los_Angeles" }),
+    );
+
+    // the timestamp has passed, the task completes at once
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Completed);
+    let task = workflow.tasks.front().expect("no WAIT task");
+    assert_eq!(task.status, TaskStatus::Completed);
+    // 2020-01-01 17:00 UTC
+    assert_eq!(task.wait_timeout, 1577898000000);
+}
+
+#[test]
+fn wait_indefinitely_until_updated() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_wait_workflow(&engine, serde_json::json!({}));
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Running);
+    let task = workflow.tasks.front().expect("no WAIT task");
+    assert_eq!(task.status, TaskStatus::InProgress);
+    assert_eq!(task.wait_timeout, 0);
+
+    {
+        let _guard = engine.enter();
+        ExecutionService::update_task(TaskResult {
+            workflow_instance_id: workflow_id.clone(),
+            task_id: task.task_id.clone(),
+            reason_for_incompletion: InlineStr::new(),
+            callback_after_seconds: 0,
+            worker_id: InlineStr::new(),
+            status: TaskResultStatus::Completed,
+            output_data: HashMap::default(),
+            output_message: Object::Null,
+            logs: Vec::default(),
+            external_output_payload_storage_path: InlineStr::new(),
+            sub_workflow_id: InlineStr::new(),
+            extend_lease: false,
+        })
+        .expect("update_task failed");
+    }
+    assert_eq!(
+        common::get_workflow(&engine, &workflow_id).status,
+        WorkflowStatus::Completed
+    );
+}
+
+#[test]
+fn wait_fails_on_invalid_duration() {
+    let engine = TegmineEngine::default();
+    let workflow_id = start_wait_workflow(&engine, serde_json::json!({ "duration": "soon" }));
+
+    let workflow = common::get_workflow(&engine, &workflow_id);
+    assert_eq!(workflow.status, WorkflowStatus::Failed);
+    let task = workflow.tasks.front().expect("no WAIT task");
+    assert_eq!(task.status, TaskStatus::FailedWithTerminalError);
+}